@@ -0,0 +1,158 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::{error::unwrap_or_panic, Array};
+
+use super::check_same_shape2;
+
+/// The tolerance of an approximate comparison of two elements
+///
+/// Equal elements, including infinities with the same sign, always satisfy the tolerance and NaN never does.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    /// `|a - b| <= epsilon`
+    Absolute(f32),
+    /// `|a - b| <= epsilon * max(|a|, |b|)`
+    Relative(f32),
+    /// there are at most this many representable floats between the elements
+    Ulps(u32),
+}
+
+impl Tolerance {
+    pub fn is_satisfied(&self, a: f32, b: f32) -> bool {
+        if a == b {
+            return true;
+        }
+
+        if !a.is_finite() || !b.is_finite() {
+            return false;
+        }
+
+        match *self {
+            Tolerance::Absolute(epsilon) => (a - b).abs() <= epsilon,
+            Tolerance::Relative(epsilon) => (a - b).abs() <= epsilon * a.abs().max(b.abs()),
+            Tolerance::Ulps(max_ulps) => {
+                // floats with the same sign are ordered like their bits
+                a.is_sign_positive() == b.is_sign_positive()
+                    && a.to_bits().abs_diff(b.to_bits()) <= max_ulps
+            }
+        }
+    }
+}
+
+/// The first pair of elements that doesn't satisfy a tolerance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difference<const D: usize> {
+    pub index: [usize; D],
+    pub left: f32,
+    pub right: f32,
+}
+
+impl<const D: usize> Array<D> {
+    /// Find the first element in row-major order that doesn't satisfy the tolerance, the shapes must be the same
+    #[track_caller]
+    pub fn first_difference(&self, other: &Self, tolerance: Tolerance) -> Option<Difference<D>> {
+        unwrap_or_panic(check_same_shape2(self, other));
+
+        let left = self.to_elements();
+        let right = other.to_elements();
+        let flat_index = left
+            .iter()
+            .zip(right.iter())
+            .position(|(a, b)| !tolerance.is_satisfied(*a, *b))?;
+
+        let mut index = [0; D];
+        let mut remaining = flat_index;
+
+        for axis in (0..D).rev() {
+            index[axis] = remaining % self.shape[axis];
+            remaining /= self.shape[axis];
+        }
+
+        Some(Difference {
+            index,
+            left: left[flat_index],
+            right: right[flat_index],
+        })
+    }
+
+    /// Check that the shapes are the same and all elements satisfy the tolerance
+    pub fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool {
+        self.shape == other.shape && self.first_difference(other, tolerance).is_none()
+    }
+
+    /// Check that the shapes are the same and `|a - b| <= epsilon` for all elements
+    pub fn abs_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.approx_eq(other, Tolerance::Absolute(epsilon))
+    }
+
+    /// Check that the shapes are the same and `|a - b| <= epsilon * max(|a|, |b|)` for all elements
+    pub fn rel_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.approx_eq(other, Tolerance::Relative(epsilon))
+    }
+
+    /// Check that the shapes are the same and there are at most `max_ulps` representable floats between the elements
+    pub fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool {
+        self.approx_eq(other, Tolerance::Ulps(max_ulps))
+    }
+}
+
+/// Assert that two arrays have the same shape and that all elements are approximately equal,
+/// otherwise panic with the first differing index.
+///
+/// The tolerance is either `abs <= epsilon`, `rel <= epsilon`, `ulps <= max_ulps` or a `Tolerance`.
+#[macro_export]
+macro_rules! assert_array_approx_eq {
+    ($left:expr, $right:expr, abs <= $epsilon:expr $(,)?) => {
+        $crate::assert_array_approx_eq!($left, $right, $crate::Tolerance::Absolute($epsilon))
+    };
+    ($left:expr, $right:expr, rel <= $epsilon:expr $(,)?) => {
+        $crate::assert_array_approx_eq!($left, $right, $crate::Tolerance::Relative($epsilon))
+    };
+    ($left:expr, $right:expr, ulps <= $max_ulps:expr $(,)?) => {
+        $crate::assert_array_approx_eq!($left, $right, $crate::Tolerance::Ulps($max_ulps))
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        match (&$left, &$right, $tolerance) {
+            (left, right, tolerance) => {
+                if left.get_shape() != right.get_shape() {
+                    panic!(
+                        "assertion `left ≈ right` failed: the shapes don't match: {:?} != {:?}",
+                        left.get_shape(),
+                        right.get_shape()
+                    );
+                }
+
+                if let Some(difference) = left.first_difference(right, tolerance) {
+                    panic!(
+                        "assertion `left ≈ right` failed at index {:?}: {} != {} ({:?})",
+                        difference.index, difference.left, difference.right, tolerance
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Like `assert_array_approx_eq`, but only checked in debug builds
+#[macro_export]
+macro_rules! debug_assert_array_approx_eq {
+    ($($arguments:tt)*) => {
+        if cfg!(debug_assertions) {
+            $crate::assert_array_approx_eq!($($arguments)*);
+        }
+    };
+}
@@ -0,0 +1,354 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::arch::x86_64::{
+    __m512, __mmask16, _mm512_add_ps, _mm512_cmpeq_ps_mask, _mm512_cmple_ps_mask,
+    _mm512_cmplt_ps_mask, _mm512_cmpneq_ps_mask, _mm512_cmpnle_ps_mask, _mm512_cmpnlt_ps_mask,
+    _mm512_div_ps, _mm512_fmadd_ps, _mm512_max_ps, _mm512_min_ps, _mm512_mul_ps, _mm512_sub_ps,
+};
+
+use crate::{error::unwrap_or_panic, Array, Mask};
+
+use super::{array_to_m512, assert_same_shape2};
+
+/// Operations between every row or column of a matrix and a vector. The registers of a row vector are reused
+/// for every row and the elements of a column vector are splat into one register per row.
+impl Array<2> {
+    fn row_broadcast_in_place(
+        &mut self,
+        row: &Array<1>,
+        func: unsafe fn(__m512, __m512) -> __m512,
+    ) {
+        unwrap_or_panic(self.check_row_broadcast(row));
+        let registers_per_row = self.shape[1].div_ceil(16);
+
+        unsafe {
+            for registers in self.data.chunks_mut(registers_per_row) {
+                for (l, r) in registers.iter_mut().zip(row.data.iter()) {
+                    *l = func(*l, *r);
+                }
+            }
+        }
+    }
+
+    fn column_broadcast_in_place(
+        &mut self,
+        column: &Array<1>,
+        func: unsafe fn(__m512, __m512) -> __m512,
+    ) {
+        unwrap_or_panic(self.check_column_broadcast(column));
+        let registers_per_row = self.shape[1].div_ceil(16);
+
+        unsafe {
+            for (registers, value) in self
+                .data
+                .chunks_mut(registers_per_row)
+                .zip(column.row_elements(0))
+            {
+                let scalar = array_to_m512([*value; 16]);
+
+                for l in registers.iter_mut() {
+                    *l = func(*l, scalar);
+                }
+            }
+        }
+    }
+
+    fn compare_row_broadcast(
+        &self,
+        row: &Array<1>,
+        func: unsafe fn(__m512, __m512) -> __mmask16,
+    ) -> Mask<2> {
+        unwrap_or_panic(self.check_row_broadcast(row));
+        let registers_per_row = self.shape[1].div_ceil(16);
+        let mut masks = Vec::with_capacity(self.data.len());
+
+        unsafe {
+            for registers in self.data.chunks(registers_per_row) {
+                for (l, r) in registers.iter().zip(row.data.iter()) {
+                    masks.push(func(*l, *r));
+                }
+            }
+        }
+
+        Mask::new_from_data(self.shape, masks)
+    }
+
+    fn compare_column_broadcast(
+        &self,
+        column: &Array<1>,
+        func: unsafe fn(__m512, __m512) -> __mmask16,
+    ) -> Mask<2> {
+        unwrap_or_panic(self.check_column_broadcast(column));
+        let registers_per_row = self.shape[1].div_ceil(16);
+        let mut masks = Vec::with_capacity(self.data.len());
+
+        unsafe {
+            for (registers, value) in self
+                .data
+                .chunks(registers_per_row)
+                .zip(column.row_elements(0))
+            {
+                let scalar = array_to_m512([*value; 16]);
+
+                for l in registers.iter() {
+                    masks.push(func(*l, scalar));
+                }
+            }
+        }
+
+        Mask::new_from_data(self.shape, masks)
+    }
+
+    /// `self += a * row` where `row` is added to every row
+    pub fn fmadd_row_broadcast_in_place(&mut self, a: &Self, row: &Array<1>) {
+        assert_same_shape2(self, a);
+        unwrap_or_panic(self.check_row_broadcast(row));
+        let registers_per_row = self.shape[1].div_ceil(16);
+
+        unsafe {
+            for (c_registers, a_registers) in self
+                .data
+                .chunks_mut(registers_per_row)
+                .zip(a.data.chunks(registers_per_row))
+            {
+                for ((c, a), b) in c_registers
+                    .iter_mut()
+                    .zip(a_registers.iter())
+                    .zip(row.data.iter())
+                {
+                    *c = _mm512_fmadd_ps(*a, *b, *c);
+                }
+            }
+        }
+    }
+
+    /// `self += a * column` where `column` holds one factor per row
+    pub fn fmadd_column_broadcast_in_place(&mut self, a: &Self, column: &Array<1>) {
+        assert_same_shape2(self, a);
+        unwrap_or_panic(self.check_column_broadcast(column));
+        let registers_per_row = self.shape[1].div_ceil(16);
+
+        unsafe {
+            for ((c_registers, a_registers), value) in self
+                .data
+                .chunks_mut(registers_per_row)
+                .zip(a.data.chunks(registers_per_row))
+                .zip(column.row_elements(0))
+            {
+                let scalar = array_to_m512([*value; 16]);
+
+                for (c, a) in c_registers.iter_mut().zip(a_registers.iter()) {
+                    *c = _mm512_fmadd_ps(*a, scalar, *c);
+                }
+            }
+        }
+    }
+
+    pub fn add_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.add_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn add_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, _mm512_add_ps);
+    }
+
+    pub fn add_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.add_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn add_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, _mm512_add_ps);
+    }
+
+    pub fn sub_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.sub_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn sub_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, _mm512_sub_ps);
+    }
+
+    pub fn sub_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.sub_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn sub_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, _mm512_sub_ps);
+    }
+
+    pub fn mul_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.mul_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn mul_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, _mm512_mul_ps);
+    }
+
+    pub fn mul_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.mul_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn mul_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, _mm512_mul_ps);
+    }
+
+    pub fn div_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.div_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn div_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, _mm512_div_ps);
+    }
+
+    pub fn div_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.div_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn div_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, _mm512_div_ps);
+    }
+
+    pub fn max_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.max_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn max_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, _mm512_max_ps);
+    }
+
+    pub fn max_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.max_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn max_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, _mm512_max_ps);
+    }
+
+    pub fn min_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.min_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn min_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, _mm512_min_ps);
+    }
+
+    pub fn min_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.min_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn min_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, _mm512_min_ps);
+    }
+
+    pub fn fmadd_row_broadcast(&self, a: &Self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.fmadd_row_broadcast_in_place(a, row);
+
+        new_array
+    }
+
+    pub fn fmadd_column_broadcast(&self, a: &Self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.fmadd_column_broadcast_in_place(a, column);
+
+        new_array
+    }
+
+    pub fn compare_equal_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, _mm512_cmpeq_ps_mask)
+    }
+
+    pub fn compare_equal_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, _mm512_cmpeq_ps_mask)
+    }
+
+    pub fn compare_not_equal_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, _mm512_cmpneq_ps_mask)
+    }
+
+    pub fn compare_not_equal_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, _mm512_cmpneq_ps_mask)
+    }
+
+    pub fn compare_greater_than_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, _mm512_cmpnle_ps_mask)
+    }
+
+    pub fn compare_greater_than_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, _mm512_cmpnle_ps_mask)
+    }
+
+    pub fn compare_greater_than_or_equal_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, _mm512_cmpnlt_ps_mask)
+    }
+
+    pub fn compare_greater_than_or_equal_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, _mm512_cmpnlt_ps_mask)
+    }
+
+    pub fn compare_less_than_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, _mm512_cmplt_ps_mask)
+    }
+
+    pub fn compare_less_than_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, _mm512_cmplt_ps_mask)
+    }
+
+    pub fn compare_less_than_or_equal_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, _mm512_cmple_ps_mask)
+    }
+
+    pub fn compare_less_than_or_equal_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, _mm512_cmple_ps_mask)
+    }
+}
@@ -14,7 +14,10 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+mod broadcast;
 mod one_dimension;
+mod scan;
+mod sort;
 mod two_dimensions;
 
 use std::{
@@ -24,11 +27,13 @@ use std::{
         _mm512_cmple_ps_mask, _mm512_cmplt_ps_mask, _mm512_cmpneq_ps_mask, _mm512_cmpnle_ps_mask,
         _mm512_cmpnlt_ps_mask, _mm512_cvtepu32_ps, _mm512_cvtps_epi32, _mm512_div_ps,
         _mm512_fmadd_ps, _mm512_mask3_fmadd_ps, _mm512_mask_abs_ps, _mm512_mask_add_ps,
-        _mm512_mask_blend_ps, _mm512_mask_div_ps, _mm512_mask_max_ps, _mm512_mask_min_ps,
+        _mm512_mask_blend_ps, _mm512_mask_cmpeq_ps_mask, _mm512_mask_compressstoreu_ps,
+        _mm512_mask_div_ps, _mm512_mask_expandloadu_ps, _mm512_mask_max_ps, _mm512_mask_min_ps,
         _mm512_mask_mul_ps, _mm512_mask_sqrt_ps, _mm512_mask_sub_ps, _mm512_max_ps, _mm512_min_ps,
         _mm512_mul_ps, _mm512_mul_round_ps, _mm512_mullo_epi32, _mm512_slli_epi32, _mm512_sqrt_ps,
         _mm512_sub_ps, _MM_FROUND_NO_EXC, _MM_FROUND_TO_NEAREST_INT,
     },
+    io::{self, Read, Write},
     simd::{f32x16, u32x16},
 };
 
@@ -38,7 +43,16 @@ use serde::{
     Deserialize,
 };
 
-use crate::{Array, Mask};
+use crate::{
+    binary::{read_f32s, read_header, write_f32_payload, write_header, Kind, READ_CHUNK_SIZE},
+    error::{check_deserialized_shape, check_same_length, unwrap_or_panic},
+    Array, Mask,
+};
+
+use super::{
+    assert_same_shape2, assert_same_shape3, assert_same_shape_mask, assert_same_shape_with_mask2,
+    assert_same_shape_with_mask3,
+};
 
 fn m512_to_array(value: __m512) -> [f32; 16] {
     let value: f32x16 = value.into();
@@ -60,75 +74,42 @@ fn array_to_m512i(value: [u32; 16]) -> __m512i {
     value.into()
 }
 
-fn assert_same_shape2<const D: usize>(a: &Array<D>, b: &Array<D>) {
-    assert_eq!(
-        a.shape, b.shape,
-        "the lengths of array one and two don't match: {:?} != {:?}",
-        a.shape, b.shape
-    );
-}
-
-fn assert_same_shape_mask<const D: usize>(a: &Array<D>, mask: &Mask<D>) {
-    assert_eq!(
-        &a.shape,
-        mask.get_shape(),
-        "the shapes of the array and the mask don't match: {:?} != {:?}",
-        a.shape,
-        mask.get_shape()
-    );
-}
-
-fn assert_same_shape_with_mask2<const D: usize>(a: &Array<D>, b: &Array<D>, mask: &Mask<D>) {
-    assert_eq!(
-        a.shape, b.shape,
-        "the lengths of array one and two don't match: {:?} != {:?}",
-        a.shape, b.shape
-    );
-    assert_eq!(
-        &a.shape,
-        mask.get_shape(),
-        "the lengths of array one and mask don't match: {:?} != {:?}",
-        a.shape,
-        mask.get_shape()
-    );
-}
-
-fn assert_same_shape3<const D: usize>(a: &Array<D>, b: &Array<D>, c: &Array<D>) {
-    assert_eq!(
-        a.shape, b.shape,
-        "the lengths of array one and two don't match: {:?} != {:?}",
-        a.shape, b.shape
-    );
-    assert_eq!(
-        b.shape, c.shape,
-        "the lengths of array two and three don't match: {:?} != {:?}",
-        b.shape, c.shape
-    );
-}
-
-fn assert_same_shape_with_mask3<const D: usize>(
-    a: &Array<D>,
-    b: &Array<D>,
-    c: &Array<D>,
-    mask: &Mask<D>,
-) {
-    assert_eq!(
-        a.shape, b.shape,
-        "the lengths of array one and two don't match: {:?} != {:?}",
-        a.shape, b.shape
-    );
-    assert_eq!(
-        b.shape, c.shape,
-        "the lengths of array two and three don't match: {:?} != {:?}",
-        b.shape, c.shape
-    );
-    assert_eq!(
-        &a.shape,
-        mask.get_shape(),
-        "the lengths of array one and mask don't match: {:?} != {:?}",
-        a.shape,
-        mask.get_shape()
-    );
+/// e^x for all lanes of `x`
+#[inline(always)]
+pub(crate) unsafe fn exp_register(x: __m512) -> __m512 {
+    // adapted from https://stackoverflow.com/a/49090523
+
+    let l2e = array_to_m512([1.442695041f32; 16]); // log2(e)
+    let l2h = array_to_m512([-6.93145752e-1f32; 16]); // -log(2)_hi
+    let l2l = array_to_m512([-1.42860677e-6f32; 16]); // -log(2)_lo
+
+    // coefficients for core approximation to exp() in [-log(2)/2, log(2)/2]
+    let c0 = array_to_m512([0.041944388f32; 16]);
+    let c1 = array_to_m512([0.168006673f32; 16]);
+    let c2 = array_to_m512([0.499999940f32; 16]);
+    let c3 = array_to_m512([0.999956906f32; 16]);
+    let c4 = array_to_m512([0.999999642f32; 16]);
+
+    // exp(x) = 2^i * e^f; i = rint (log2(e) * x), f = x - log(2) * i
+    let t = _mm512_mul_ps(x, l2e);
+    let mut r = _mm512_mul_round_ps(x, l2e, _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC); // r = rint (t)
+    let mut f = _mm512_fmadd_ps(r, l2h, x); // x - log(2)_hi * r
+    f = _mm512_fmadd_ps(r, l2l, f); // f = x - log(2)_hi * r - log(2)_lo * r
+
+    let i = _mm512_cvtps_epi32(t); // i = (int)rint(t)
+
+    // p ~= exp (f), -log(2)/2 <= f <= log(2)/2
+    let mut p = c0; // c0
+    p = _mm512_fmadd_ps(p, f, c1); // c0*f+c1
+    p = _mm512_fmadd_ps(p, f, c2); // (c0*f+c1)*f+c2
+    p = _mm512_fmadd_ps(p, f, c3); // ((c0*f+c1)*f+c2)*f+c3
+    p = _mm512_fmadd_ps(p, f, c4); // (((c0*f+c1)*f+c2)*f+c3)*f+c4 ~= exp(f)
+
+    // exp(x) = 2^i * p
+    let j = _mm512_slli_epi32(i, 23); // i << 23
+    r = _mm512_castsi512_ps(_mm512_add_epi32(j, _mm512_castps_si512(p))); // r = p * 2^i
+
+    r
 }
 
 unsafe fn reduce(
@@ -198,6 +179,53 @@ impl<'a, const D: usize> Serialize for DataSerializeWrapper<'a, D> {
     }
 }
 
+/// the lanes of the last register of a row that contain elements
+fn last_register_lanes(column_count: usize) -> __mmask16 {
+    if column_count.is_multiple_of(16) {
+        0xFFFF
+    } else {
+        (1 << (column_count % 16)) - 1
+    }
+}
+
+impl<const D: usize> PartialEq for Array<D> {
+    /// compares the shapes and the elements, the padding of the rows is ignored
+    fn eq(&self, other: &Self) -> bool {
+        if self.shape != other.shape {
+            return false;
+        }
+
+        let column_count = *self.shape.last().unwrap();
+        let registers_per_row = column_count.div_ceil(16);
+
+        if registers_per_row == 0 {
+            return true;
+        }
+
+        let last_lanes = last_register_lanes(column_count);
+
+        for (row, other_row) in self
+            .data
+            .chunks(registers_per_row)
+            .zip(other.data.chunks(registers_per_row))
+        {
+            for (i, (a, b)) in row.iter().zip(other_row.iter()).enumerate() {
+                let lanes = if i + 1 == registers_per_row {
+                    last_lanes
+                } else {
+                    0xFFFF
+                };
+
+                if unsafe { _mm512_mask_cmpeq_ps_mask(lanes, *a, *b) } != lanes {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
 impl<const D: usize> Serialize for Array<D> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -225,11 +253,16 @@ impl<'de, const D: usize> Deserialize<'de> for Array<D> {
         De: serde::Deserializer<'de>,
     {
         let proxy = ArrayDeserializerProxy::deserialize(deserializer)?;
-        assert_eq!(proxy.shape.len(), D);
+        let shape: [usize; D] = check_deserialized_shape(&proxy.shape, proxy.data.len())
+            .map_err(serde::de::Error::custom)?;
 
-        let mut shape = [0; D];
-        shape.copy_from_slice(&proxy.shape[..D]);
+        Ok(Array::from_elements(proxy.data, shape))
+    }
+}
 
+impl<const D: usize> Array<D> {
+    /// create an array from elements in row-major order, the number of elements must match the shape
+    pub(crate) fn from_elements(elements: Vec<f32>, shape: [usize; D]) -> Self {
         let registers_per_row = shape.last().unwrap().div_ceil(16);
         let mut register_count = registers_per_row;
 
@@ -248,7 +281,7 @@ impl<'de, const D: usize> Deserialize<'de> for Array<D> {
                 limit = ((shape.last().unwrap() - 1) % 16) + 1;
             }
 
-            let content = &proxy.data[element_index..element_index + limit];
+            let content = &elements[element_index..element_index + limit];
             element_index += limit;
 
             let mut register_content = [0.0; 16];
@@ -257,8 +290,174 @@ impl<'de, const D: usize> Deserialize<'de> for Array<D> {
             data[register_index] = array_to_m512(register_content);
         }
 
+        Array { data, shape }
+    }
+
+    /// the number of elements of a row including the padding, a whole number of registers
+    pub(crate) fn padded_row_length(column_count: usize) -> usize {
+        column_count.div_ceil(16) * 16
+    }
+
+    /// the distance between the first elements of two consecutive rows, a whole number of registers
+    pub(crate) fn row_stride(&self) -> usize {
+        Self::padded_row_length(*self.shape.last().unwrap())
+    }
+
+    /// all elements including the padding at the end of each row
+    pub(crate) fn padded_elements(&self) -> &[f32] {
+        unsafe {
+            std::slice::from_raw_parts(self.data.as_ptr() as *const f32, self.data.len() * 16)
+        }
+    }
+
+    /// all elements including the padding at the end of each row
+    pub(crate) fn padded_elements_mut(&mut self) -> &mut [f32] {
+        unsafe {
+            std::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut f32, self.data.len() * 16)
+        }
+    }
+
+    /// the elements of a row without the padding, the rows are counted over all axes except the last one
+    pub(crate) fn row_elements(&self, row: usize) -> &[f32] {
+        let column_count = *self.shape.last().unwrap();
+        let registers_per_row = column_count.div_ceil(16);
+        let registers = &self.data[row * registers_per_row..(row + 1) * registers_per_row];
+
+        // the registers of a row are contiguous and start with the elements of the row
+        unsafe { std::slice::from_raw_parts(registers.as_ptr() as *const f32, column_count) }
+    }
+
+    /// the elements of a row without the padding, the rows are counted over all axes except the last one
+    pub(crate) fn row_elements_mut(&mut self, row: usize) -> &mut [f32] {
+        let column_count = *self.shape.last().unwrap();
+        let registers_per_row = column_count.div_ceil(16);
+        let registers = &mut self.data[row * registers_per_row..(row + 1) * registers_per_row];
+
+        // the registers of a row are contiguous and start with the elements of the row
+        unsafe { std::slice::from_raw_parts_mut(registers.as_mut_ptr() as *mut f32, column_count) }
+    }
+
+    /// the elements in row-major order without padding
+    pub(crate) fn to_elements(&self) -> Vec<f32> {
+        let column_count = *self.shape.last().unwrap();
+        let registers_per_row = column_count.div_ceil(16);
+        let mut elements = Vec::with_capacity(self.number_of_elements());
+
+        if registers_per_row == 0 {
+            return elements;
+        }
+
+        for row in self.data.chunks(registers_per_row) {
+            for (i, register) in row.iter().enumerate() {
+                let end = (column_count - i * 16).min(16);
+                elements.extend_from_slice(&m512_to_array(*register)[..end]);
+            }
+        }
+
+        elements
+    }
+
+    /// read elements in row-major order as little-endian f32 directly into the registers
+    pub(crate) fn read_elements(reader: &mut impl Read, shape: [usize; D]) -> io::Result<Self> {
+        let column_count = *shape.last().unwrap();
+        let row_count: usize = shape[..D - 1].iter().product();
+        let mut data = Vec::new();
+        let mut buffer = [0.0; READ_CHUNK_SIZE];
+
+        if column_count == 0 {
+            return Ok(Array { data, shape });
+        }
+
+        for _ in 0..row_count {
+            for start in (0..column_count).step_by(READ_CHUNK_SIZE) {
+                let chunk = &mut buffer[..(column_count - start).min(READ_CHUNK_SIZE)];
+                read_f32s(reader, chunk)?;
+
+                for register in chunk.chunks(16) {
+                    let mut elements = [0.0; 16];
+                    elements[..register.len()].copy_from_slice(register);
+
+                    data.push(array_to_m512(elements));
+                }
+            }
+        }
+
         Ok(Array { data, shape })
     }
+
+    /// write the elements in row-major order as little-endian f32
+    pub(crate) fn write_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        let column_count = *self.shape.last().unwrap();
+        let registers_per_row = column_count.div_ceil(16);
+
+        if registers_per_row == 0 {
+            return Ok(());
+        }
+
+        let mut row_elements = Vec::with_capacity(registers_per_row * 16);
+
+        for row in self.data.chunks(registers_per_row) {
+            row_elements.clear();
+
+            for register in row {
+                row_elements.extend_from_slice(&m512_to_array(*register));
+            }
+
+            write_f32_payload(writer, &row_elements[..column_count])?;
+        }
+
+        Ok(())
+    }
+
+    /// write the registers as little-endian f32, the padding at the end of the rows is written as zeros
+    #[cfg(feature = "mmap")]
+    pub(crate) fn write_padded_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        let column_count = *self.shape.last().unwrap();
+        let registers_per_row = column_count.div_ceil(16);
+        let mut elements = Vec::with_capacity(self.data.len().min(256) * 16);
+
+        for (i, register) in self.data.iter().enumerate() {
+            let mut register = m512_to_array(*register);
+            let start_column = (i % registers_per_row) * 16;
+
+            // the padding isn't necessarily zero in memory, e.g. after `add_scalar`
+            for lane in register.iter_mut().skip(column_count - start_column) {
+                *lane = 0.0;
+            }
+
+            elements.extend_from_slice(&register);
+
+            if elements.len() == elements.capacity() {
+                write_f32_payload(writer, &elements)?;
+                elements.clear();
+            }
+        }
+
+        write_f32_payload(writer, &elements)
+    }
+
+    /// the padded elements written by `write_padded_elements`, they are used without copying them
+    #[cfg(feature = "mmap")]
+    pub(crate) fn mapped_elements(bytes: &[u8]) -> &[f32] {
+        // SAFETY: every bit pattern is a valid f32 and x86 is little-endian like the file
+        let (prefix, elements, suffix) = unsafe { bytes.align_to::<f32>() };
+        assert!(prefix.is_empty() && suffix.is_empty());
+
+        elements
+    }
+
+    /// Write the array in a compact binary format, which can be read with `read_from`
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        write_header(&mut writer, Kind::Array, &self.shape)?;
+        self.write_elements(&mut writer)
+    }
+
+    /// Read an array in the binary format written by `write_to`
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let shape = read_header(&mut reader, Kind::Array)?;
+
+        Array::read_elements(&mut reader, shape)
+    }
 }
 
 impl<const D: usize> Array<D> {
@@ -279,6 +478,23 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    /// the number of registers of an array with the given shape
+    pub(crate) fn buffer_length(shape: &[usize; D]) -> usize {
+        assert!(D > 0);
+
+        calculate_register_count(shape)
+    }
+
+    /// an array that reuses the allocation of `data`, the values of the elements are unspecified
+    pub(crate) fn from_buffer(shape: &[usize; D], mut data: Vec<__m512>) -> Self {
+        data.resize(Self::buffer_length(shape), array_to_m512([0.0; 16]));
+
+        Self {
+            data,
+            shape: *shape,
+        }
+    }
+
     pub fn assert_invariants_satisfied(&self) {
         // check number of registers
         let registers_per_row = self.shape.last().unwrap().div_ceil(16);
@@ -345,7 +561,7 @@ impl<const D: usize> Array<D> {
 
     /// set the elements to `value` where `mask` is 1
     pub fn set_masked(&mut self, value: f32, mask: &Mask<D>) {
-        assert_eq!(&self.shape, mask.get_shape()); // TODO: add messages to asserts
+        assert_same_shape_mask(self, mask);
 
         let value_register = array_to_m512([value; 16]);
 
@@ -358,7 +574,7 @@ impl<const D: usize> Array<D> {
 
     /// set the elements to `v1` where `mask` is 0 and to `v2` where `mask` is 1
     pub fn set_masked2(&mut self, v1: f32, v2: f32, mask: &Mask<D>) {
-        assert_eq!(&self.shape, mask.get_shape());
+        assert_same_shape_mask(self, mask);
 
         let v1_register = array_to_m512([v1; 16]);
         let v2_register = array_to_m512([v2; 16]);
@@ -371,7 +587,7 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn copy(&mut self, other: &Array<D>) {
-        assert_eq!(self.shape, other.shape);
+        assert_same_shape2(self, other);
 
         for (d1, d2) in self.data.iter_mut().zip(other.data.iter()) {
             *d1 = *d2;
@@ -411,6 +627,124 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    /// create a new array with the elements from `if_true` where `mask` is 1 and from `if_false` where `mask` is 0
+    pub fn select(mask: &Mask<D>, if_true: &Self, if_false: &Self) -> Self {
+        let mut new_array = Self::zeros(mask.get_shape());
+        Self::select_out_of_place(mask, if_true, if_false, &mut new_array);
+
+        new_array
+    }
+
+    pub fn select_out_of_place(mask: &Mask<D>, if_true: &Self, if_false: &Self, output: &mut Self) {
+        assert_same_shape_with_mask3(if_true, if_false, output, mask);
+
+        unsafe {
+            for (((t, f), o), m) in if_true
+                .data
+                .iter()
+                .zip(if_false.data.iter())
+                .zip(output.data.iter_mut())
+                .zip(mask.get_masks().iter())
+            {
+                *o = _mm512_mask_blend_ps(*m, *f, *t);
+            }
+        }
+    }
+
+    /// create a new array with the elements from `if_true` where `mask` is 1 and `if_false` where `mask` is 0
+    pub fn select_scalar(mask: &Mask<D>, if_true: &Self, if_false: f32) -> Self {
+        let mut new_array = Self::zeros(mask.get_shape());
+        Self::select_scalar_out_of_place(mask, if_true, if_false, &mut new_array);
+
+        new_array
+    }
+
+    pub fn select_scalar_out_of_place(
+        mask: &Mask<D>,
+        if_true: &Self,
+        if_false: f32,
+        output: &mut Self,
+    ) {
+        assert_same_shape_with_mask2(if_true, output, mask);
+
+        let if_false = array_to_m512([if_false; 16]);
+
+        unsafe {
+            for ((t, o), m) in if_true
+                .data
+                .iter()
+                .zip(output.data.iter_mut())
+                .zip(mask.get_masks().iter())
+            {
+                *o = _mm512_mask_blend_ps(*m, if_false, *t);
+            }
+        }
+    }
+
+    /// create a new array that is `if_true` where `mask` is 1 and `if_false` where `mask` is 0
+    pub fn select_scalars(mask: &Mask<D>, if_true: f32, if_false: f32) -> Self {
+        let mut new_array = Self::zeros(mask.get_shape());
+        Self::select_scalars_out_of_place(mask, if_true, if_false, &mut new_array);
+
+        new_array
+    }
+
+    pub fn select_scalars_out_of_place(
+        mask: &Mask<D>,
+        if_true: f32,
+        if_false: f32,
+        output: &mut Self,
+    ) {
+        assert_same_shape_mask(output, mask);
+
+        let if_true = array_to_m512([if_true; 16]);
+        let if_false = array_to_m512([if_false; 16]);
+
+        unsafe {
+            for (o, m) in output.data.iter_mut().zip(mask.get_masks().iter()) {
+                *o = _mm512_mask_blend_ps(*m, if_false, if_true);
+            }
+        }
+    }
+
+    /// pack the elements where `mask` is 1 into a dense array, in row-major order
+    pub fn compress(&self, mask: &Mask<D>) -> Array<1> {
+        assert_same_shape_mask(self, mask);
+
+        let mut output = Array::<1>::zeros(&[mask.number_of_true_elements()]);
+        let output_pointer = output.data.as_mut_ptr() as *mut f32;
+        let mut index = 0;
+
+        unsafe {
+            for (d, m) in self.data.iter().zip(mask.get_masks().iter()) {
+                _mm512_mask_compressstoreu_ps(output_pointer.add(index), *m, *d);
+                index += m.count_ones() as usize;
+            }
+        }
+
+        output
+    }
+
+    /// write the elements of `values` in row-major order to the positions where `mask` is 1,
+    /// this is the inverse of `compress`
+    pub fn expand_into(&mut self, values: &Array<1>, mask: &Mask<D>) {
+        assert_same_shape_mask(self, mask);
+        unwrap_or_panic(check_same_length(
+            mask.number_of_true_elements(),
+            values.shape[0],
+        ));
+
+        let values_pointer = values.data.as_ptr() as *const f32;
+        let mut index = 0;
+
+        unsafe {
+            for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
+                *d = _mm512_mask_expandloadu_ps(*d, *m, values_pointer.add(index));
+                index += m.count_ones() as usize;
+            }
+        }
+    }
+
     pub fn add(&self, other: &Self) -> Self {
         let mut new_array = self.clone();
         new_array.add_in_place(other);
@@ -458,8 +792,6 @@ impl<const D: usize> Array<D> {
         }
     }
 
-    // TODO: more out_of_place functions
-
     pub fn sub(&self, other: &Self) -> Self {
         let mut new_array = self.clone();
         new_array.sub_in_place(other);
@@ -677,6 +1009,17 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn max_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+        let scalar = array_to_m512([scalar; 16]);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_max_ps(*d, scalar);
+            }
+        }
+    }
+
     pub fn min(&self, other: &Self) -> Self {
         let mut new_array = self.clone();
         new_array.min_in_place(other);
@@ -753,6 +1096,17 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn min_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+        let scalar = array_to_m512([scalar; 16]);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_min_ps(*d, scalar);
+            }
+        }
+    }
+
     pub fn add_scalar(&self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.add_scalar_in_place(scalar);
@@ -782,6 +1136,17 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn add_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+        let scalar = array_to_m512([scalar; 16]);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_add_ps(*d, scalar);
+            }
+        }
+    }
+
     pub fn sub_scalar(&self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.sub_scalar_in_place(scalar);
@@ -811,6 +1176,17 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn sub_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+        let scalar = array_to_m512([scalar; 16]);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_sub_ps(*d, scalar);
+            }
+        }
+    }
+
     pub fn mul_scalar(&self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.mul_scalar_in_place(scalar);
@@ -840,6 +1216,17 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn mul_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+        let scalar = array_to_m512([scalar; 16]);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_mul_ps(*d, scalar);
+            }
+        }
+    }
+
     pub fn div_scalar(&self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.div_scalar_in_place(scalar);
@@ -869,6 +1256,17 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn div_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+        let scalar = array_to_m512([scalar; 16]);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_div_ps(*d, scalar);
+            }
+        }
+    }
+
     pub fn fmadd(&self, a: &Self, b: &Self) -> Self {
         let mut new_array = self.clone();
         new_array.fmadd_in_place(a, b);
@@ -902,6 +1300,23 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn fmadd_out_of_place(&self, a: &Self, b: &Self, output: &mut Self) {
+        assert_same_shape3(self, a, b);
+        assert_same_shape2(self, output);
+
+        unsafe {
+            for (((a, b), c), o) in a
+                .data
+                .iter()
+                .zip(b.data.iter())
+                .zip(self.data.iter())
+                .zip(output.data.iter_mut())
+            {
+                *o = _mm512_fmadd_ps(*a, *b, *c);
+            }
+        }
+    }
+
     pub fn fmadd_scalar(&self, a: &Self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.fmadd_scalar_in_place(a, scalar);
@@ -936,6 +1351,22 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn fmadd_scalar_out_of_place(&self, a: &Self, scalar: f32, output: &mut Self) {
+        assert_same_shape3(self, a, output);
+        let scalar_register = array_to_m512([scalar; 16]);
+
+        unsafe {
+            for ((a, b), o) in a
+                .data
+                .iter()
+                .zip(self.data.iter())
+                .zip(output.data.iter_mut())
+            {
+                *o = _mm512_fmadd_ps(*a, scalar_register, *b);
+            }
+        }
+    }
+
     pub fn sqrt(&self) -> Self {
         let mut new_array = self.clone();
         new_array.sqrt_in_place();
@@ -952,6 +1383,8 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn sqrt_in_place_masked(&mut self, mask: &Mask<D>) {
+        assert_same_shape_mask(self, mask);
+
         unsafe {
             for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
                 *d = _mm512_mask_sqrt_ps(*d, *m, *d);
@@ -959,6 +1392,16 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn sqrt_out_of_place(&self, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_sqrt_ps(*d);
+            }
+        }
+    }
+
     pub fn square(&self) -> Self {
         let mut new_array = self.clone();
         new_array.square_in_place();
@@ -975,6 +1418,8 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn square_in_place_masked(&mut self, mask: &Mask<D>) {
+        assert_same_shape_mask(self, mask);
+
         unsafe {
             for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
                 *d = _mm512_mask_mul_ps(*d, *m, *d, *d);
@@ -982,6 +1427,16 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn square_out_of_place(&self, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_mul_ps(*d, *d);
+            }
+        }
+    }
+
     pub fn abs(&self) -> Self {
         let mut new_array = self.clone();
         new_array.abs_in_place();
@@ -998,6 +1453,8 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn abs_in_place_masked(&mut self, mask: &Mask<D>) {
+        assert_same_shape_mask(self, mask);
+
         unsafe {
             for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
                 *d = _mm512_mask_abs_ps(*d, *m, *d);
@@ -1005,6 +1462,16 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn abs_out_of_place(&self, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        unsafe {
+            for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = _mm512_abs_ps(*d);
+            }
+        }
+    }
+
     fn compare(
         a: &Array<D>,
         b: &Array<D>,
@@ -1065,7 +1532,7 @@ impl<const D: usize> Array<D> {
         mask: &mut Mask<D>,
         func: unsafe fn(__m512, __m512) -> __mmask16,
     ) {
-        assert_eq!(&a.shape, mask.get_shape());
+        assert_same_shape_mask(a, mask);
         let scalar = array_to_m512([scalar; 16]);
 
         unsafe {
@@ -1179,41 +1646,19 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn exp_in_place(&mut self) {
-        // adapted from https://stackoverflow.com/a/49090523
+        unsafe {
+            for x in self.data.iter_mut() {
+                *x = exp_register(*x);
+            }
+        }
+    }
 
-        let l2e = array_to_m512([1.442695041f32; 16]); // log2(e)
-        let l2h = array_to_m512([-6.93145752e-1f32; 16]); // -log(2)_hi
-        let l2l = array_to_m512([-1.42860677e-6f32; 16]); // -log(2)_lo
-                                                          // coefficients for core approximation to exp() in [-log(2)/2, log(2)/2]
-        let c0 = array_to_m512([0.041944388f32; 16]);
-        let c1 = array_to_m512([0.168006673f32; 16]);
-        let c2 = array_to_m512([0.499999940f32; 16]);
-        let c3 = array_to_m512([0.999956906f32; 16]);
-        let c4 = array_to_m512([0.999999642f32; 16]);
+    pub fn exp_out_of_place(&self, output: &mut Self) {
+        assert_same_shape2(self, output);
 
         unsafe {
-            for x in self.data.iter_mut() {
-                // exp(x) = 2^i * e^f; i = rint (log2(e) * x), f = x - log(2) * i
-                let t = _mm512_mul_ps(*x, l2e);
-                let mut r =
-                    _mm512_mul_round_ps(*x, l2e, _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC); // r = rint (t)
-                let mut f = _mm512_fmadd_ps(r, l2h, *x); // x - log(2)_hi * r
-                f = _mm512_fmadd_ps(r, l2l, f); // f = x - log(2)_hi * r - log(2)_lo * r
-
-                let i = _mm512_cvtps_epi32(t); // i = (int)rint(t)
-
-                // p ~= exp (f), -log(2)/2 <= f <= log(2)/2
-                let mut p = c0; // c0
-                p = _mm512_fmadd_ps(p, f, c1); // c0*f+c1
-                p = _mm512_fmadd_ps(p, f, c2); // (c0*f+c1)*f+c2
-                p = _mm512_fmadd_ps(p, f, c3); // ((c0*f+c1)*f+c2)*f+c3
-                p = _mm512_fmadd_ps(p, f, c4); // (((c0*f+c1)*f+c2)*f+c3)*f+c4 ~= exp(f)
-
-                // exp(x) = 2^i * p
-                let j = _mm512_slli_epi32(i, 23); // i << 23
-                r = _mm512_castsi512_ps(_mm512_add_epi32(j, _mm512_castps_si512(p))); // r = p * 2^i
-
-                *x = r;
+            for (x, o) in self.data.iter().zip(output.data.iter_mut()) {
+                *o = exp_register(*x);
             }
         }
     }
@@ -15,15 +15,55 @@ limitations under the License.
 */
 
 use std::arch::x86_64::{
-    __m512, _mm512_add_ps, _mm512_broadcastss_ps, _mm512_castps512_ps128, _mm512_mask_add_ps,
+    __m512, __m512i, __mmask16, _mm512_add_ps, _mm512_broadcastss_ps, _mm512_castps512_ps128,
+    _mm512_fmadd_ps, _mm512_i32gather_ps, _mm512_i32scatter_ps, _mm512_mask_add_ps,
+    _mm512_mask_cmpge_epu32_mask, _mm512_mask_i32gather_ps, _mm512_mask_i32scatter_ps,
     _mm512_mask_max_ps, _mm512_mask_min_ps, _mm512_mask_mul_ps, _mm512_max_ps, _mm512_min_ps,
     _mm512_mul_ps, _mm512_permutexvar_ps, _mm512_reduce_add_ps, _mm512_reduce_max_ps,
     _mm512_reduce_min_ps, _mm512_reduce_mul_ps,
 };
 
-use crate::{array::avx512f::array_to_m512i, Array};
+use crate::{array::avx512f::array_to_m512i, error::unwrap_or_panic, Array, Mask};
 
-use super::{array_to_m512, assert_same_shape2, m512_to_array, reduce};
+use super::{array_to_m512, assert_same_shape2, m512_to_array, m512i_to_array, reduce};
+
+/// load the `chunk`-th group of 16 indices and return a mask with the lanes that contain an index
+fn load_indices(indices: &[u32], chunk: usize) -> (__m512i, __mmask16) {
+    let start = chunk * 16;
+    let end = (start + 16).min(indices.len());
+    let mut register = [0u32; 16];
+    register[..end - start].copy_from_slice(&indices[start..end]);
+
+    (array_to_m512i(register), 0xFFFF >> (16 - (end - start)))
+}
+
+/// panic if one of the indices in the active lanes is out of bounds
+fn assert_indices_in_bounds(indices: __m512i, mask: __mmask16, len: usize) {
+    // the gather and scatter instructions interpret the indices as signed integers
+    let len_register = array_to_m512i([len.min(i32::MAX as usize) as u32; 16]);
+    let out_of_bounds = unsafe { _mm512_mask_cmpge_epu32_mask(mask, indices, len_register) };
+
+    if out_of_bounds != 0 {
+        let lane = out_of_bounds.trailing_zeros() as usize;
+
+        panic!(
+            "tried to access index {}, but the array has only {} element(s)",
+            m512i_to_array(indices)[lane],
+            len
+        );
+    }
+}
+
+fn assert_same_length(indices: &[u32], other: &Array<1>, name: &str) {
+    assert_eq!(
+        indices.len(),
+        other.shape[0],
+        "the number of indices and the length of {} don't match: {} != {}",
+        name,
+        indices.len(),
+        other.shape[0]
+    );
+}
 
 impl From<Array<1>> for Vec<f32> {
     fn from(value: Array<1>) -> Self {
@@ -75,12 +115,7 @@ impl From<Vec<f32>> for Array<1> {
 
 impl Array<1> {
     pub fn get(&self, index: usize) -> f32 {
-        if index >= self.shape[0] {
-            panic!(
-                "tried to get index {}, but the array has only {} element(s)",
-                index, self.shape[0]
-            );
-        }
+        unwrap_or_panic(self.check_index(index));
 
         let register_index = index / 16;
         let value_index = index % 16;
@@ -91,12 +126,7 @@ impl Array<1> {
     }
 
     pub fn set(&mut self, index: usize, value: f32) {
-        if index >= self.shape[0] {
-            panic!(
-                "tried to set index {}, but the array has only {} element(s)",
-                index, self.shape[0]
-            );
-        }
+        unwrap_or_panic(self.check_index(index));
 
         let register_index = index / 16;
         let value_index = index % 16;
@@ -107,6 +137,21 @@ impl Array<1> {
         self.data[register_index] = array_to_m512(new_register);
     }
 
+    /// set the element `i` to `start + i * step`, the indices are computed from a register of lane offsets
+    pub(crate) fn fill_arithmetic_sequence(&mut self, start: f32, step: f32) {
+        let mut indices = array_to_m512(std::array::from_fn(|i| i as f32));
+        let register_size = array_to_m512([16.0; 16]);
+        let start = array_to_m512([start; 16]);
+        let step = array_to_m512([step; 16]);
+
+        unsafe {
+            for d in self.data.iter_mut() {
+                *d = _mm512_fmadd_ps(indices, step, start);
+                indices = _mm512_add_ps(indices, register_size);
+            }
+        }
+    }
+
     pub fn sum(&self) -> f32 {
         if self.shape[0] == 0 {
             return 0.0;
@@ -208,18 +253,152 @@ impl Array<1> {
         }
     }
 
-    /// Copy the array `k`-times into `output`
-    pub fn tile_in_place(&self, k: usize, output: &mut Array<1>) {
-        assert!(
-            self.shape[0] % 16 == 0,
-            "the number of elements needs to be a multiple of 16"
+    /// collect the elements at `indices` into a new array
+    pub fn gather(&self, indices: &[u32]) -> Array<1> {
+        let mut output = Array::zeros(&[indices.len()]);
+        self.gather_in_place(indices, &mut output);
+
+        output
+    }
+
+    /// collect the elements at `indices` into `output`
+    pub fn gather_in_place(&self, indices: &[u32], output: &mut Array<1>) {
+        assert_same_length(indices, output, "output");
+
+        let base = self.data.as_ptr() as *const f32;
+
+        unsafe {
+            for (i, o) in output.data.iter_mut().enumerate() {
+                let (index_register, lanes) = load_indices(indices, i);
+                assert_indices_in_bounds(index_register, lanes, self.shape[0]);
+
+                if lanes == 0xFFFF {
+                    *o = _mm512_i32gather_ps::<4>(index_register, base);
+                } else {
+                    *o = _mm512_mask_i32gather_ps::<4>(*o, lanes, index_register, base);
+                }
+            }
+        }
+    }
+
+    /// collect the elements at `indices` into `output` where `mask` is 1, indices where `mask` is 0 are not accessed
+    pub fn gather_in_place_masked(&self, indices: &[u32], mask: &Mask<1>, output: &mut Array<1>) {
+        assert_same_length(indices, output, "output");
+        assert_eq!(
+            &[indices.len()],
+            mask.get_shape(),
+            "the number of indices and the length of the mask don't match"
+        );
+
+        let base = self.data.as_ptr() as *const f32;
+
+        unsafe {
+            for (i, (o, m)) in output
+                .data
+                .iter_mut()
+                .zip(mask.get_masks().iter())
+                .enumerate()
+            {
+                let (index_register, lanes) = load_indices(indices, i);
+                let lanes = lanes & *m;
+                assert_indices_in_bounds(index_register, lanes, self.shape[0]);
+
+                *o = _mm512_mask_i32gather_ps::<4>(*o, lanes, index_register, base);
+            }
+        }
+    }
+
+    /// write `values` to the positions given by `indices`, if an index occurs multiple times the last value is kept
+    pub fn scatter_in_place(&mut self, indices: &[u32], values: &Array<1>) {
+        assert_same_length(indices, values, "values");
+
+        let len = self.shape[0];
+        let base = self.data.as_mut_ptr() as *mut f32;
+
+        unsafe {
+            for (i, v) in values.data.iter().enumerate() {
+                let (index_register, lanes) = load_indices(indices, i);
+                assert_indices_in_bounds(index_register, lanes, len);
+
+                if lanes == 0xFFFF {
+                    _mm512_i32scatter_ps::<4>(base, index_register, *v);
+                } else {
+                    _mm512_mask_i32scatter_ps::<4>(base, lanes, index_register, *v);
+                }
+            }
+        }
+    }
+
+    /// write `values` to the positions given by `indices` where `mask` is 1
+    pub fn scatter_in_place_masked(&mut self, indices: &[u32], values: &Array<1>, mask: &Mask<1>) {
+        assert_same_length(indices, values, "values");
+        assert_eq!(
+            &[indices.len()],
+            mask.get_shape(),
+            "the number of indices and the length of the mask don't match"
         );
+
+        let len = self.shape[0];
+        let base = self.data.as_mut_ptr() as *mut f32;
+
+        unsafe {
+            for (i, (v, m)) in values.data.iter().zip(mask.get_masks().iter()).enumerate() {
+                let (index_register, lanes) = load_indices(indices, i);
+                let lanes = lanes & *m;
+                assert_indices_in_bounds(index_register, lanes, len);
+
+                _mm512_mask_i32scatter_ps::<4>(base, lanes, index_register, *v);
+            }
+        }
+    }
+
+    /// add `values` to the elements at `indices`, values for repeated indices are accumulated
+    pub fn scatter_add_in_place(&mut self, indices: &[u32], values: &Array<1>) {
+        let mask = Mask::<1>::from(vec![true; indices.len()]);
+        self.scatter_add_in_place_masked(indices, values, &mask);
+    }
+
+    /// add `values` to the elements at `indices` where `mask` is 1, values for repeated indices are accumulated
+    pub fn scatter_add_in_place_masked(
+        &mut self,
+        indices: &[u32],
+        values: &Array<1>,
+        mask: &Mask<1>,
+    ) {
+        assert_same_length(indices, values, "values");
         assert_eq!(
-            self.shape[0] * k,
-            output.shape[0],
-            "the number of elements in output must be k-times more than the elements in this array"
+            &[indices.len()],
+            mask.get_shape(),
+            "the number of indices and the length of the mask don't match"
         );
 
+        let len = self.shape[0];
+        let base = self.data.as_mut_ptr() as *mut f32;
+
+        for (i, (v, m)) in values.data.iter().zip(mask.get_masks().iter()).enumerate() {
+            let (index_register, lanes) = load_indices(indices, i);
+            let lanes = lanes & *m;
+            assert_indices_in_bounds(index_register, lanes, len);
+
+            // a scatter of gathered sums loses updates when an index occurs multiple times in
+            // the same register, so the lanes are accumulated one after another
+            let index_array = m512i_to_array(index_register);
+            let value_array = m512_to_array(*v);
+
+            for lane in 0..16 {
+                if lanes & (1 << lane) != 0 {
+                    unsafe {
+                        *base.add(index_array[lane] as usize) += value_array[lane];
+                    }
+                }
+            }
+        }
+    }
+
+    /// Copy the array `k`-times into `output`
+    pub fn tile_in_place(&self, k: usize, output: &mut Array<1>) {
+        unwrap_or_panic(self.check_tile(k, output));
+
         let self_registers = self.data.len();
 
         for (i, d) in output.data.iter_mut().enumerate() {
@@ -229,18 +408,7 @@ impl Array<1> {
 
     /// Repeat each element of the array `k`-times and store the result in `output`
     pub fn repeat_in_place(&self, k: usize, output: &mut Array<1>) {
-        let self_len = self.shape[0];
-
-        assert!(
-            self_len % 16 == 0,
-            "the number of elements needs to be a multiple of 16"
-        );
-        assert!(k % 16 == 0, "k needs to be a multiple of 16");
-        assert_eq!(
-            self_len * k,
-            output.shape[0],
-            "the number of elements in output must be k-times more than the elements in this array"
-        );
+        unwrap_or_panic(self.check_repeat(k, output));
 
         let permutation_indices =
             array_to_m512i([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0]);
@@ -264,8 +432,7 @@ impl Array<1> {
     }
 
     pub fn repeat_as_row_in_place(&self, k: usize, output: &mut Array<2>) {
-        assert_eq!(output.shape[0], k);
-        assert_eq!(output.shape[1], self.shape[0]);
+        unwrap_or_panic(self.check_repeat_as_row(k, output));
 
         let registers_per_row = self.shape[0].div_ceil(16);
 
@@ -275,8 +442,7 @@ impl Array<1> {
     }
 
     pub fn repeat_as_column_in_place(&self, k: usize, output: &mut Array<2>) {
-        assert_eq!(output.shape[0], self.shape[0]);
-        assert_eq!(output.shape[1], k);
+        unwrap_or_panic(self.check_repeat_as_column(k, output));
 
         let registers_per_row = k.div_ceil(16);
 
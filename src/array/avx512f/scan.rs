@@ -0,0 +1,484 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::arch::x86_64::{
+    __m512, __mmask16, _mm512_add_ps, _mm512_mask_blend_ps, _mm512_mask_permutexvar_ps,
+    _mm512_max_ps, _mm512_min_ps, _mm512_mul_ps, _mm512_permutexvar_ps,
+};
+
+use crate::{Array, Mask};
+
+use super::{array_to_m512, array_to_m512i, assert_same_shape_mask};
+
+/// Inclusive scan of the lanes of `register` in log2(16) steps.
+/// In each step the register is shifted up by `shift` lanes, filled with `identity` and combined with itself.
+unsafe fn scan_register(
+    mut register: __m512,
+    identity: __m512,
+    func: unsafe fn(__m512, __m512) -> __m512,
+) -> __m512 {
+    for shift in [1, 2, 4, 8] {
+        let mut permutation = [0u32; 16];
+
+        for (lane, p) in permutation.iter_mut().enumerate().skip(shift) {
+            *p = (lane - shift) as u32;
+        }
+
+        let shifted = _mm512_mask_permutexvar_ps(
+            identity,
+            0xFFFF << shift,
+            array_to_m512i(permutation),
+            register,
+        );
+        register = func(register, shifted);
+    }
+
+    register
+}
+
+/// Scan a row of registers and carry the last lane of each register over to the next one.
+/// Elements where `masks` is 0 are left unchanged and don't contribute to the result.
+unsafe fn scan_row(
+    data: &mut [__m512],
+    masks: Option<&[__mmask16]>,
+    identity: f32,
+    func: unsafe fn(__m512, __m512) -> __m512,
+) {
+    let identity = array_to_m512([identity; 16]);
+    let last_lane = array_to_m512i([15; 16]);
+    let mut carry = identity;
+
+    for (i, d) in data.iter_mut().enumerate() {
+        let mask = masks.map_or(0xFFFF, |m| m[i]);
+        let input = _mm512_mask_blend_ps(mask, identity, *d);
+        let result = func(scan_register(input, identity, func), carry);
+
+        *d = _mm512_mask_blend_ps(mask, *d, result);
+        carry = _mm512_permutexvar_ps(last_lane, result);
+    }
+}
+
+/// Scan each column by combining every row with the running result of the rows above it.
+/// Elements where `masks` is 0 are left unchanged and don't contribute to the result.
+unsafe fn scan_columns(
+    data: &mut [__m512],
+    registers_per_row: usize,
+    masks: Option<&[__mmask16]>,
+    identity: f32,
+    func: unsafe fn(__m512, __m512) -> __m512,
+) {
+    let identity = array_to_m512([identity; 16]);
+    let mut carries = vec![identity; registers_per_row];
+
+    for (i, d) in data.iter_mut().enumerate() {
+        let mask = masks.map_or(0xFFFF, |m| m[i]);
+        let carry = &mut carries[i % registers_per_row];
+        let result = func(*carry, _mm512_mask_blend_ps(mask, identity, *d));
+
+        *d = _mm512_mask_blend_ps(mask, *d, result);
+        *carry = result;
+    }
+}
+
+impl Array<1> {
+    /// cumulative sum of the elements
+    pub fn cumsum(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumsum_in_place();
+
+        new_array
+    }
+
+    pub fn cumsum_in_place(&mut self) {
+        unsafe {
+            scan_row(&mut self.data, None, 0.0, _mm512_add_ps);
+        }
+    }
+
+    /// cumulative sum of the elements where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumsum_in_place_masked(&mut self, mask: &Mask<1>) {
+        assert_same_shape_mask(self, mask);
+
+        unsafe {
+            scan_row(&mut self.data, Some(mask.get_masks()), 0.0, _mm512_add_ps);
+        }
+    }
+
+    /// cumulative product of the elements
+    pub fn cumprod(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumprod_in_place();
+
+        new_array
+    }
+
+    pub fn cumprod_in_place(&mut self) {
+        unsafe {
+            scan_row(&mut self.data, None, 1.0, _mm512_mul_ps);
+        }
+    }
+
+    /// cumulative product of the elements where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumprod_in_place_masked(&mut self, mask: &Mask<1>) {
+        assert_same_shape_mask(self, mask);
+
+        unsafe {
+            scan_row(&mut self.data, Some(mask.get_masks()), 1.0, _mm512_mul_ps);
+        }
+    }
+
+    /// cumulative maximum of the elements
+    pub fn cummax(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummax_in_place();
+
+        new_array
+    }
+
+    pub fn cummax_in_place(&mut self) {
+        unsafe {
+            scan_row(&mut self.data, None, f32::NEG_INFINITY, _mm512_max_ps);
+        }
+    }
+
+    /// cumulative maximum of the elements where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummax_in_place_masked(&mut self, mask: &Mask<1>) {
+        assert_same_shape_mask(self, mask);
+
+        unsafe {
+            scan_row(
+                &mut self.data,
+                Some(mask.get_masks()),
+                f32::NEG_INFINITY,
+                _mm512_max_ps,
+            );
+        }
+    }
+
+    /// cumulative minimum of the elements
+    pub fn cummin(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummin_in_place();
+
+        new_array
+    }
+
+    pub fn cummin_in_place(&mut self) {
+        unsafe {
+            scan_row(&mut self.data, None, f32::INFINITY, _mm512_min_ps);
+        }
+    }
+
+    /// cumulative minimum of the elements where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummin_in_place_masked(&mut self, mask: &Mask<1>) {
+        assert_same_shape_mask(self, mask);
+
+        unsafe {
+            scan_row(
+                &mut self.data,
+                Some(mask.get_masks()),
+                f32::INFINITY,
+                _mm512_min_ps,
+            );
+        }
+    }
+}
+
+impl Array<2> {
+    /// cumulative sum along each row
+    pub fn cumsum_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumsum_rows_in_place();
+
+        new_array
+    }
+
+    pub fn cumsum_rows_in_place(&mut self) {
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            for row in self.data.chunks_mut(registers_per_row) {
+                scan_row(row, None, 0.0, _mm512_add_ps);
+            }
+        }
+    }
+
+    /// cumulative sum along each row where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumsum_rows_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            for (row, masks) in self
+                .data
+                .chunks_mut(registers_per_row)
+                .zip(mask.get_masks().chunks(registers_per_row))
+            {
+                scan_row(row, Some(masks), 0.0, _mm512_add_ps);
+            }
+        }
+    }
+
+    /// cumulative sum along each column
+    pub fn cumsum_columns(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumsum_columns_in_place();
+
+        new_array
+    }
+
+    pub fn cumsum_columns_in_place(&mut self) {
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            scan_columns(&mut self.data, registers_per_row, None, 0.0, _mm512_add_ps);
+        }
+    }
+
+    /// cumulative sum along each column where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumsum_columns_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            scan_columns(
+                &mut self.data,
+                registers_per_row,
+                Some(mask.get_masks()),
+                0.0,
+                _mm512_add_ps,
+            );
+        }
+    }
+
+    /// cumulative product along each row
+    pub fn cumprod_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumprod_rows_in_place();
+
+        new_array
+    }
+
+    pub fn cumprod_rows_in_place(&mut self) {
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            for row in self.data.chunks_mut(registers_per_row) {
+                scan_row(row, None, 1.0, _mm512_mul_ps);
+            }
+        }
+    }
+
+    /// cumulative product along each row where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumprod_rows_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            for (row, masks) in self
+                .data
+                .chunks_mut(registers_per_row)
+                .zip(mask.get_masks().chunks(registers_per_row))
+            {
+                scan_row(row, Some(masks), 1.0, _mm512_mul_ps);
+            }
+        }
+    }
+
+    /// cumulative product along each column
+    pub fn cumprod_columns(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumprod_columns_in_place();
+
+        new_array
+    }
+
+    pub fn cumprod_columns_in_place(&mut self) {
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            scan_columns(&mut self.data, registers_per_row, None, 1.0, _mm512_mul_ps);
+        }
+    }
+
+    /// cumulative product along each column where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumprod_columns_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            scan_columns(
+                &mut self.data,
+                registers_per_row,
+                Some(mask.get_masks()),
+                1.0,
+                _mm512_mul_ps,
+            );
+        }
+    }
+
+    /// cumulative maximum along each row
+    pub fn cummax_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummax_rows_in_place();
+
+        new_array
+    }
+
+    pub fn cummax_rows_in_place(&mut self) {
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            for row in self.data.chunks_mut(registers_per_row) {
+                scan_row(row, None, f32::NEG_INFINITY, _mm512_max_ps);
+            }
+        }
+    }
+
+    /// cumulative maximum along each row where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummax_rows_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            for (row, masks) in self
+                .data
+                .chunks_mut(registers_per_row)
+                .zip(mask.get_masks().chunks(registers_per_row))
+            {
+                scan_row(row, Some(masks), f32::NEG_INFINITY, _mm512_max_ps);
+            }
+        }
+    }
+
+    /// cumulative maximum along each column
+    pub fn cummax_columns(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummax_columns_in_place();
+
+        new_array
+    }
+
+    pub fn cummax_columns_in_place(&mut self) {
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            scan_columns(
+                &mut self.data,
+                registers_per_row,
+                None,
+                f32::NEG_INFINITY,
+                _mm512_max_ps,
+            );
+        }
+    }
+
+    /// cumulative maximum along each column where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummax_columns_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            scan_columns(
+                &mut self.data,
+                registers_per_row,
+                Some(mask.get_masks()),
+                f32::NEG_INFINITY,
+                _mm512_max_ps,
+            );
+        }
+    }
+
+    /// cumulative minimum along each row
+    pub fn cummin_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummin_rows_in_place();
+
+        new_array
+    }
+
+    pub fn cummin_rows_in_place(&mut self) {
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            for row in self.data.chunks_mut(registers_per_row) {
+                scan_row(row, None, f32::INFINITY, _mm512_min_ps);
+            }
+        }
+    }
+
+    /// cumulative minimum along each row where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummin_rows_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            for (row, masks) in self
+                .data
+                .chunks_mut(registers_per_row)
+                .zip(mask.get_masks().chunks(registers_per_row))
+            {
+                scan_row(row, Some(masks), f32::INFINITY, _mm512_min_ps);
+            }
+        }
+    }
+
+    /// cumulative minimum along each column
+    pub fn cummin_columns(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummin_columns_in_place();
+
+        new_array
+    }
+
+    pub fn cummin_columns_in_place(&mut self) {
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            scan_columns(
+                &mut self.data,
+                registers_per_row,
+                None,
+                f32::INFINITY,
+                _mm512_min_ps,
+            );
+        }
+    }
+
+    /// cumulative minimum along each column where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummin_columns_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        let registers_per_row = self.shape[1].div_ceil(16).max(1);
+
+        unsafe {
+            scan_columns(
+                &mut self.data,
+                registers_per_row,
+                Some(mask.get_masks()),
+                f32::INFINITY,
+                _mm512_min_ps,
+            );
+        }
+    }
+}
@@ -0,0 +1,325 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::arch::x86_64::{
+    __m512, __m512i, __mmask16, _mm512_castps_si512, _mm512_cmpeq_epi32_mask,
+    _mm512_cmplt_epi32_mask, _mm512_cmplt_epu32_mask, _mm512_mask_blend_epi32,
+    _mm512_permutexvar_epi32, _mm512_srai_epi32, _mm512_srli_epi32, _mm512_xor_si512,
+};
+
+use crate::Array;
+
+use super::{array_to_m512, array_to_m512i, m512i_to_array};
+
+/// The steps of a bitonic sorting network for the 16 lanes of a register.
+/// In each step every lane is compared with the lane given by the permutation,
+/// the mask contains the lanes that keep the larger of the two elements.
+fn bitonic_network() -> [(__m512i, __mmask16); 10] {
+    let mut steps = [(array_to_m512i([0; 16]), 0); 10];
+    let mut step = 0;
+    let mut k = 2;
+
+    while k <= 16 {
+        let mut j = k / 2;
+
+        while j > 0 {
+            let mut permutation = [0u32; 16];
+            let mut max_lanes: __mmask16 = 0;
+
+            for (lane, p) in permutation.iter_mut().enumerate() {
+                *p = (lane ^ j) as u32;
+
+                let lower = lane & j == 0;
+                let ascending = lane & k == 0;
+
+                if lower != ascending {
+                    max_lanes |= 1 << lane;
+                }
+            }
+
+            steps[step] = (array_to_m512i(permutation), max_lanes);
+            step += 1;
+            j /= 2;
+        }
+
+        k *= 2;
+    }
+
+    steps
+}
+
+/// maps the floats to integers whose order matches `f32::total_cmp`
+unsafe fn to_total_order_keys(register: __m512) -> __m512i {
+    let bits = _mm512_castps_si512(register);
+    let sign = _mm512_srli_epi32(_mm512_srai_epi32(bits, 31), 1);
+
+    _mm512_xor_si512(bits, sign)
+}
+
+/// the inverse of `to_total_order_keys` for a single key
+fn from_total_order_key(key: i32, descending: bool) -> f32 {
+    let key = if descending { !key } else { key };
+    let bits = key ^ (((key >> 31) as u32) >> 1) as i32;
+
+    f32::from_bits(bits as u32)
+}
+
+/// sort the keys of a register in ascending order, equal keys are ordered by their indices
+unsafe fn sort_register(
+    mut keys: __m512i,
+    mut indices: __m512i,
+    network: &[(__m512i, __mmask16); 10],
+) -> (__m512i, __m512i) {
+    for (permutation, max_lanes) in network.iter() {
+        let partner_keys = _mm512_permutexvar_epi32(*permutation, keys);
+        let partner_indices = _mm512_permutexvar_epi32(*permutation, indices);
+
+        let less = _mm512_cmplt_epi32_mask(keys, partner_keys)
+            | (_mm512_cmpeq_epi32_mask(keys, partner_keys)
+                & _mm512_cmplt_epu32_mask(indices, partner_indices));
+
+        // lanes that keep the smaller element swap if they hold the larger one and vice versa
+        let swap = !(less ^ max_lanes);
+
+        keys = _mm512_mask_blend_epi32(swap, keys, partner_keys);
+        indices = _mm512_mask_blend_epi32(swap, indices, partner_indices);
+    }
+
+    (keys, indices)
+}
+
+/// merge two sorted runs into `output`, but stop after `limit` elements
+fn merge(left: &[(i32, u32)], right: &[(i32, u32)], limit: usize, output: &mut Vec<(i32, u32)>) {
+    let mut l = 0;
+    let mut r = 0;
+    let end = (left.len() + right.len()).min(limit);
+
+    for _ in 0..end {
+        if r >= right.len() || (l < left.len() && left[l] < right[r]) {
+            output.push(left[l]);
+            l += 1;
+        } else {
+            output.push(right[r]);
+            r += 1;
+        }
+    }
+}
+
+/// Sort the first `len` elements of `data` by `f32::total_cmp` and return the keys together with the original indices.
+/// Equal elements keep their original order and at most `limit` elements are returned.
+fn sorted_keys(data: &[__m512], len: usize, descending: bool, limit: usize) -> Vec<(i32, u32)> {
+    let limit = limit.min(len);
+
+    if limit == 0 {
+        return Vec::new();
+    }
+
+    let network = bitonic_network();
+    let padding = array_to_m512i([i32::MAX as u32; 16]);
+    let all_ones = array_to_m512i([u32::MAX; 16]);
+    let run_length = limit.min(16);
+    let mut pairs = Vec::with_capacity(data.len() * run_length);
+    let mut run_lengths = Vec::with_capacity(data.len());
+
+    unsafe {
+        for (i, register) in data.iter().enumerate() {
+            let mut keys = to_total_order_keys(*register);
+
+            if descending {
+                keys = _mm512_xor_si512(keys, all_ones);
+            }
+
+            // unused lanes get the largest key, so that they are sorted to the end
+            let valid_lanes = (len - i * 16).min(16);
+            keys = _mm512_mask_blend_epi32(0xFFFF >> (16 - valid_lanes), padding, keys);
+
+            let mut indices = [0u32; 16];
+
+            for (lane, index) in indices.iter_mut().enumerate() {
+                *index = (i * 16 + lane) as u32;
+            }
+
+            let (keys, indices) = sort_register(keys, array_to_m512i(indices), &network);
+            let keys = m512i_to_array(keys);
+            let indices = m512i_to_array(indices);
+
+            for lane in 0..run_length {
+                pairs.push((keys[lane] as i32, indices[lane]));
+            }
+
+            run_lengths.push(run_length);
+        }
+    }
+
+    let mut merged = Vec::with_capacity(pairs.len());
+
+    while run_lengths.len() > 1 {
+        let mut merged_lengths = Vec::with_capacity(run_lengths.len().div_ceil(2));
+        let mut start = 0;
+
+        for runs in run_lengths.chunks(2) {
+            let left_length = runs[0];
+            let right_length = runs.get(1).copied().unwrap_or(0);
+            let left = &pairs[start..start + left_length];
+            let right = &pairs[start + left_length..start + left_length + right_length];
+
+            let before = merged.len();
+            merge(left, right, limit, &mut merged);
+            merged_lengths.push(merged.len() - before);
+
+            start += left_length + right_length;
+        }
+
+        std::mem::swap(&mut pairs, &mut merged);
+        merged.clear();
+        run_lengths = merged_lengths;
+    }
+
+    pairs.truncate(limit);
+
+    pairs
+}
+
+/// write the values of sorted keys into consecutive registers, unused lanes are set to 0
+fn keys_to_registers(pairs: &[(i32, u32)], descending: bool, output: &mut [__m512]) {
+    for (register, chunk) in output.iter_mut().zip(pairs.chunks(16)) {
+        let mut values = [0.0; 16];
+
+        for (v, (key, _)) in values.iter_mut().zip(chunk.iter()) {
+            *v = from_total_order_key(*key, descending);
+        }
+
+        *register = array_to_m512(values);
+    }
+}
+
+impl Array<1> {
+    /// Sort the elements in ascending order.
+    /// NaNs are ordered by `f32::total_cmp`, so positive NaNs are placed after infinity and negative NaNs before negative infinity.
+    pub fn sort(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.sort_in_place();
+
+        new_array
+    }
+
+    /// Sort the elements in ascending order.
+    /// NaNs are ordered by `f32::total_cmp`, so positive NaNs are placed after infinity and negative NaNs before negative infinity.
+    pub fn sort_in_place(&mut self) {
+        let pairs = sorted_keys(&self.data, self.shape[0], false, self.shape[0]);
+        keys_to_registers(&pairs, false, &mut self.data);
+    }
+
+    /// Return the indices that would sort the array in ascending order, the sort is stable
+    pub fn argsort(&self) -> Vec<u32> {
+        let pairs = sorted_keys(&self.data, self.shape[0], false, self.shape[0]);
+
+        pairs.iter().map(|(_, index)| *index).collect()
+    }
+
+    /// Return the `k` largest elements in descending order together with their indices.
+    /// Equal elements are ordered by their index and NaNs are ordered by `f32::total_cmp`.
+    pub fn top_k(&self, k: usize) -> (Array<1>, Vec<u32>) {
+        assert!(
+            k <= self.shape[0],
+            "tried to get the top {} elements, but the array has only {} element(s)",
+            k,
+            self.shape[0]
+        );
+
+        let pairs = sorted_keys(&self.data, self.shape[0], true, k);
+        let mut values = Array::zeros(&[k]);
+        keys_to_registers(&pairs, true, &mut values.data);
+
+        (values, pairs.iter().map(|(_, index)| *index).collect())
+    }
+}
+
+impl Array<2> {
+    /// Sort each row in ascending order, NaNs are ordered by `f32::total_cmp`
+    pub fn sort_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.sort_rows_in_place();
+
+        new_array
+    }
+
+    /// Sort each row in ascending order, NaNs are ordered by `f32::total_cmp`
+    pub fn sort_rows_in_place(&mut self) {
+        let column_count = self.shape[1];
+        let registers_per_row = column_count.div_ceil(16);
+
+        if registers_per_row == 0 {
+            return;
+        }
+
+        for row in self.data.chunks_mut(registers_per_row) {
+            let pairs = sorted_keys(row, column_count, false, column_count);
+            keys_to_registers(&pairs, false, row);
+        }
+    }
+
+    /// Return the column indices that would sort each row in ascending order, the indices are stored in row-major order
+    pub fn argsort_rows(&self) -> Vec<u32> {
+        let column_count = self.shape[1];
+        let registers_per_row = column_count.div_ceil(16);
+        let mut indices = Vec::with_capacity(self.shape[0] * column_count);
+
+        if registers_per_row == 0 {
+            return indices;
+        }
+
+        for row in self.data.chunks(registers_per_row) {
+            let pairs = sorted_keys(row, column_count, false, column_count);
+            indices.extend(pairs.iter().map(|(_, index)| *index));
+        }
+
+        indices
+    }
+
+    /// Return the `k` largest elements of each row in descending order together with their column indices.
+    /// The indices are stored in row-major order.
+    pub fn top_k_rows(&self, k: usize) -> (Array<2>, Vec<u32>) {
+        let column_count = self.shape[1];
+
+        assert!(
+            k <= column_count,
+            "tried to get the top {} elements of each row, but the array has only {} column(s)",
+            k,
+            column_count
+        );
+
+        let registers_per_row = column_count.div_ceil(16);
+        let mut values = Array::zeros(&[self.shape[0], k]);
+        let mut indices = Vec::with_capacity(self.shape[0] * k);
+
+        if k == 0 {
+            return (values, indices);
+        }
+
+        for (row, output_row) in self
+            .data
+            .chunks(registers_per_row)
+            .zip(values.data.chunks_mut(k.div_ceil(16)))
+        {
+            let pairs = sorted_keys(row, column_count, true, k);
+            keys_to_registers(&pairs, true, output_row);
+            indices.extend(pairs.iter().map(|(_, index)| *index));
+        }
+
+        (values, indices)
+    }
+}
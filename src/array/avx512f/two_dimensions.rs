@@ -21,7 +21,10 @@ use std::arch::x86_64::{
     _mm512_reduce_mul_ps,
 };
 
-use crate::{Array, Mask};
+use crate::{
+    error::{check_data_length, check_non_empty_shape, unwrap_or_panic},
+    Array, Mask, Workspace,
+};
 
 use super::{array_to_m512, m512_to_array, reduce};
 
@@ -51,8 +54,7 @@ impl From<Array<2>> for Vec<f32> {
 
 impl Array<2> {
     pub fn from_vec(data: &Vec<f32>, shape: [usize; 2]) -> Self {
-        assert!(shape[0] > 0);
-        assert!(shape[1] > 0);
+        unwrap_or_panic(check_data_length(data.len(), &shape));
 
         let row_count = shape[0];
         let column_count = shape[1];
@@ -87,19 +89,7 @@ impl Array<2> {
     }
 
     pub fn get(&self, row: usize, column: usize) -> f32 {
-        if row >= self.shape[0] {
-            panic!(
-                "tried to get row {}, but the array has only {} row(s)",
-                row, self.shape[0]
-            );
-        }
-
-        if column >= self.shape[1] {
-            panic!(
-                "tried to get column {}, but the array has only {} column(s)",
-                column, self.shape[1]
-            );
-        }
+        unwrap_or_panic(self.check_index(row, column));
 
         let registers_per_row = self.shape[1].div_ceil(16);
 
@@ -107,19 +97,7 @@ impl Array<2> {
     }
 
     pub fn set(&mut self, row: usize, column: usize, value: f32) {
-        if row >= self.shape[0] {
-            panic!(
-                "tried to set row {}, but the array has only {} row(s)",
-                row, self.shape[0]
-            );
-        }
-
-        if column >= self.shape[1] {
-            panic!(
-                "tried to set column {}, but the array has only {} column(s)",
-                column, self.shape[1]
-            );
-        }
+        unwrap_or_panic(self.check_index(row, column));
 
         let registers_per_row = self.shape[1].div_ceil(16);
 
@@ -130,8 +108,7 @@ impl Array<2> {
     }
 
     pub fn sum(&self) -> f32 {
-        assert!(self.shape[0] > 0);
-        assert!(self.shape[1] > 0);
+        unwrap_or_panic(check_non_empty_shape(&self.shape));
 
         let row_count = self.shape[0];
         let column_count = self.shape[1];
@@ -160,8 +137,7 @@ impl Array<2> {
     }
 
     pub fn product(&self) -> f32 {
-        assert!(self.shape[0] > 0);
-        assert!(self.shape[1] > 0);
+        unwrap_or_panic(check_non_empty_shape(&self.shape));
 
         let row_count = self.shape[0];
         let column_count = self.shape[1];
@@ -190,8 +166,7 @@ impl Array<2> {
     }
 
     pub fn max_reduce(&self) -> f32 {
-        assert!(self.shape[0] > 0);
-        assert!(self.shape[1] > 0);
+        unwrap_or_panic(check_non_empty_shape(&self.shape));
 
         let row_count = self.shape[0];
         let column_count = self.shape[1];
@@ -220,8 +195,7 @@ impl Array<2> {
     }
 
     pub fn min_reduce(&self) -> f32 {
-        assert!(self.shape[0] > 0);
-        assert!(self.shape[1] > 0);
+        unwrap_or_panic(check_non_empty_shape(&self.shape));
 
         let row_count = self.shape[0];
         let column_count = self.shape[1];
@@ -250,7 +224,20 @@ impl Array<2> {
     }
 
     pub fn vector_multiplication(&self, other: &Array<1>) -> Array<1> {
-        assert_eq!(self.shape[1], other.shape[0]);
+        let mut result = Array::zeros(&[self.shape[0]]);
+        self.vector_multiplication_out_of_place(other, &mut result);
+
+        result
+    }
+
+    pub fn vector_multiplication_out_of_place(&self, other: &Array<1>, output: &mut Array<1>) {
+        unwrap_or_panic(self.check_vector_multiplication_out_of_place(other, output));
+
+        // the sums of no products, the loop below needs at least one register per row
+        if self.shape[1] == 0 {
+            output.set_all(0.0);
+            return;
+        }
 
         let row_count = self.shape[0];
         let column_count = self.shape[1];
@@ -261,10 +248,10 @@ impl Array<2> {
             last_register_mask = 0xFFFF >> (16 - (column_count % 16));
         }
 
-        let mut result = Vec::with_capacity(row_count);
+        let result = output.padded_elements_mut();
 
         unsafe {
-            for i in 0..row_count {
+            for (i, result) in result.iter_mut().take(row_count).enumerate() {
                 let mut sum = array_to_m512([0.0; 16]);
 
                 for j in 0..registers_per_row - 1 {
@@ -278,11 +265,9 @@ impl Array<2> {
                     last_register_mask,
                 );
 
-                result.push(_mm512_reduce_add_ps(sum));
+                *result = _mm512_reduce_add_ps(sum);
             }
         }
-
-        result.into()
     }
 
     fn transpose_chunk(chunk: &[__m512; 16]) -> [__m512; 16] {
@@ -343,62 +328,122 @@ impl Array<2> {
         padded_chunk
     }
 
-    fn transpose(&self) -> Self {
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Array::zeros(&[self.shape[1], self.shape[0]]);
+        self.transpose_out_of_place(&mut transposed);
+
+        transposed
+    }
+
+    pub fn transpose_out_of_place(&self, output: &mut Self) {
+        unwrap_or_panic(self.check_transpose_out_of_place(output));
+
         let chunk_rows = self.shape[0].div_ceil(16);
         let chunk_columns = self.shape[1].div_ceil(16);
-        let mut transposed_data = Vec::with_capacity(chunk_rows * 16 * self.shape[1]);
 
         for chunk_column in 0..chunk_columns {
-            let mut transposed_chunks = Vec::new();
+            let start_column = chunk_column * 16;
+            let end_column = ((chunk_column + 1) * 16).min(self.shape[1]);
 
             for chunk_row in 0..chunk_rows {
                 let chunk = Self::get_padded_chunk(self, chunk_row, chunk_column);
-                transposed_chunks.push(Self::transpose_chunk(&chunk));
-            }
-
-            let start_column = chunk_column * 16;
-            let end_column = ((chunk_column + 1) * 16).min(self.shape[1]);
+                let transposed_chunk = Self::transpose_chunk(&chunk);
 
-            for i in 0..(end_column - start_column) {
-                for row in 0..chunk_rows {
-                    transposed_data.push(transposed_chunks[row][i]);
+                for (i, register) in transposed_chunk
+                    .iter()
+                    .take(end_column - start_column)
+                    .enumerate()
+                {
+                    output.data[(start_column + i) * chunk_rows + chunk_row] = *register;
                 }
             }
         }
-
-        Self {
-            data: transposed_data,
-            shape: [self.shape[1], self.shape[0]],
-        }
     }
 
     pub fn matrix_multiplication(&self, matrix_b: &Self) -> Self {
+        let mut result = Array::zeros(&[self.shape[0], matrix_b.shape[1]]);
+        self.matrix_multiplication_out_of_place(matrix_b, &mut result);
+
+        result
+    }
+
+    /// `matrix_b` is still transposed into a temporary array, use
+    /// `matrix_multiplication_out_of_place_with_workspace` to avoid that allocation
+    pub fn matrix_multiplication_out_of_place(&self, matrix_b: &Self, output: &mut Self) {
+        unwrap_or_panic(self.check_matrix_multiplication_out_of_place(matrix_b, output));
+
+        self.matrix_multiplication_transposed_out_of_place(&matrix_b.transpose(), output);
+    }
+
+    /// `matrix_b` is transposed into a buffer of `workspace`, which is recycled afterwards,
+    /// so repeated calls don't allocate
+    pub fn matrix_multiplication_out_of_place_with_workspace(
+        &self,
+        matrix_b: &Self,
+        output: &mut Self,
+        workspace: &mut Workspace,
+    ) {
+        unwrap_or_panic(self.check_matrix_multiplication_out_of_place(matrix_b, output));
+
+        let mut transposed_b = workspace.array(&[matrix_b.shape[1], matrix_b.shape[0]]);
+        matrix_b.transpose_out_of_place(&mut transposed_b);
+        self.matrix_multiplication_transposed_out_of_place(&transposed_b, output);
+        workspace.recycle_array(transposed_b);
+    }
+
+    /// the matrix multiplication with `transposed_b`, the transpose of the second matrix
+    pub fn matrix_multiplication_transposed_out_of_place(
+        &self,
+        transposed_b: &Self,
+        output: &mut Self,
+    ) {
+        unwrap_or_panic(
+            self.check_matrix_multiplication_transposed_out_of_place(transposed_b, output),
+        );
+
+        // the sums of no products, the loop below needs at least one register per row
+        if self.shape[1] == 0 {
+            output.set_all(0.0);
+            return;
+        }
+
         let matrix_a = self;
         let column_chunks_a = matrix_a.shape[1].div_ceil(16);
-        let row_chunks_b = matrix_b.shape[0].div_ceil(16);
-        let column_chunks_b = matrix_b.shape[1].div_ceil(16);
-        let mut result_data = Vec::with_capacity(matrix_a.shape[0] * column_chunks_b);
-        let transposed_b = matrix_b.transpose();
+        let row_chunks_b = transposed_b.shape[1].div_ceil(16);
+        let column_chunks_b = transposed_b.shape[0].div_ceil(16);
+        let mut last_register_mask = 0xFFFF;
+
+        if !matrix_a.shape[1].is_multiple_of(16) {
+            last_register_mask = 0xFFFF >> (16 - (matrix_a.shape[1] % 16));
+        }
 
         unsafe {
             for row_a in 0..matrix_a.shape[0] {
                 for column_b in 0..column_chunks_b {
                     let mut temp_results = [array_to_m512([0.0; 16]); 16];
                     let start_column = column_b * 16;
-                    let end_column = ((column_b + 1) * 16).min(matrix_b.shape[1]);
+                    let end_column = ((column_b + 1) * 16).min(transposed_b.shape[0]);
 
                     let matrix_a_index = row_a * column_chunks_a;
 
                     for i in 0..(end_column - start_column) {
                         let matrix_b_index = (column_b * 16 + i) * row_chunks_b;
 
-                        for chunk_inner_loop_index in 0..row_chunks_b {
+                        for chunk_inner_loop_index in 0..row_chunks_b - 1 {
                             temp_results[i] = _mm512_fmadd_ps(
                                 self.data[matrix_a_index + chunk_inner_loop_index],
                                 transposed_b.data[matrix_b_index + chunk_inner_loop_index],
                                 temp_results[i],
                             );
                         }
+
+                        // the padding of the rows isn't necessarily zero
+                        temp_results[i] = _mm512_mask3_fmadd_ps(
+                            self.data[matrix_a_index + row_chunks_b - 1],
+                            transposed_b.data[matrix_b_index + row_chunks_b - 1],
+                            temp_results[i],
+                            last_register_mask,
+                        );
                     }
 
                     let mut result_register = [0.0; 16];
@@ -407,20 +452,15 @@ impl Array<2> {
                         result_register[i] = _mm512_reduce_add_ps(temp_results[i]);
                     }
 
-                    result_data.push(array_to_m512(result_register));
+                    output.data[row_a * column_chunks_b + column_b] =
+                        array_to_m512(result_register);
                 }
             }
         }
-
-        Self {
-            data: result_data,
-            shape: [matrix_a.shape[0], matrix_b.shape[1]],
-        }
     }
 
     pub fn sum_to_row_in_place_masked(&self, mask: &Mask<2>, output: &mut Array<1>) {
-        assert_eq!(&self.shape, mask.get_shape());
-        assert_eq!(output.shape[0], self.shape[1]);
+        unwrap_or_panic(self.check_sum_to_row_masked(mask, output));
 
         let registers_per_row = output.data.len();
         output.set_all(0.0);
@@ -434,8 +474,7 @@ impl Array<2> {
     }
 
     pub fn sum_to_column_in_place_masked(&self, mask: &Mask<2>, output: &mut Array<1>) {
-        assert_eq!(&self.shape, mask.get_shape());
-        assert_eq!(output.shape[0], self.shape[0]);
+        unwrap_or_panic(self.check_sum_to_column_masked(mask, output));
 
         let registers_per_row = self.shape[1].div_ceil(16);
 
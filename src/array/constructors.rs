@@ -0,0 +1,132 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::{
+    error::{check_non_empty_shape, unwrap_or_panic},
+    Array,
+};
+
+impl<const D: usize> Array<D> {
+    pub fn ones(shape: &[usize; D]) -> Self {
+        Self::new_from_value(shape, 1.0)
+    }
+
+    /// Create an array where each element is computed from its index
+    pub fn from_fn(shape: &[usize; D], mut f: impl FnMut([usize; D]) -> f32) -> Self {
+        unwrap_or_panic(check_non_empty_shape(shape));
+
+        let mut array = Self::zeros(shape);
+        let mut index = [0; D];
+
+        for row in 0..array.number_of_elements() / shape[D - 1] {
+            for (column, element) in array.row_elements_mut(row).iter_mut().enumerate() {
+                index[D - 1] = column;
+                *element = f(index);
+            }
+
+            // move to the next row
+            for axis in (0..D - 1).rev() {
+                index[axis] += 1;
+
+                if index[axis] < shape[axis] {
+                    break;
+                }
+
+                index[axis] = 0;
+            }
+        }
+
+        array
+    }
+}
+
+impl Array<1> {
+    /// Create an array with the values `start + i * step` that are smaller than `stop`, or larger for a negative step
+    pub fn arange(start: f32, stop: f32, step: f32) -> Self {
+        assert!(step != 0.0, "the step must not be 0");
+        let count = ((stop - start) / step).ceil();
+        assert!(
+            count >= 1.0 && count.is_finite(),
+            "the range from {} to {} with step {} doesn't contain any values",
+            start,
+            stop,
+            step
+        );
+
+        let mut array = Self::zeros(&[count as usize]);
+        array.fill_arithmetic_sequence(start, step);
+
+        array
+    }
+
+    /// Create an array with `count` evenly spaced values from `start` to `end`, both are included
+    pub fn linspace(start: f32, end: f32, count: usize) -> Self {
+        unwrap_or_panic(check_non_empty_shape(&[count]));
+
+        let mut array = Self::zeros(&[count]);
+
+        if count == 1 {
+            array.set(0, start);
+        } else {
+            array.fill_arithmetic_sequence(start, (end - start) / (count - 1) as f32);
+            array.set(count - 1, end);
+        }
+
+        array
+    }
+
+    /// Create an array with `count` values from `base^start` to `base^end`, the exponents are evenly spaced
+    pub fn logspace(start: f32, end: f32, count: usize, base: f32) -> Self {
+        let mut array = Self::linspace(start, end, count);
+
+        for element in array.row_elements_mut(0) {
+            *element = base.powf(*element);
+        }
+
+        array
+    }
+}
+
+impl Array<2> {
+    /// Create the `n`×`n` identity matrix
+    pub fn eye(n: usize) -> Self {
+        Self::from_diag(&Array::ones(&[n]))
+    }
+
+    /// Create a square matrix with `diagonal` on its main diagonal and zeros everywhere else
+    pub fn from_diag(diagonal: &Array<1>) -> Self {
+        let n = diagonal.shape[0];
+        let mut array = Self::zeros(&[n, n]);
+
+        for (i, value) in diagonal.row_elements(0).iter().enumerate() {
+            array.row_elements_mut(i)[i] = *value;
+        }
+
+        array
+    }
+
+    /// Copy the main diagonal into a new array
+    pub fn diag(&self) -> Array<1> {
+        let n = self.shape[0].min(self.shape[1]);
+        let mut diagonal = Array::zeros(&[n]);
+
+        for (i, element) in diagonal.row_elements_mut(0).iter_mut().enumerate() {
+            *element = self.row_elements(i)[i];
+        }
+
+        diagonal
+    }
+}
@@ -14,10 +14,24 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::{
+    io::{self, Read, Write},
+    ops::{Add, Mul},
+};
+
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{Array, Mask};
+use crate::{
+    binary::{read_f32s, read_header, write_f32_payload, write_header, Kind, READ_CHUNK_SIZE},
+    error::{check_data_length, check_deserialized_shape, check_same_length, unwrap_or_panic},
+    Array, Mask, Workspace,
+};
+
+use super::{
+    assert_same_shape2, assert_same_shape3, assert_same_shape_mask, assert_same_shape_with_mask2,
+    assert_same_shape_with_mask3,
+};
 
 impl<const D: usize> From<Array<D>> for Vec<f32> {
     fn from(value: Array<D>) -> Self {
@@ -46,83 +60,19 @@ fn calculate_size(shape: &[usize]) -> usize {
     size
 }
 
-fn assert_same_shape2<const D: usize>(a: &Array<D>, b: &Array<D>) {
-    assert_eq!(
-        a.shape, b.shape,
-        "the lengths of array one and two don't match: {:?} != {:?}",
-        a.shape, b.shape
-    );
-}
-
-fn assert_same_shape_mask<const D: usize>(a: &Array<D>, mask: &Mask<D>) {
-    assert_eq!(
-        &a.shape,
-        mask.get_shape(),
-        "the shapes of the array and the mask don't match: {:?} != {:?}",
-        a.shape,
-        mask.get_shape()
-    );
-}
-
-fn assert_same_shape_with_mask2<const D: usize>(a: &Array<D>, b: &Array<D>, mask: &Mask<D>) {
-    assert_eq!(
-        a.shape, b.shape,
-        "the lengths of array one and two don't match: {:?} != {:?}",
-        a.shape, b.shape
-    );
-    assert_eq!(
-        &a.shape,
-        mask.get_shape(),
-        "the lengths of array one and mask don't match: {:?} != {:?}",
-        a.shape,
-        mask.get_shape()
-    );
-}
-
-fn assert_same_shape3<const D: usize>(a: &Array<D>, b: &Array<D>, c: &Array<D>) {
-    assert_eq!(
-        a.shape, b.shape,
-        "the lengths of array one and two don't match: {:?} != {:?}",
-        a.shape, b.shape
-    );
-    assert_eq!(
-        b.shape, c.shape,
-        "the lengths of array two and three don't match: {:?} != {:?}",
-        b.shape, c.shape
-    );
-}
-
-fn assert_same_shape_with_mask3<const D: usize>(
-    a: &Array<D>,
-    b: &Array<D>,
-    c: &Array<D>,
-    mask: &Mask<D>,
-) {
-    assert_eq!(
-        a.shape, b.shape,
-        "the lengths of array one and two don't match: {:?} != {:?}",
-        a.shape, b.shape
-    );
-    assert_eq!(
-        b.shape, c.shape,
-        "the lengths of array two and three don't match: {:?} != {:?}",
-        b.shape, c.shape
-    );
-    assert_eq!(
-        &a.shape,
-        mask.get_shape(),
-        "the lengths of array one and mask don't match: {:?} != {:?}",
-        a.shape,
-        mask.get_shape()
-    );
-}
-
 #[derive(Serialize)]
 struct ArraySerializerProxy<'a> {
     data: &'a Vec<f32>,
     shape: Vec<usize>,
 }
 
+impl<const D: usize> PartialEq for Array<D> {
+    /// compares the shapes and the elements
+    fn eq(&self, other: &Self) -> bool {
+        self.shape == other.shape && self.data == other.data
+    }
+}
+
 impl<const D: usize> Serialize for Array<D> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -148,10 +98,8 @@ impl<'de, const D: usize> Deserialize<'de> for Array<D> {
         De: serde::Deserializer<'de>,
     {
         let proxy = ArrayDeserializerProxy::deserialize(deserializer)?;
-        assert_eq!(proxy.shape.len(), D);
-
-        let mut shape = [0; D];
-        shape.copy_from_slice(&proxy.shape[..D]);
+        let shape: [usize; D] = check_deserialized_shape(&proxy.shape, proxy.data.len())
+            .map_err(serde::de::Error::custom)?;
 
         Ok(Array {
             data: proxy.data,
@@ -160,6 +108,125 @@ impl<'de, const D: usize> Deserialize<'de> for Array<D> {
     }
 }
 
+impl<const D: usize> Array<D> {
+    /// create an array from elements in row-major order, the number of elements must match the shape
+    #[cfg(feature = "arrow")]
+    pub(crate) fn from_elements(elements: Vec<f32>, shape: [usize; D]) -> Self {
+        Array {
+            data: elements,
+            shape,
+        }
+    }
+
+    /// the number of elements of a row, there is no padding
+    pub(crate) fn padded_row_length(column_count: usize) -> usize {
+        column_count
+    }
+
+    /// the distance between the first elements of two consecutive rows
+    pub(crate) fn row_stride(&self) -> usize {
+        Self::padded_row_length(*self.shape.last().unwrap())
+    }
+
+    /// all elements in row-major order, there is no padding
+    pub(crate) fn padded_elements(&self) -> &[f32] {
+        &self.data
+    }
+
+    /// all elements in row-major order, there is no padding
+    pub(crate) fn padded_elements_mut(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+
+    /// the elements of a row, the rows are counted over all axes except the last one
+    pub(crate) fn row_elements(&self, row: usize) -> &[f32] {
+        let column_count = *self.shape.last().unwrap();
+
+        &self.data[row * column_count..(row + 1) * column_count]
+    }
+
+    /// the elements of a row, the rows are counted over all axes except the last one
+    pub(crate) fn row_elements_mut(&mut self, row: usize) -> &mut [f32] {
+        let column_count = *self.shape.last().unwrap();
+
+        &mut self.data[row * column_count..(row + 1) * column_count]
+    }
+
+    /// the elements in row-major order
+    pub(crate) fn to_elements(&self) -> Vec<f32> {
+        self.data.clone()
+    }
+
+    /// read elements in row-major order as little-endian f32
+    pub(crate) fn read_elements(reader: &mut impl Read, shape: [usize; D]) -> io::Result<Self> {
+        let element_count = calculate_size(&shape);
+        let mut data = Vec::new();
+
+        // the data is read in chunks, so that a corrupted shape can't exhaust the memory
+        while data.len() < element_count {
+            let start = data.len();
+            data.resize(start + (element_count - start).min(READ_CHUNK_SIZE), 0.0);
+            read_f32s(reader, &mut data[start..])?;
+        }
+
+        Ok(Array { data, shape })
+    }
+
+    /// write the elements in row-major order as little-endian f32
+    pub(crate) fn write_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        write_f32_payload(writer, &self.data)
+    }
+
+    /// write the elements as little-endian f32, each row is padded with zeros to a multiple of 16 elements
+    #[cfg(feature = "mmap")]
+    pub(crate) fn write_padded_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        let column_count = *self.shape.last().unwrap();
+        let padding = [0u8; 64];
+
+        if column_count == 0 {
+            return Ok(());
+        }
+
+        for row in self.data.chunks(column_count) {
+            write_f32_payload(writer, row)?;
+            writer.write_all(&padding[..(column_count.div_ceil(16) * 16 - column_count) * 4])?;
+        }
+
+        Ok(())
+    }
+
+    /// create an array from the padded elements in `bytes`, the elements are copied
+    /// because the rows of this implementation aren't padded
+    #[cfg(feature = "mmap")]
+    pub(crate) fn from_mapped(bytes: &[u8], shape: [usize; D]) -> Self {
+        let column_count = *shape.last().unwrap();
+        let mut data = Vec::with_capacity(calculate_size(&shape));
+
+        if column_count > 0 {
+            for row in bytes.chunks_exact(column_count.div_ceil(16) * 64) {
+                for b in row[..column_count * 4].chunks_exact(4) {
+                    data.push(f32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+                }
+            }
+        }
+
+        Array { data, shape }
+    }
+
+    /// Write the array in a compact binary format, which can be read with `read_from`
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        write_header(&mut writer, Kind::Array, &self.shape)?;
+        self.write_elements(&mut writer)
+    }
+
+    /// Read an array in the binary format written by `write_to`
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let shape = read_header(&mut reader, Kind::Array)?;
+
+        Array::read_elements(&mut reader, shape)
+    }
+}
+
 impl<const D: usize> Array<D> {
     pub fn zeros(shape: &[usize; D]) -> Self {
         Self {
@@ -175,6 +242,21 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    /// the number of elements of an array with the given shape
+    pub(crate) fn buffer_length(shape: &[usize; D]) -> usize {
+        calculate_size(shape)
+    }
+
+    /// an array that reuses the allocation of `data`, the values of the elements are unspecified
+    pub(crate) fn from_buffer(shape: &[usize; D], mut data: Vec<f32>) -> Self {
+        data.resize(Self::buffer_length(shape), 0.0);
+
+        Self {
+            data,
+            shape: *shape,
+        }
+    }
+
     pub fn random_seed() -> [u32; 16] {
         let mut rng = SmallRng::from_entropy();
         let mut seed = [0; 16];
@@ -221,7 +303,7 @@ impl<const D: usize> Array<D> {
 
     /// set the elements to `value` where `mask` is 1
     pub fn set_masked(&mut self, value: f32, mask: &Mask<D>) {
-        assert_eq!(&self.shape, mask.get_shape()); // TODO: add messages to asserts
+        assert_same_shape_mask(self, mask);
 
         for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
             if *m {
@@ -232,7 +314,7 @@ impl<const D: usize> Array<D> {
 
     /// set the elements to `v1` where `mask` is 0 and to `v2` where `mask` is 1
     pub fn set_masked2(&mut self, v1: f32, v2: f32, mask: &Mask<D>) {
-        assert_eq!(&self.shape, mask.get_shape());
+        assert_same_shape_mask(self, mask);
 
         for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
             if *m {
@@ -244,7 +326,7 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn copy(&mut self, other: &Array<D>) {
-        assert_eq!(self.shape, other.shape);
+        assert_same_shape2(self, other);
 
         for (d1, d2) in self.data.iter_mut().zip(other.data.iter()) {
             *d1 = *d2;
@@ -286,6 +368,108 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    /// create a new array with the elements from `if_true` where `mask` is 1 and from `if_false` where `mask` is 0
+    pub fn select(mask: &Mask<D>, if_true: &Self, if_false: &Self) -> Self {
+        let mut new_array = Self::zeros(mask.get_shape());
+        Self::select_out_of_place(mask, if_true, if_false, &mut new_array);
+
+        new_array
+    }
+
+    pub fn select_out_of_place(mask: &Mask<D>, if_true: &Self, if_false: &Self, output: &mut Self) {
+        assert_same_shape_with_mask3(if_true, if_false, output, mask);
+
+        for (((t, f), o), m) in if_true
+            .data
+            .iter()
+            .zip(if_false.data.iter())
+            .zip(output.data.iter_mut())
+            .zip(mask.get_masks().iter())
+        {
+            *o = if *m { *t } else { *f };
+        }
+    }
+
+    /// create a new array with the elements from `if_true` where `mask` is 1 and `if_false` where `mask` is 0
+    pub fn select_scalar(mask: &Mask<D>, if_true: &Self, if_false: f32) -> Self {
+        let mut new_array = Self::zeros(mask.get_shape());
+        Self::select_scalar_out_of_place(mask, if_true, if_false, &mut new_array);
+
+        new_array
+    }
+
+    pub fn select_scalar_out_of_place(
+        mask: &Mask<D>,
+        if_true: &Self,
+        if_false: f32,
+        output: &mut Self,
+    ) {
+        assert_same_shape_with_mask2(if_true, output, mask);
+
+        for ((t, o), m) in if_true
+            .data
+            .iter()
+            .zip(output.data.iter_mut())
+            .zip(mask.get_masks().iter())
+        {
+            *o = if *m { *t } else { if_false };
+        }
+    }
+
+    /// create a new array that is `if_true` where `mask` is 1 and `if_false` where `mask` is 0
+    pub fn select_scalars(mask: &Mask<D>, if_true: f32, if_false: f32) -> Self {
+        let mut new_array = Self::zeros(mask.get_shape());
+        Self::select_scalars_out_of_place(mask, if_true, if_false, &mut new_array);
+
+        new_array
+    }
+
+    pub fn select_scalars_out_of_place(
+        mask: &Mask<D>,
+        if_true: f32,
+        if_false: f32,
+        output: &mut Self,
+    ) {
+        assert_same_shape_mask(output, mask);
+
+        for (o, m) in output.data.iter_mut().zip(mask.get_masks().iter()) {
+            *o = if *m { if_true } else { if_false };
+        }
+    }
+
+    /// pack the elements where `mask` is 1 into a dense array, in row-major order
+    pub fn compress(&self, mask: &Mask<D>) -> Array<1> {
+        assert_same_shape_mask(self, mask);
+
+        let mut data = Vec::with_capacity(mask.number_of_true_elements());
+
+        for (d, m) in self.data.iter().zip(mask.get_masks().iter()) {
+            if *m {
+                data.push(*d);
+            }
+        }
+
+        data.into()
+    }
+
+    /// write the elements of `values` in row-major order to the positions where `mask` is 1,
+    /// this is the inverse of `compress`
+    pub fn expand_into(&mut self, values: &Array<1>, mask: &Mask<D>) {
+        assert_same_shape_mask(self, mask);
+        unwrap_or_panic(check_same_length(
+            mask.number_of_true_elements(),
+            values.shape[0],
+        ));
+
+        let mut values = values.data.iter();
+
+        for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
+            if *m {
+                *d = *values.next().unwrap();
+            }
+        }
+    }
+
     pub fn add(&self, other: &Self) -> Self {
         let mut new_array = self.clone();
         new_array.add_in_place(other);
@@ -524,6 +708,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn max_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = d.max(scalar);
+        }
+    }
+
     pub fn min(&self, other: &Self) -> Self {
         let mut new_array = self.clone();
         new_array.min_in_place(other);
@@ -590,6 +782,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn min_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = d.min(scalar);
+        }
+    }
+
     pub fn add_scalar(&self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.add_scalar_in_place(scalar);
@@ -613,6 +813,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn add_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = *d + scalar;
+        }
+    }
+
     pub fn sub_scalar(&self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.sub_scalar_in_place(scalar);
@@ -636,6 +844,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn sub_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = *d - scalar;
+        }
+    }
+
     pub fn mul_scalar(&self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.mul_scalar_in_place(scalar);
@@ -659,6 +875,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn mul_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = *d * scalar;
+        }
+    }
+
     pub fn div_scalar(&self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.div_scalar_in_place(scalar);
@@ -682,6 +906,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn div_scalar_out_of_place(&self, scalar: f32, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = *d / scalar;
+        }
+    }
+
     pub fn fmadd(&self, a: &Self, b: &Self) -> Self {
         let mut new_array = self.clone();
         new_array.fmadd_in_place(a, b);
@@ -713,6 +945,21 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn fmadd_out_of_place(&self, a: &Self, b: &Self, output: &mut Self) {
+        assert_same_shape3(self, a, b);
+        assert_same_shape2(self, output);
+
+        for (((a, b), c), o) in a
+            .data
+            .iter()
+            .zip(b.data.iter())
+            .zip(self.data.iter())
+            .zip(output.data.iter_mut())
+        {
+            *o = *a * *b + *c;
+        }
+    }
+
     pub fn fmadd_scalar(&self, a: &Self, scalar: f32) -> Self {
         let mut new_array = self.clone();
         new_array.fmadd_scalar_in_place(a, scalar);
@@ -743,6 +990,19 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn fmadd_scalar_out_of_place(&self, a: &Self, scalar: f32, output: &mut Self) {
+        assert_same_shape3(self, a, output);
+
+        for ((a, b), o) in a
+            .data
+            .iter()
+            .zip(self.data.iter())
+            .zip(output.data.iter_mut())
+        {
+            *o = *a * scalar + *b;
+        }
+    }
+
     pub fn sqrt(&self) -> Self {
         let mut new_array = self.clone();
         new_array.sqrt_in_place();
@@ -757,6 +1017,8 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn sqrt_in_place_masked(&mut self, mask: &Mask<D>) {
+        assert_same_shape_mask(self, mask);
+
         for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
             if *m {
                 *d = d.sqrt();
@@ -764,6 +1026,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn sqrt_out_of_place(&self, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = d.sqrt();
+        }
+    }
+
     pub fn square(&self) -> Self {
         let mut new_array = self.clone();
         new_array.square_in_place();
@@ -778,6 +1048,8 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn square_in_place_masked(&mut self, mask: &Mask<D>) {
+        assert_same_shape_mask(self, mask);
+
         for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
             if *m {
                 *d = *d * *d;
@@ -785,6 +1057,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn square_out_of_place(&self, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = *d * *d;
+        }
+    }
+
     pub fn abs(&self) -> Self {
         let mut new_array = self.clone();
         new_array.abs_in_place();
@@ -799,6 +1079,8 @@ impl<const D: usize> Array<D> {
     }
 
     pub fn abs_in_place_masked(&mut self, mask: &Mask<D>) {
+        assert_same_shape_mask(self, mask);
+
         for (d, m) in self.data.iter_mut().zip(mask.get_masks().iter()) {
             if *m {
                 *d = d.abs();
@@ -806,6 +1088,14 @@ impl<const D: usize> Array<D> {
         }
     }
 
+    pub fn abs_out_of_place(&self, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = d.abs();
+        }
+    }
+
     pub fn sum(&self) -> f32 {
         let mut sum = 0.0;
 
@@ -891,7 +1181,7 @@ impl<const D: usize> Array<D> {
         mask: &mut Mask<D>,
         func: fn(&f32, &f32) -> bool,
     ) {
-        assert_eq!(&a.shape, mask.get_shape());
+        assert_same_shape_mask(a, mask);
 
         for (d, m) in a.data.iter().zip(mask.get_masks_mut().iter_mut()) {
             *m = func(d, &scalar);
@@ -1006,32 +1296,105 @@ impl<const D: usize> Array<D> {
             *d = d.exp()
         }
     }
+
+    pub fn exp_out_of_place(&self, output: &mut Self) {
+        assert_same_shape2(self, output);
+
+        for (d, o) in self.data.iter().zip(output.data.iter_mut()) {
+            *o = d.exp();
+        }
+    }
+}
+
+/// Scan the elements of a row, elements where `masks` is false are left unchanged and don't contribute to the result
+fn scan_row(data: &mut [f32], masks: Option<&[bool]>, identity: f32, func: fn(f32, f32) -> f32) {
+    let mut carry = identity;
+
+    for (i, d) in data.iter_mut().enumerate() {
+        if masks.is_none_or(|m| m[i]) {
+            carry = func(carry, *d);
+            *d = carry;
+        }
+    }
+}
+
+/// Scan each column, elements where `masks` is false are left unchanged and don't contribute to the result
+fn scan_columns(
+    data: &mut [f32],
+    columns: usize,
+    masks: Option<&[bool]>,
+    identity: f32,
+    func: fn(f32, f32) -> f32,
+) {
+    let mut carries = vec![identity; columns];
+
+    for (i, d) in data.iter_mut().enumerate() {
+        if masks.is_none_or(|m| m[i]) {
+            let carry = &mut carries[i % columns];
+            *carry = func(*carry, *d);
+            *d = *carry;
+        }
+    }
+}
+
+/// Return the indices of the first `limit` elements after a stable sort by `f32::total_cmp`
+fn argsort(data: &[f32], descending: bool, limit: usize) -> Vec<u32> {
+    let mut indices: Vec<u32> = (0..data.len() as u32).collect();
+
+    if descending {
+        indices.sort_by(|a, b| data[*b as usize].total_cmp(&data[*a as usize]));
+    } else {
+        indices.sort_by(|a, b| data[*a as usize].total_cmp(&data[*b as usize]));
+    }
+
+    indices.truncate(limit);
+
+    indices
+}
+
+fn assert_index_in_bounds(index: u32, len: usize) {
+    if index as usize >= len {
+        panic!(
+            "tried to access index {}, but the array has only {} element(s)",
+            index, len
+        );
+    }
+}
+
+fn assert_same_length(indices: &[u32], other: &Array<1>, name: &str) {
+    assert_eq!(
+        indices.len(),
+        other.shape[0],
+        "the number of indices and the length of {} don't match: {} != {}",
+        name,
+        indices.len(),
+        other.shape[0]
+    );
 }
 
 impl Array<1> {
     pub fn get(&self, index: usize) -> f32 {
-        if index >= self.shape[0] {
-            panic!(
-                "tried to get index {}, but the array has only {} element(s)",
-                index, self.shape[0]
-            );
-        }
+        unwrap_or_panic(self.check_index(index));
 
         self.data[index]
     }
 
     pub fn set(&mut self, index: usize, value: f32) {
-        if index >= self.shape[0] {
-            panic!(
-                "tried to set index {}, but the array has only {} element(s)",
-                index, self.shape[0]
-            );
-        }
+        unwrap_or_panic(self.check_index(index));
 
         self.data[index] = value;
     }
 
+    /// set the element `i` to `start + i * step`
+    pub(crate) fn fill_arithmetic_sequence(&mut self, start: f32, step: f32) {
+        for (i, d) in self.data.iter_mut().enumerate() {
+            *d = (i as f32).mul_add(step, start);
+        }
+    }
+
     pub fn dot_product(&self, other: &Self) -> f32 {
+        assert_same_shape2(self, other);
+
         let mut result = 0.0;
 
         for (v1, v2) in self.data.iter().zip(other.data.iter()) {
@@ -1041,42 +1404,247 @@ impl Array<1> {
         result
     }
 
-    /// Copy the array `k`-times into `output`
-    pub fn tile_in_place(&self, k: usize, output: &mut Array<1>) {
-        assert!(
-            self.shape[0] % 16 == 0,
-            "the number of elements needs to be a multiple of 16"
-        );
-        assert_eq!(
-            self.shape[0] * k,
-            output.shape[0],
-            "the number of elements in output must be k-times more than the elements in this array"
-        );
+    /// collect the elements at `indices` into a new array
+    pub fn gather(&self, indices: &[u32]) -> Array<1> {
+        let mut output = Array::zeros(&[indices.len()]);
+        self.gather_in_place(indices, &mut output);
 
-        let self_len = self.data.len();
+        output
+    }
 
-        for (i, d) in output.data.iter_mut().enumerate() {
-            *d = self.data[i % self_len];
+    /// collect the elements at `indices` into `output`
+    pub fn gather_in_place(&self, indices: &[u32], output: &mut Array<1>) {
+        assert_same_length(indices, output, "output");
+
+        for (i, o) in indices.iter().zip(output.data.iter_mut()) {
+            assert_index_in_bounds(*i, self.shape[0]);
+            *o = self.data[*i as usize];
         }
     }
 
-    /// Repeat each element of the array `k`-times and store the result in `output`
-    pub fn repeat_in_place(&self, k: usize, output: &mut Array<1>) {
-        let self_len = self.shape[0];
-
-        assert!(
-            self_len % 16 == 0,
-            "the number of elements needs to be a multiple of 16"
-        );
-        assert!(k % 16 == 0, "k needs to be a multiple of 16");
+    /// collect the elements at `indices` into `output` where `mask` is 1, indices where `mask` is 0 are not accessed
+    pub fn gather_in_place_masked(&self, indices: &[u32], mask: &Mask<1>, output: &mut Array<1>) {
+        assert_same_length(indices, output, "output");
         assert_eq!(
-            self_len * k,
-            output.shape[0],
-            "the number of elements in output must be k-times more than the elements in this array"
+            &[indices.len()],
+            mask.get_shape(),
+            "the number of indices and the length of the mask don't match"
         );
 
-        let mut index = 0;
-
+        for ((i, o), m) in indices
+            .iter()
+            .zip(output.data.iter_mut())
+            .zip(mask.get_masks().iter())
+        {
+            if *m {
+                assert_index_in_bounds(*i, self.shape[0]);
+                *o = self.data[*i as usize];
+            }
+        }
+    }
+
+    /// write `values` to the positions given by `indices`, if an index occurs multiple times the last value is kept
+    pub fn scatter_in_place(&mut self, indices: &[u32], values: &Array<1>) {
+        assert_same_length(indices, values, "values");
+
+        for (i, v) in indices.iter().zip(values.data.iter()) {
+            assert_index_in_bounds(*i, self.shape[0]);
+            self.data[*i as usize] = *v;
+        }
+    }
+
+    /// write `values` to the positions given by `indices` where `mask` is 1
+    pub fn scatter_in_place_masked(&mut self, indices: &[u32], values: &Array<1>, mask: &Mask<1>) {
+        assert_same_length(indices, values, "values");
+        assert_eq!(
+            &[indices.len()],
+            mask.get_shape(),
+            "the number of indices and the length of the mask don't match"
+        );
+
+        for ((i, v), m) in indices
+            .iter()
+            .zip(values.data.iter())
+            .zip(mask.get_masks().iter())
+        {
+            if *m {
+                assert_index_in_bounds(*i, self.shape[0]);
+                self.data[*i as usize] = *v;
+            }
+        }
+    }
+
+    /// add `values` to the elements at `indices`, values for repeated indices are accumulated
+    pub fn scatter_add_in_place(&mut self, indices: &[u32], values: &Array<1>) {
+        assert_same_length(indices, values, "values");
+
+        for (i, v) in indices.iter().zip(values.data.iter()) {
+            assert_index_in_bounds(*i, self.shape[0]);
+            self.data[*i as usize] += *v;
+        }
+    }
+
+    /// add `values` to the elements at `indices` where `mask` is 1, values for repeated indices are accumulated
+    pub fn scatter_add_in_place_masked(
+        &mut self,
+        indices: &[u32],
+        values: &Array<1>,
+        mask: &Mask<1>,
+    ) {
+        assert_same_length(indices, values, "values");
+        assert_eq!(
+            &[indices.len()],
+            mask.get_shape(),
+            "the number of indices and the length of the mask don't match"
+        );
+
+        for ((i, v), m) in indices
+            .iter()
+            .zip(values.data.iter())
+            .zip(mask.get_masks().iter())
+        {
+            if *m {
+                assert_index_in_bounds(*i, self.shape[0]);
+                self.data[*i as usize] += *v;
+            }
+        }
+    }
+
+    /// Sort the elements in ascending order.
+    /// NaNs are ordered by `f32::total_cmp`, so positive NaNs are placed after infinity and negative NaNs before negative infinity.
+    pub fn sort(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.sort_in_place();
+
+        new_array
+    }
+
+    /// Sort the elements in ascending order.
+    /// NaNs are ordered by `f32::total_cmp`, so positive NaNs are placed after infinity and negative NaNs before negative infinity.
+    pub fn sort_in_place(&mut self) {
+        self.data.sort_by(f32::total_cmp);
+    }
+
+    /// Return the indices that would sort the array in ascending order, the sort is stable
+    pub fn argsort(&self) -> Vec<u32> {
+        argsort(&self.data, false, self.shape[0])
+    }
+
+    /// Return the `k` largest elements in descending order together with their indices.
+    /// Equal elements are ordered by their index and NaNs are ordered by `f32::total_cmp`.
+    pub fn top_k(&self, k: usize) -> (Array<1>, Vec<u32>) {
+        assert!(
+            k <= self.shape[0],
+            "tried to get the top {} elements, but the array has only {} element(s)",
+            k,
+            self.shape[0]
+        );
+
+        let indices = argsort(&self.data, true, k);
+        let values: Vec<f32> = indices.iter().map(|i| self.data[*i as usize]).collect();
+
+        (values.into(), indices)
+    }
+
+    /// cumulative sum of the elements
+    pub fn cumsum(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumsum_in_place();
+
+        new_array
+    }
+
+    pub fn cumsum_in_place(&mut self) {
+        scan_row(&mut self.data, None, 0.0, f32::add);
+    }
+
+    /// cumulative sum of the elements where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumsum_in_place_masked(&mut self, mask: &Mask<1>) {
+        assert_same_shape_mask(self, mask);
+        scan_row(&mut self.data, Some(mask.get_masks()), 0.0, f32::add);
+    }
+
+    /// cumulative product of the elements
+    pub fn cumprod(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumprod_in_place();
+
+        new_array
+    }
+
+    pub fn cumprod_in_place(&mut self) {
+        scan_row(&mut self.data, None, 1.0, f32::mul);
+    }
+
+    /// cumulative product of the elements where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumprod_in_place_masked(&mut self, mask: &Mask<1>) {
+        assert_same_shape_mask(self, mask);
+        scan_row(&mut self.data, Some(mask.get_masks()), 1.0, f32::mul);
+    }
+
+    /// cumulative maximum of the elements
+    pub fn cummax(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummax_in_place();
+
+        new_array
+    }
+
+    pub fn cummax_in_place(&mut self) {
+        scan_row(&mut self.data, None, f32::NEG_INFINITY, f32::max);
+    }
+
+    /// cumulative maximum of the elements where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummax_in_place_masked(&mut self, mask: &Mask<1>) {
+        assert_same_shape_mask(self, mask);
+        scan_row(
+            &mut self.data,
+            Some(mask.get_masks()),
+            f32::NEG_INFINITY,
+            f32::max,
+        );
+    }
+
+    /// cumulative minimum of the elements
+    pub fn cummin(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummin_in_place();
+
+        new_array
+    }
+
+    pub fn cummin_in_place(&mut self) {
+        scan_row(&mut self.data, None, f32::INFINITY, f32::min);
+    }
+
+    /// cumulative minimum of the elements where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummin_in_place_masked(&mut self, mask: &Mask<1>) {
+        assert_same_shape_mask(self, mask);
+        scan_row(
+            &mut self.data,
+            Some(mask.get_masks()),
+            f32::INFINITY,
+            f32::min,
+        );
+    }
+
+    /// Copy the array `k`-times into `output`
+    pub fn tile_in_place(&self, k: usize, output: &mut Array<1>) {
+        unwrap_or_panic(self.check_tile(k, output));
+
+        let self_len = self.data.len();
+
+        for (i, d) in output.data.iter_mut().enumerate() {
+            *d = self.data[i % self_len];
+        }
+    }
+
+    /// Repeat each element of the array `k`-times and store the result in `output`
+    pub fn repeat_in_place(&self, k: usize, output: &mut Array<1>) {
+        unwrap_or_panic(self.check_repeat(k, output));
+
+        let mut index = 0;
+
         for i in 0..self.data.len() {
             let value = self.data[i];
 
@@ -1088,8 +1656,7 @@ impl Array<1> {
     }
 
     pub fn repeat_as_row_in_place(&self, k: usize, output: &mut Array<2>) {
-        assert_eq!(output.shape[0], k);
-        assert_eq!(output.shape[1], self.shape[0]);
+        unwrap_or_panic(self.check_repeat_as_row(k, output));
 
         for (i, m) in output.data.iter_mut().enumerate() {
             *m = self.data[i % self.shape[0]];
@@ -1097,8 +1664,7 @@ impl Array<1> {
     }
 
     pub fn repeat_as_column_in_place(&self, k: usize, output: &mut Array<2>) {
-        assert_eq!(output.shape[0], self.shape[0]);
-        assert_eq!(output.shape[1], k);
+        unwrap_or_panic(self.check_repeat_as_column(k, output));
 
         for i in 0..output.shape[0] {
             let value = self.get(i);
@@ -1110,10 +1676,291 @@ impl Array<1> {
     }
 }
 
+/// Operations between every row or column of a matrix and a vector
+impl Array<2> {
+    fn row_broadcast_in_place(&mut self, row: &Array<1>, func: fn(f32, f32) -> f32) {
+        unwrap_or_panic(self.check_row_broadcast(row));
+
+        for elements in self.data.chunks_mut(self.shape[1]) {
+            for (l, r) in elements.iter_mut().zip(row.data.iter()) {
+                *l = func(*l, *r);
+            }
+        }
+    }
+
+    fn column_broadcast_in_place(&mut self, column: &Array<1>, func: fn(f32, f32) -> f32) {
+        unwrap_or_panic(self.check_column_broadcast(column));
+
+        for (elements, value) in self.data.chunks_mut(self.shape[1]).zip(column.data.iter()) {
+            for l in elements.iter_mut() {
+                *l = func(*l, *value);
+            }
+        }
+    }
+
+    fn compare_row_broadcast(&self, row: &Array<1>, func: fn(&f32, &f32) -> bool) -> Mask<2> {
+        unwrap_or_panic(self.check_row_broadcast(row));
+        let mut data = Vec::with_capacity(self.data.len());
+
+        for elements in self.data.chunks(self.shape[1]) {
+            for (l, r) in elements.iter().zip(row.data.iter()) {
+                data.push(func(l, r));
+            }
+        }
+
+        Mask::new_from_data(self.shape, data)
+    }
+
+    fn compare_column_broadcast(&self, column: &Array<1>, func: fn(&f32, &f32) -> bool) -> Mask<2> {
+        unwrap_or_panic(self.check_column_broadcast(column));
+        let mut data = Vec::with_capacity(self.data.len());
+
+        for (elements, value) in self.data.chunks(self.shape[1]).zip(column.data.iter()) {
+            for l in elements.iter() {
+                data.push(func(l, value));
+            }
+        }
+
+        Mask::new_from_data(self.shape, data)
+    }
+
+    /// `self += a * row` where `row` is added to every row
+    pub fn fmadd_row_broadcast_in_place(&mut self, a: &Self, row: &Array<1>) {
+        assert_same_shape2(self, a);
+        unwrap_or_panic(self.check_row_broadcast(row));
+        let column_count = self.shape[1];
+
+        for (c_elements, a_elements) in self
+            .data
+            .chunks_mut(column_count)
+            .zip(a.data.chunks(column_count))
+        {
+            for ((c, a), b) in c_elements
+                .iter_mut()
+                .zip(a_elements.iter())
+                .zip(row.data.iter())
+            {
+                *c += *a * *b;
+            }
+        }
+    }
+
+    /// `self += a * column` where `column` holds one factor per row
+    pub fn fmadd_column_broadcast_in_place(&mut self, a: &Self, column: &Array<1>) {
+        assert_same_shape2(self, a);
+        unwrap_or_panic(self.check_column_broadcast(column));
+        let column_count = self.shape[1];
+
+        for ((c_elements, a_elements), value) in self
+            .data
+            .chunks_mut(column_count)
+            .zip(a.data.chunks(column_count))
+            .zip(column.data.iter())
+        {
+            for (c, a) in c_elements.iter_mut().zip(a_elements.iter()) {
+                *c += *a * *value;
+            }
+        }
+    }
+
+    pub fn add_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.add_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn add_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, |l, r| l + r);
+    }
+
+    pub fn add_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.add_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn add_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, |l, r| l + r);
+    }
+
+    pub fn sub_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.sub_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn sub_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, |l, r| l - r);
+    }
+
+    pub fn sub_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.sub_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn sub_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, |l, r| l - r);
+    }
+
+    pub fn mul_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.mul_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn mul_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, |l, r| l * r);
+    }
+
+    pub fn mul_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.mul_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn mul_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, |l, r| l * r);
+    }
+
+    pub fn div_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.div_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn div_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, |l, r| l / r);
+    }
+
+    pub fn div_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.div_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn div_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, |l, r| l / r);
+    }
+
+    pub fn max_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.max_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn max_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, f32::max);
+    }
+
+    pub fn max_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.max_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn max_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, f32::max);
+    }
+
+    pub fn min_row_broadcast(&self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.min_row_broadcast_in_place(row);
+
+        new_array
+    }
+
+    pub fn min_row_broadcast_in_place(&mut self, row: &Array<1>) {
+        self.row_broadcast_in_place(row, f32::min);
+    }
+
+    pub fn min_column_broadcast(&self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.min_column_broadcast_in_place(column);
+
+        new_array
+    }
+
+    pub fn min_column_broadcast_in_place(&mut self, column: &Array<1>) {
+        self.column_broadcast_in_place(column, f32::min);
+    }
+
+    pub fn fmadd_row_broadcast(&self, a: &Self, row: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.fmadd_row_broadcast_in_place(a, row);
+
+        new_array
+    }
+
+    pub fn fmadd_column_broadcast(&self, a: &Self, column: &Array<1>) -> Self {
+        let mut new_array = self.clone();
+        new_array.fmadd_column_broadcast_in_place(a, column);
+
+        new_array
+    }
+
+    pub fn compare_equal_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, f32::eq)
+    }
+
+    pub fn compare_equal_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, f32::eq)
+    }
+
+    pub fn compare_not_equal_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, f32::ne)
+    }
+
+    pub fn compare_not_equal_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, f32::ne)
+    }
+
+    pub fn compare_greater_than_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, f32::gt)
+    }
+
+    pub fn compare_greater_than_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, f32::gt)
+    }
+
+    pub fn compare_greater_than_or_equal_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, f32::ge)
+    }
+
+    pub fn compare_greater_than_or_equal_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, f32::ge)
+    }
+
+    pub fn compare_less_than_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, f32::lt)
+    }
+
+    pub fn compare_less_than_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, f32::lt)
+    }
+
+    pub fn compare_less_than_or_equal_row_broadcast(&self, row: &Array<1>) -> Mask<2> {
+        self.compare_row_broadcast(row, f32::le)
+    }
+
+    pub fn compare_less_than_or_equal_column_broadcast(&self, column: &Array<1>) -> Mask<2> {
+        self.compare_column_broadcast(column, f32::le)
+    }
+}
+
 impl Array<2> {
     pub fn from_vec(data: &Vec<f32>, shape: [usize; 2]) -> Self {
-        assert!(shape[0] > 0);
-        assert!(shape[1] > 0);
+        unwrap_or_panic(check_data_length(data.len(), &shape));
 
         Self {
             data: data.clone(),
@@ -1122,45 +1969,308 @@ impl Array<2> {
     }
 
     pub fn get(&self, row: usize, column: usize) -> f32 {
-        if row >= self.shape[0] {
-            panic!(
-                "tried to get row {}, but the array has only {} row(s)",
-                row, self.shape[0]
-            );
+        unwrap_or_panic(self.check_index(row, column));
+
+        self.data[row * self.shape[1] + column]
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: f32) {
+        unwrap_or_panic(self.check_index(row, column));
+
+        self.data[row * self.shape[1] + column] = value;
+    }
+
+    /// Sort each row in ascending order, NaNs are ordered by `f32::total_cmp`
+    pub fn sort_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.sort_rows_in_place();
+
+        new_array
+    }
+
+    /// Sort each row in ascending order, NaNs are ordered by `f32::total_cmp`
+    pub fn sort_rows_in_place(&mut self) {
+        if self.shape[1] == 0 {
+            return;
         }
 
-        if column >= self.shape[1] {
-            panic!(
-                "tried to get column {}, but the array has only {} column(s)",
-                column, self.shape[1]
-            );
+        for row in self.data.chunks_mut(self.shape[1]) {
+            row.sort_by(f32::total_cmp);
         }
+    }
 
-        self.data[row * self.shape[1] + column]
+    /// Return the column indices that would sort each row in ascending order, the indices are stored in row-major order
+    pub fn argsort_rows(&self) -> Vec<u32> {
+        let mut indices = Vec::with_capacity(self.data.len());
+
+        if self.shape[1] == 0 {
+            return indices;
+        }
+
+        for row in self.data.chunks(self.shape[1]) {
+            indices.extend(argsort(row, false, self.shape[1]));
+        }
+
+        indices
     }
 
-    pub fn set(&mut self, row: usize, column: usize, value: f32) {
-        if row >= self.shape[0] {
-            panic!(
-                "tried to set row {}, but the array has only {} row(s)",
-                row, self.shape[0]
-            );
+    /// Return the `k` largest elements of each row in descending order together with their column indices.
+    /// The indices are stored in row-major order.
+    pub fn top_k_rows(&self, k: usize) -> (Array<2>, Vec<u32>) {
+        assert!(
+            k <= self.shape[1],
+            "tried to get the top {} elements of each row, but the array has only {} column(s)",
+            k,
+            self.shape[1]
+        );
+
+        let mut values = Vec::with_capacity(self.shape[0] * k);
+        let mut indices = Vec::with_capacity(self.shape[0] * k);
+
+        if k > 0 {
+            for row in self.data.chunks(self.shape[1]) {
+                let row_indices = argsort(row, true, k);
+                values.extend(row_indices.iter().map(|i| row[*i as usize]));
+                indices.extend(row_indices);
+            }
+        }
+
+        let values = Array {
+            data: values,
+            shape: [self.shape[0], k],
+        };
+
+        (values, indices)
+    }
+
+    /// cumulative sum along each row
+    pub fn cumsum_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumsum_rows_in_place();
+
+        new_array
+    }
+
+    pub fn cumsum_rows_in_place(&mut self) {
+        for row in self.data.chunks_mut(self.shape[1].max(1)) {
+            scan_row(row, None, 0.0, f32::add);
         }
+    }
 
-        if column >= self.shape[1] {
-            panic!(
-                "tried to set column {}, but the array has only {} column(s)",
-                column, self.shape[1]
-            );
+    /// cumulative sum along each row where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumsum_rows_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        for (row, masks) in self
+            .data
+            .chunks_mut(self.shape[1].max(1))
+            .zip(mask.get_masks().chunks(self.shape[1].max(1)))
+        {
+            scan_row(row, Some(masks), 0.0, f32::add);
         }
+    }
 
-        self.data[row * self.shape[1] + column] = value;
+    /// cumulative sum along each column
+    pub fn cumsum_columns(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumsum_columns_in_place();
+
+        new_array
+    }
+
+    pub fn cumsum_columns_in_place(&mut self) {
+        scan_columns(&mut self.data, self.shape[1].max(1), None, 0.0, f32::add);
+    }
+
+    /// cumulative sum along each column where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumsum_columns_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+        scan_columns(
+            &mut self.data,
+            self.shape[1].max(1),
+            Some(mask.get_masks()),
+            0.0,
+            f32::add,
+        );
+    }
+
+    /// cumulative product along each row
+    pub fn cumprod_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumprod_rows_in_place();
+
+        new_array
+    }
+
+    pub fn cumprod_rows_in_place(&mut self) {
+        for row in self.data.chunks_mut(self.shape[1].max(1)) {
+            scan_row(row, None, 1.0, f32::mul);
+        }
+    }
+
+    /// cumulative product along each row where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumprod_rows_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        for (row, masks) in self
+            .data
+            .chunks_mut(self.shape[1].max(1))
+            .zip(mask.get_masks().chunks(self.shape[1].max(1)))
+        {
+            scan_row(row, Some(masks), 1.0, f32::mul);
+        }
+    }
+
+    /// cumulative product along each column
+    pub fn cumprod_columns(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cumprod_columns_in_place();
+
+        new_array
+    }
+
+    pub fn cumprod_columns_in_place(&mut self) {
+        scan_columns(&mut self.data, self.shape[1].max(1), None, 1.0, f32::mul);
+    }
+
+    /// cumulative product along each column where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cumprod_columns_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+        scan_columns(
+            &mut self.data,
+            self.shape[1].max(1),
+            Some(mask.get_masks()),
+            1.0,
+            f32::mul,
+        );
+    }
+
+    /// cumulative maximum along each row
+    pub fn cummax_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummax_rows_in_place();
+
+        new_array
+    }
+
+    pub fn cummax_rows_in_place(&mut self) {
+        for row in self.data.chunks_mut(self.shape[1].max(1)) {
+            scan_row(row, None, f32::NEG_INFINITY, f32::max);
+        }
+    }
+
+    /// cumulative maximum along each row where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummax_rows_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        for (row, masks) in self
+            .data
+            .chunks_mut(self.shape[1].max(1))
+            .zip(mask.get_masks().chunks(self.shape[1].max(1)))
+        {
+            scan_row(row, Some(masks), f32::NEG_INFINITY, f32::max);
+        }
+    }
+
+    /// cumulative maximum along each column
+    pub fn cummax_columns(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummax_columns_in_place();
+
+        new_array
+    }
+
+    pub fn cummax_columns_in_place(&mut self) {
+        scan_columns(
+            &mut self.data,
+            self.shape[1].max(1),
+            None,
+            f32::NEG_INFINITY,
+            f32::max,
+        );
+    }
+
+    /// cumulative maximum along each column where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummax_columns_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+        scan_columns(
+            &mut self.data,
+            self.shape[1].max(1),
+            Some(mask.get_masks()),
+            f32::NEG_INFINITY,
+            f32::max,
+        );
+    }
+
+    /// cumulative minimum along each row
+    pub fn cummin_rows(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummin_rows_in_place();
+
+        new_array
+    }
+
+    pub fn cummin_rows_in_place(&mut self) {
+        for row in self.data.chunks_mut(self.shape[1].max(1)) {
+            scan_row(row, None, f32::INFINITY, f32::min);
+        }
+    }
+
+    /// cumulative minimum along each row where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummin_rows_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+
+        for (row, masks) in self
+            .data
+            .chunks_mut(self.shape[1].max(1))
+            .zip(mask.get_masks().chunks(self.shape[1].max(1)))
+        {
+            scan_row(row, Some(masks), f32::INFINITY, f32::min);
+        }
+    }
+
+    /// cumulative minimum along each column
+    pub fn cummin_columns(&self) -> Self {
+        let mut new_array = self.clone();
+        new_array.cummin_columns_in_place();
+
+        new_array
+    }
+
+    pub fn cummin_columns_in_place(&mut self) {
+        scan_columns(
+            &mut self.data,
+            self.shape[1].max(1),
+            None,
+            f32::INFINITY,
+            f32::min,
+        );
+    }
+
+    /// cumulative minimum along each column where `mask` is 1, the other elements are skipped and left unchanged
+    pub fn cummin_columns_in_place_masked(&mut self, mask: &Mask<2>) {
+        assert_same_shape_mask(self, mask);
+        scan_columns(
+            &mut self.data,
+            self.shape[1].max(1),
+            Some(mask.get_masks()),
+            f32::INFINITY,
+            f32::min,
+        );
     }
 
     pub fn vector_multiplication(&self, other: &Array<1>) -> Array<1> {
+        let mut result = Array::zeros(&[self.shape[0]]);
+        self.vector_multiplication_out_of_place(other, &mut result);
+
+        result
+    }
+
+    pub fn vector_multiplication_out_of_place(&self, other: &Array<1>, output: &mut Array<1>) {
+        unwrap_or_panic(self.check_vector_multiplication_out_of_place(other, output));
+
         let rows = self.shape[0];
         let columns = self.shape[1];
-        let mut result = Vec::with_capacity(columns);
 
         for i in 0..rows {
             let mut sum = 0.0;
@@ -1169,40 +2279,98 @@ impl Array<2> {
                 sum += self.data[i * columns + j] * other.data[j];
             }
 
-            result.push(sum);
+            output.data[i] = sum;
         }
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Array::zeros(&[self.shape[1], self.shape[0]]);
+        self.transpose_out_of_place(&mut transposed);
+
+        transposed
+    }
+
+    pub fn transpose_out_of_place(&self, output: &mut Self) {
+        unwrap_or_panic(self.check_transpose_out_of_place(output));
+
+        let rows = self.shape[0];
+        let columns = self.shape[1];
 
-        Array::<1> {
-            data: result,
-            shape: [rows],
+        for row in 0..rows {
+            for column in 0..columns {
+                output.data[column * rows + row] = self.data[row * columns + column];
+            }
         }
     }
 
     pub fn matrix_multiplication(&self, matrix_b: &Self) -> Self {
+        let mut result = Array::zeros(&[self.shape[0], matrix_b.shape[1]]);
+        self.matrix_multiplication_out_of_place(matrix_b, &mut result);
+
+        result
+    }
+
+    pub fn matrix_multiplication_out_of_place(&self, matrix_b: &Self, output: &mut Self) {
+        unwrap_or_panic(self.check_matrix_multiplication_out_of_place(matrix_b, output));
+
         let a_rows = self.shape[0];
         let a_columns = self.shape[1];
         let b_columns = matrix_b.shape[1];
-        let mut result = vec![0.0; a_rows * b_columns];
 
         for a_row in 0..a_rows {
             for b_column in 0..b_columns {
+                let mut sum = 0.0;
+
                 for inner_loop_index in 0..a_columns {
-                    result[a_row * b_columns + b_column] += self.data
-                        [a_row * a_columns + inner_loop_index]
+                    sum += self.data[a_row * a_columns + inner_loop_index]
                         * matrix_b.data[inner_loop_index * b_columns + b_column];
                 }
+
+                output.data[a_row * b_columns + b_column] = sum;
             }
         }
+    }
 
-        Self {
-            data: result,
-            shape: [a_rows, b_columns],
+    /// the same as `matrix_multiplication_out_of_place`, which doesn't need a temporary array here
+    pub fn matrix_multiplication_out_of_place_with_workspace(
+        &self,
+        matrix_b: &Self,
+        output: &mut Self,
+        _workspace: &mut Workspace,
+    ) {
+        self.matrix_multiplication_out_of_place(matrix_b, output);
+    }
+
+    /// the matrix multiplication with `transposed_b`, the transpose of the second matrix
+    pub fn matrix_multiplication_transposed_out_of_place(
+        &self,
+        transposed_b: &Self,
+        output: &mut Self,
+    ) {
+        unwrap_or_panic(
+            self.check_matrix_multiplication_transposed_out_of_place(transposed_b, output),
+        );
+
+        let a_rows = self.shape[0];
+        let a_columns = self.shape[1];
+        let b_columns = transposed_b.shape[0];
+
+        for a_row in 0..a_rows {
+            for b_column in 0..b_columns {
+                let mut sum = 0.0;
+
+                for inner_loop_index in 0..a_columns {
+                    sum += self.data[a_row * a_columns + inner_loop_index]
+                        * transposed_b.data[b_column * a_columns + inner_loop_index];
+                }
+
+                output.data[a_row * b_columns + b_column] = sum;
+            }
         }
     }
 
     pub fn sum_to_row_in_place_masked(&self, mask: &Mask<2>, output: &mut Array<1>) {
-        assert_eq!(&self.shape, mask.get_shape());
-        assert_eq!(output.shape[0], self.shape[1]);
+        unwrap_or_panic(self.check_sum_to_row_masked(mask, output));
 
         output.set_all(0.0);
 
@@ -1214,8 +2382,7 @@ impl Array<2> {
     }
 
     pub fn sum_to_column_in_place_masked(&self, mask: &Mask<2>, output: &mut Array<1>) {
-        assert_eq!(&self.shape, mask.get_shape());
-        assert_eq!(output.shape[0], self.shape[0]);
+        unwrap_or_panic(self.check_sum_to_column_masked(mask, output));
 
         for row in 0..self.shape[0] {
             let data_range = row * self.shape[1]..(row + 1) * self.shape[1];
@@ -0,0 +1,1302 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::{
+    error::{check_data_length, check_index, check_non_empty_shape, check_same_length},
+    Array, Error, Mask, Workspace,
+};
+
+use super::{
+    check_same_shape2, check_same_shape3, check_same_shape_mask, check_same_shape_with_mask2,
+    check_same_shape_with_mask3,
+};
+
+/// check that there are `expected_length` indices and that all indices, where the mask is set, are smaller than `len`
+fn check_indices(
+    indices: &[u32],
+    mask: Option<&Mask<1>>,
+    expected_length: usize,
+    len: usize,
+) -> Result<(), Error> {
+    check_same_length(expected_length, indices.len())?;
+
+    if let Some(mask) = mask {
+        check_same_length(indices.len(), mask.get_shape()[0])?;
+    }
+
+    for (i, index) in indices.iter().enumerate() {
+        if mask.is_none_or(|m| m.get(i)) {
+            check_index(0, *index as usize, len)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl<const D: usize> Array<D> {
+    /// fallible version of `set_masked`
+    pub fn try_set_masked(&mut self, value: f32, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.set_masked(value, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `set_masked2`
+    pub fn try_set_masked2(&mut self, v1: f32, v2: f32, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.set_masked2(v1, v2, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `copy`
+    pub fn try_copy(&mut self, other: &Array<D>) -> Result<(), Error> {
+        check_same_shape2(self, other)?;
+
+        self.copy(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `copy_masked`
+    pub fn try_copy_masked(&mut self, other: &Array<D>, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.copy_masked(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `copy_masked2`
+    pub fn try_copy_masked2(
+        &mut self,
+        other1: &Array<D>,
+        other2: &Array<D>,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask3(self, other1, other2, mask)?;
+
+        self.copy_masked2(other1, other2, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `select`
+    pub fn try_select(mask: &Mask<D>, if_true: &Self, if_false: &Self) -> Result<Self, Error> {
+        check_same_shape_with_mask2(if_true, if_false, mask)?;
+
+        Ok(Self::select(mask, if_true, if_false))
+    }
+
+    /// fallible version of `select_out_of_place`
+    pub fn try_select_out_of_place(
+        mask: &Mask<D>,
+        if_true: &Self,
+        if_false: &Self,
+        output: &mut Self,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask3(if_true, if_false, output, mask)?;
+
+        Self::select_out_of_place(mask, if_true, if_false, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `select_scalar`
+    pub fn try_select_scalar(mask: &Mask<D>, if_true: &Self, if_false: f32) -> Result<Self, Error> {
+        check_same_shape_mask(if_true, mask)?;
+
+        Ok(Self::select_scalar(mask, if_true, if_false))
+    }
+
+    /// fallible version of `select_scalar_out_of_place`
+    pub fn try_select_scalar_out_of_place(
+        mask: &Mask<D>,
+        if_true: &Self,
+        if_false: f32,
+        output: &mut Self,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask2(if_true, output, mask)?;
+
+        Self::select_scalar_out_of_place(mask, if_true, if_false, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `select_scalars_out_of_place`
+    pub fn try_select_scalars_out_of_place(
+        mask: &Mask<D>,
+        if_true: f32,
+        if_false: f32,
+        output: &mut Self,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(output, mask)?;
+
+        Self::select_scalars_out_of_place(mask, if_true, if_false, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `compress`
+    pub fn try_compress(&self, mask: &Mask<D>) -> Result<Array<1>, Error> {
+        check_same_shape_mask(self, mask)?;
+
+        Ok(self.compress(mask))
+    }
+
+    /// fallible version of `expand_into`
+    pub fn try_expand_into(&mut self, values: &Array<1>, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+        check_same_length(mask.number_of_true_elements(), values.shape[0])?;
+
+        self.expand_into(values, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `add`
+    pub fn try_add(&self, other: &Self) -> Result<Self, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.add(other))
+    }
+
+    /// fallible version of `add_in_place`
+    pub fn try_add_in_place(&mut self, other: &Self) -> Result<(), Error> {
+        check_same_shape2(self, other)?;
+
+        self.add_in_place(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `add_in_place_masked`
+    pub fn try_add_in_place_masked(&mut self, other: &Self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.add_in_place_masked(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `add_out_of_place`
+    pub fn try_add_out_of_place(&self, other: &Self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape3(self, other, output)?;
+
+        self.add_out_of_place(other, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `add_scalar_in_place_masked`
+    pub fn try_add_scalar_in_place_masked(
+        &mut self,
+        scalar: f32,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.add_scalar_in_place_masked(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `add_scalar_out_of_place`
+    pub fn try_add_scalar_out_of_place(&self, scalar: f32, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.add_scalar_out_of_place(scalar, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `sub`
+    pub fn try_sub(&self, other: &Self) -> Result<Self, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.sub(other))
+    }
+
+    /// fallible version of `sub_in_place`
+    pub fn try_sub_in_place(&mut self, other: &Self) -> Result<(), Error> {
+        check_same_shape2(self, other)?;
+
+        self.sub_in_place(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `sub_in_place_masked`
+    pub fn try_sub_in_place_masked(&mut self, other: &Self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.sub_in_place_masked(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `sub_out_of_place`
+    pub fn try_sub_out_of_place(&self, other: &Self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape3(self, other, output)?;
+
+        self.sub_out_of_place(other, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `sub_scalar_in_place_masked`
+    pub fn try_sub_scalar_in_place_masked(
+        &mut self,
+        scalar: f32,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.sub_scalar_in_place_masked(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `sub_scalar_out_of_place`
+    pub fn try_sub_scalar_out_of_place(&self, scalar: f32, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.sub_scalar_out_of_place(scalar, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `mul`
+    pub fn try_mul(&self, other: &Self) -> Result<Self, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.mul(other))
+    }
+
+    /// fallible version of `mul_in_place`
+    pub fn try_mul_in_place(&mut self, other: &Self) -> Result<(), Error> {
+        check_same_shape2(self, other)?;
+
+        self.mul_in_place(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `mul_in_place_masked`
+    pub fn try_mul_in_place_masked(&mut self, other: &Self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.mul_in_place_masked(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `mul_out_of_place`
+    pub fn try_mul_out_of_place(&self, other: &Self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape3(self, other, output)?;
+
+        self.mul_out_of_place(other, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `mul_scalar_in_place_masked`
+    pub fn try_mul_scalar_in_place_masked(
+        &mut self,
+        scalar: f32,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.mul_scalar_in_place_masked(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `mul_scalar_out_of_place`
+    pub fn try_mul_scalar_out_of_place(&self, scalar: f32, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.mul_scalar_out_of_place(scalar, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `div`
+    pub fn try_div(&self, other: &Self) -> Result<Self, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.div(other))
+    }
+
+    /// fallible version of `div_in_place`
+    pub fn try_div_in_place(&mut self, other: &Self) -> Result<(), Error> {
+        check_same_shape2(self, other)?;
+
+        self.div_in_place(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `div_in_place_masked`
+    pub fn try_div_in_place_masked(&mut self, other: &Self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.div_in_place_masked(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `div_out_of_place`
+    pub fn try_div_out_of_place(&self, other: &Self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape3(self, other, output)?;
+
+        self.div_out_of_place(other, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `div_scalar_in_place_masked`
+    pub fn try_div_scalar_in_place_masked(
+        &mut self,
+        scalar: f32,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.div_scalar_in_place_masked(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `div_scalar_out_of_place`
+    pub fn try_div_scalar_out_of_place(&self, scalar: f32, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.div_scalar_out_of_place(scalar, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `max`
+    pub fn try_max(&self, other: &Self) -> Result<Self, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.max(other))
+    }
+
+    /// fallible version of `max_in_place`
+    pub fn try_max_in_place(&mut self, other: &Self) -> Result<(), Error> {
+        check_same_shape2(self, other)?;
+
+        self.max_in_place(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `max_in_place_masked`
+    pub fn try_max_in_place_masked(&mut self, other: &Self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.max_in_place_masked(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `max_out_of_place`
+    pub fn try_max_out_of_place(&self, other: &Self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape3(self, other, output)?;
+
+        self.max_out_of_place(other, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `max_scalar_in_place_masked`
+    pub fn try_max_scalar_in_place_masked(
+        &mut self,
+        scalar: f32,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.max_scalar_in_place_masked(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `max_scalar_out_of_place`
+    pub fn try_max_scalar_out_of_place(&self, scalar: f32, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.max_scalar_out_of_place(scalar, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `min`
+    pub fn try_min(&self, other: &Self) -> Result<Self, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.min(other))
+    }
+
+    /// fallible version of `min_in_place`
+    pub fn try_min_in_place(&mut self, other: &Self) -> Result<(), Error> {
+        check_same_shape2(self, other)?;
+
+        self.min_in_place(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `min_in_place_masked`
+    pub fn try_min_in_place_masked(&mut self, other: &Self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.min_in_place_masked(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `min_out_of_place`
+    pub fn try_min_out_of_place(&self, other: &Self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape3(self, other, output)?;
+
+        self.min_out_of_place(other, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `min_scalar_in_place_masked`
+    pub fn try_min_scalar_in_place_masked(
+        &mut self,
+        scalar: f32,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.min_scalar_in_place_masked(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `min_scalar_out_of_place`
+    pub fn try_min_scalar_out_of_place(&self, scalar: f32, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.min_scalar_out_of_place(scalar, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `fmadd`
+    pub fn try_fmadd(&self, a: &Self, b: &Self) -> Result<Self, Error> {
+        check_same_shape3(self, a, b)?;
+
+        Ok(self.fmadd(a, b))
+    }
+
+    /// fallible version of `fmadd_in_place`
+    pub fn try_fmadd_in_place(&mut self, a: &Self, b: &Self) -> Result<(), Error> {
+        check_same_shape3(self, a, b)?;
+
+        self.fmadd_in_place(a, b);
+
+        Ok(())
+    }
+
+    /// fallible version of `fmadd_in_place_masked`
+    pub fn try_fmadd_in_place_masked(
+        &mut self,
+        a: &Self,
+        b: &Self,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask3(self, a, b, mask)?;
+
+        self.fmadd_in_place_masked(a, b, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `fmadd_out_of_place`
+    pub fn try_fmadd_out_of_place(
+        &self,
+        a: &Self,
+        b: &Self,
+        output: &mut Self,
+    ) -> Result<(), Error> {
+        check_same_shape3(self, a, b)?;
+        check_same_shape2(self, output)?;
+
+        self.fmadd_out_of_place(a, b, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `fmadd_scalar`
+    pub fn try_fmadd_scalar(&self, a: &Self, scalar: f32) -> Result<Self, Error> {
+        check_same_shape2(self, a)?;
+
+        Ok(self.fmadd_scalar(a, scalar))
+    }
+
+    /// fallible version of `fmadd_scalar_in_place`
+    pub fn try_fmadd_scalar_in_place(&mut self, a: &Self, scalar: f32) -> Result<(), Error> {
+        check_same_shape2(self, a)?;
+
+        self.fmadd_scalar_in_place(a, scalar);
+
+        Ok(())
+    }
+
+    /// fallible version of `fmadd_scalar_in_place_masked`
+    pub fn try_fmadd_scalar_in_place_masked(
+        &mut self,
+        a: &Self,
+        scalar: f32,
+        mask: &Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, a, mask)?;
+
+        self.fmadd_scalar_in_place_masked(a, scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `fmadd_scalar_out_of_place`
+    pub fn try_fmadd_scalar_out_of_place(
+        &self,
+        a: &Self,
+        scalar: f32,
+        output: &mut Self,
+    ) -> Result<(), Error> {
+        check_same_shape3(self, a, output)?;
+
+        self.fmadd_scalar_out_of_place(a, scalar, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `sqrt_in_place_masked`
+    pub fn try_sqrt_in_place_masked(&mut self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.sqrt_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `sqrt_out_of_place`
+    pub fn try_sqrt_out_of_place(&self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.sqrt_out_of_place(output);
+
+        Ok(())
+    }
+
+    /// fallible version of `square_in_place_masked`
+    pub fn try_square_in_place_masked(&mut self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.square_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `square_out_of_place`
+    pub fn try_square_out_of_place(&self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.square_out_of_place(output);
+
+        Ok(())
+    }
+
+    /// fallible version of `abs_in_place_masked`
+    pub fn try_abs_in_place_masked(&mut self, mask: &Mask<D>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.abs_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `abs_out_of_place`
+    pub fn try_abs_out_of_place(&self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.abs_out_of_place(output);
+
+        Ok(())
+    }
+
+    /// fallible version of `exp_out_of_place`
+    pub fn try_exp_out_of_place(&self, output: &mut Self) -> Result<(), Error> {
+        check_same_shape2(self, output)?;
+
+        self.exp_out_of_place(output);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_equal`
+    pub fn try_compare_equal(&self, other: &Self) -> Result<Mask<D>, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.compare_equal(other))
+    }
+
+    /// fallible version of `compare_equal_in_place`
+    pub fn try_compare_equal_in_place(
+        &self,
+        other: &Self,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.compare_equal_in_place(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_scalar_equal_in_place`
+    pub fn try_compare_scalar_equal_in_place(
+        &self,
+        scalar: f32,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.compare_scalar_equal_in_place(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_not_equal`
+    pub fn try_compare_not_equal(&self, other: &Self) -> Result<Mask<D>, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.compare_not_equal(other))
+    }
+
+    /// fallible version of `compare_not_equal_in_place`
+    pub fn try_compare_not_equal_in_place(
+        &self,
+        other: &Self,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.compare_not_equal_in_place(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_scalar_not_equal_in_place`
+    pub fn try_compare_scalar_not_equal_in_place(
+        &self,
+        scalar: f32,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.compare_scalar_not_equal_in_place(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_greater_than`
+    pub fn try_compare_greater_than(&self, other: &Self) -> Result<Mask<D>, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.compare_greater_than(other))
+    }
+
+    /// fallible version of `compare_greater_than_in_place`
+    pub fn try_compare_greater_than_in_place(
+        &self,
+        other: &Self,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.compare_greater_than_in_place(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_scalar_greater_than_in_place`
+    pub fn try_compare_scalar_greater_than_in_place(
+        &self,
+        scalar: f32,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.compare_scalar_greater_than_in_place(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_greater_than_or_equal`
+    pub fn try_compare_greater_than_or_equal(&self, other: &Self) -> Result<Mask<D>, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.compare_greater_than_or_equal(other))
+    }
+
+    /// fallible version of `compare_greater_than_or_equal_in_place`
+    pub fn try_compare_greater_than_or_equal_in_place(
+        &self,
+        other: &Self,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.compare_greater_than_or_equal_in_place(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_scalar_greater_than_or_equal_in_place`
+    pub fn try_compare_scalar_greater_than_or_equal_in_place(
+        &self,
+        scalar: f32,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.compare_scalar_greater_than_or_equal_in_place(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_less_than`
+    pub fn try_compare_less_than(&self, other: &Self) -> Result<Mask<D>, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.compare_less_than(other))
+    }
+
+    /// fallible version of `compare_less_than_in_place`
+    pub fn try_compare_less_than_in_place(
+        &self,
+        other: &Self,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.compare_less_than_in_place(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_scalar_less_than_in_place`
+    pub fn try_compare_scalar_less_than_in_place(
+        &self,
+        scalar: f32,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.compare_scalar_less_than_in_place(scalar, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_less_than_or_equal`
+    pub fn try_compare_less_than_or_equal(&self, other: &Self) -> Result<Mask<D>, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.compare_less_than_or_equal(other))
+    }
+
+    /// fallible version of `compare_less_than_or_equal_in_place`
+    pub fn try_compare_less_than_or_equal_in_place(
+        &self,
+        other: &Self,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_with_mask2(self, other, mask)?;
+
+        self.compare_less_than_or_equal_in_place(other, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `compare_scalar_less_than_or_equal_in_place`
+    pub fn try_compare_scalar_less_than_or_equal_in_place(
+        &self,
+        scalar: f32,
+        mask: &mut Mask<D>,
+    ) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.compare_scalar_less_than_or_equal_in_place(scalar, mask);
+
+        Ok(())
+    }
+}
+
+impl Array<1> {
+    /// fallible version of `get`
+    pub fn try_get(&self, index: usize) -> Result<f32, Error> {
+        self.check_index(index)?;
+
+        Ok(self.get(index))
+    }
+
+    /// fallible version of `set`
+    pub fn try_set(&mut self, index: usize, value: f32) -> Result<(), Error> {
+        self.check_index(index)?;
+
+        self.set(index, value);
+
+        Ok(())
+    }
+
+    /// fallible version of `dot_product`
+    pub fn try_dot_product(&self, other: &Self) -> Result<f32, Error> {
+        check_same_shape2(self, other)?;
+
+        Ok(self.dot_product(other))
+    }
+
+    /// fallible version of `gather`
+    pub fn try_gather(&self, indices: &[u32]) -> Result<Array<1>, Error> {
+        check_indices(indices, None, indices.len(), self.shape[0])?;
+
+        Ok(self.gather(indices))
+    }
+
+    /// fallible version of `gather_in_place`
+    pub fn try_gather_in_place(&self, indices: &[u32], output: &mut Array<1>) -> Result<(), Error> {
+        check_indices(indices, None, output.shape[0], self.shape[0])?;
+
+        self.gather_in_place(indices, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `gather_in_place_masked`
+    pub fn try_gather_in_place_masked(
+        &self,
+        indices: &[u32],
+        mask: &Mask<1>,
+        output: &mut Array<1>,
+    ) -> Result<(), Error> {
+        check_indices(indices, Some(mask), output.shape[0], self.shape[0])?;
+
+        self.gather_in_place_masked(indices, mask, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `scatter_in_place`
+    pub fn try_scatter_in_place(
+        &mut self,
+        indices: &[u32],
+        values: &Array<1>,
+    ) -> Result<(), Error> {
+        check_indices(indices, None, values.shape[0], self.shape[0])?;
+
+        self.scatter_in_place(indices, values);
+
+        Ok(())
+    }
+
+    /// fallible version of `scatter_in_place_masked`
+    pub fn try_scatter_in_place_masked(
+        &mut self,
+        indices: &[u32],
+        values: &Array<1>,
+        mask: &Mask<1>,
+    ) -> Result<(), Error> {
+        check_indices(indices, Some(mask), values.shape[0], self.shape[0])?;
+
+        self.scatter_in_place_masked(indices, values, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `scatter_add_in_place`
+    pub fn try_scatter_add_in_place(
+        &mut self,
+        indices: &[u32],
+        values: &Array<1>,
+    ) -> Result<(), Error> {
+        check_indices(indices, None, values.shape[0], self.shape[0])?;
+
+        self.scatter_add_in_place(indices, values);
+
+        Ok(())
+    }
+
+    /// fallible version of `scatter_add_in_place_masked`
+    pub fn try_scatter_add_in_place_masked(
+        &mut self,
+        indices: &[u32],
+        values: &Array<1>,
+        mask: &Mask<1>,
+    ) -> Result<(), Error> {
+        check_indices(indices, Some(mask), values.shape[0], self.shape[0])?;
+
+        self.scatter_add_in_place_masked(indices, values, mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cumsum_in_place_masked`
+    pub fn try_cumsum_in_place_masked(&mut self, mask: &Mask<1>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cumsum_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cumprod_in_place_masked`
+    pub fn try_cumprod_in_place_masked(&mut self, mask: &Mask<1>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cumprod_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cummax_in_place_masked`
+    pub fn try_cummax_in_place_masked(&mut self, mask: &Mask<1>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cummax_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cummin_in_place_masked`
+    pub fn try_cummin_in_place_masked(&mut self, mask: &Mask<1>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cummin_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `tile_in_place`
+    pub fn try_tile_in_place(&self, k: usize, output: &mut Array<1>) -> Result<(), Error> {
+        self.check_tile(k, output)?;
+
+        self.tile_in_place(k, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `repeat_in_place`
+    pub fn try_repeat_in_place(&self, k: usize, output: &mut Array<1>) -> Result<(), Error> {
+        self.check_repeat(k, output)?;
+
+        self.repeat_in_place(k, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `repeat_as_row_in_place`
+    pub fn try_repeat_as_row_in_place(&self, k: usize, output: &mut Array<2>) -> Result<(), Error> {
+        self.check_repeat_as_row(k, output)?;
+
+        self.repeat_as_row_in_place(k, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `repeat_as_column_in_place`
+    pub fn try_repeat_as_column_in_place(
+        &self,
+        k: usize,
+        output: &mut Array<2>,
+    ) -> Result<(), Error> {
+        self.check_repeat_as_column(k, output)?;
+
+        self.repeat_as_column_in_place(k, output);
+
+        Ok(())
+    }
+}
+
+impl Array<2> {
+    /// fallible version of `from_vec`
+    pub fn try_from_vec(data: &Vec<f32>, shape: [usize; 2]) -> Result<Self, Error> {
+        check_data_length(data.len(), &shape)?;
+
+        Ok(Self::from_vec(data, shape))
+    }
+
+    /// fallible version of `get`
+    pub fn try_get(&self, row: usize, column: usize) -> Result<f32, Error> {
+        self.check_index(row, column)?;
+
+        Ok(self.get(row, column))
+    }
+
+    /// fallible version of `set`
+    pub fn try_set(&mut self, row: usize, column: usize, value: f32) -> Result<(), Error> {
+        self.check_index(row, column)?;
+
+        self.set(row, column, value);
+
+        Ok(())
+    }
+
+    /// fallible version of `sum`
+    pub fn try_sum(&self) -> Result<f32, Error> {
+        check_non_empty_shape(&self.shape)?;
+
+        Ok(self.sum())
+    }
+
+    /// fallible version of `product`
+    pub fn try_product(&self) -> Result<f32, Error> {
+        check_non_empty_shape(&self.shape)?;
+
+        Ok(self.product())
+    }
+
+    /// fallible version of `max_reduce`
+    pub fn try_max_reduce(&self) -> Result<f32, Error> {
+        check_non_empty_shape(&self.shape)?;
+
+        Ok(self.max_reduce())
+    }
+
+    /// fallible version of `min_reduce`
+    pub fn try_min_reduce(&self) -> Result<f32, Error> {
+        check_non_empty_shape(&self.shape)?;
+
+        Ok(self.min_reduce())
+    }
+
+    /// fallible version of `vector_multiplication`
+    pub fn try_vector_multiplication(&self, other: &Array<1>) -> Result<Array<1>, Error> {
+        self.check_vector_multiplication(other)?;
+
+        Ok(self.vector_multiplication(other))
+    }
+
+    /// fallible version of `vector_multiplication_out_of_place`
+    pub fn try_vector_multiplication_out_of_place(
+        &self,
+        other: &Array<1>,
+        output: &mut Array<1>,
+    ) -> Result<(), Error> {
+        self.check_vector_multiplication_out_of_place(other, output)?;
+
+        self.vector_multiplication_out_of_place(other, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `matrix_multiplication`
+    pub fn try_matrix_multiplication(&self, matrix_b: &Self) -> Result<Self, Error> {
+        self.check_matrix_multiplication(matrix_b)?;
+
+        Ok(self.matrix_multiplication(matrix_b))
+    }
+
+    /// fallible version of `matrix_multiplication_out_of_place`
+    pub fn try_matrix_multiplication_out_of_place(
+        &self,
+        matrix_b: &Self,
+        output: &mut Self,
+    ) -> Result<(), Error> {
+        self.check_matrix_multiplication_out_of_place(matrix_b, output)?;
+
+        self.matrix_multiplication_out_of_place(matrix_b, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `matrix_multiplication_out_of_place_with_workspace`
+    pub fn try_matrix_multiplication_out_of_place_with_workspace(
+        &self,
+        matrix_b: &Self,
+        output: &mut Self,
+        workspace: &mut Workspace,
+    ) -> Result<(), Error> {
+        self.check_matrix_multiplication_out_of_place(matrix_b, output)?;
+
+        self.matrix_multiplication_out_of_place_with_workspace(matrix_b, output, workspace);
+
+        Ok(())
+    }
+
+    /// fallible version of `matrix_multiplication_transposed_out_of_place`
+    pub fn try_matrix_multiplication_transposed_out_of_place(
+        &self,
+        transposed_b: &Self,
+        output: &mut Self,
+    ) -> Result<(), Error> {
+        self.check_matrix_multiplication_transposed_out_of_place(transposed_b, output)?;
+
+        self.matrix_multiplication_transposed_out_of_place(transposed_b, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `transpose_out_of_place`
+    pub fn try_transpose_out_of_place(&self, output: &mut Self) -> Result<(), Error> {
+        self.check_transpose_out_of_place(output)?;
+
+        self.transpose_out_of_place(output);
+
+        Ok(())
+    }
+
+    /// fallible version of `cumsum_rows_in_place_masked`
+    pub fn try_cumsum_rows_in_place_masked(&mut self, mask: &Mask<2>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cumsum_rows_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cumsum_columns_in_place_masked`
+    pub fn try_cumsum_columns_in_place_masked(&mut self, mask: &Mask<2>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cumsum_columns_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cumprod_rows_in_place_masked`
+    pub fn try_cumprod_rows_in_place_masked(&mut self, mask: &Mask<2>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cumprod_rows_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cumprod_columns_in_place_masked`
+    pub fn try_cumprod_columns_in_place_masked(&mut self, mask: &Mask<2>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cumprod_columns_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cummax_rows_in_place_masked`
+    pub fn try_cummax_rows_in_place_masked(&mut self, mask: &Mask<2>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cummax_rows_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cummax_columns_in_place_masked`
+    pub fn try_cummax_columns_in_place_masked(&mut self, mask: &Mask<2>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cummax_columns_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cummin_rows_in_place_masked`
+    pub fn try_cummin_rows_in_place_masked(&mut self, mask: &Mask<2>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cummin_rows_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `cummin_columns_in_place_masked`
+    pub fn try_cummin_columns_in_place_masked(&mut self, mask: &Mask<2>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+
+        self.cummin_columns_in_place_masked(mask);
+
+        Ok(())
+    }
+
+    /// fallible version of `sum_to_row_in_place_masked`
+    pub fn try_sum_to_row_in_place_masked(
+        &self,
+        mask: &Mask<2>,
+        output: &mut Array<1>,
+    ) -> Result<(), Error> {
+        self.check_sum_to_row_masked(mask, output)?;
+
+        self.sum_to_row_in_place_masked(mask, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `sum_to_column_in_place_masked`
+    pub fn try_sum_to_column_in_place_masked(
+        &self,
+        mask: &Mask<2>,
+        output: &mut Array<1>,
+    ) -> Result<(), Error> {
+        self.check_sum_to_column_masked(mask, output)?;
+
+        self.sum_to_column_in_place_masked(mask, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `get_row`
+    pub fn try_get_row(&self, row: usize) -> Result<Array<1>, Error> {
+        check_index(0, row, self.shape[0])?;
+
+        Ok(self.get_row(row))
+    }
+
+    /// fallible version of `copy_row_from`
+    pub fn try_copy_row_from(&mut self, row: usize, values: &Array<1>) -> Result<(), Error> {
+        self.check_row(row, values)?;
+
+        self.copy_row_from(row, values);
+
+        Ok(())
+    }
+
+    /// fallible version of `column`
+    pub fn try_column(&self, column: usize) -> Result<Array<1>, Error> {
+        check_index(1, column, self.shape[1])?;
+
+        Ok(self.column(column))
+    }
+
+    /// fallible version of `set_column`
+    pub fn try_set_column(&mut self, column: usize, values: &Array<1>) -> Result<(), Error> {
+        self.check_column(column, values)?;
+
+        self.set_column(column, values);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::slice::{Chunks, ChunksMut};
+
+use crate::Array;
+
+/// move a row-major multi-dimensional index to the next element
+pub(crate) fn advance_index<const D: usize>(index: &mut [usize; D], shape: &[usize; D]) {
+    for axis in (0..D).rev() {
+        index[axis] += 1;
+
+        if index[axis] < shape[axis] || axis == 0 {
+            return;
+        }
+
+        index[axis] = 0;
+    }
+}
+
+/// An iterator over the elements of an array in row-major order, the padding of the rows is skipped
+pub struct ArrayIter<'a> {
+    rows: Chunks<'a, f32>,
+    row: std::slice::Iter<'a, f32>,
+    column_count: usize,
+}
+
+impl Iterator for ArrayIter<'_> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        loop {
+            if let Some(element) = self.row.next() {
+                return Some(*element);
+            }
+
+            self.row = self.rows.next()?[..self.column_count].iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.row.len() + self.rows.len() * self.column_count;
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ArrayIter<'_> {}
+
+/// A mutable iterator over the elements of an array in row-major order, the padding of the rows is skipped
+pub struct ArrayIterMut<'a> {
+    rows: ChunksMut<'a, f32>,
+    row: std::slice::IterMut<'a, f32>,
+    column_count: usize,
+}
+
+impl<'a> Iterator for ArrayIterMut<'a> {
+    type Item = &'a mut f32;
+
+    fn next(&mut self) -> Option<&'a mut f32> {
+        loop {
+            if let Some(element) = self.row.next() {
+                return Some(element);
+            }
+
+            self.row = self.rows.next()?[..self.column_count].iter_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.row.len() + self.rows.len() * self.column_count;
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ArrayIterMut<'_> {}
+
+/// An iterator over the elements of an array in row-major order together with their indices
+pub struct ArrayIndexedIter<'a, const D: usize> {
+    iter: ArrayIter<'a>,
+    index: [usize; D],
+    shape: [usize; D],
+}
+
+impl<const D: usize> Iterator for ArrayIndexedIter<'_, D> {
+    type Item = ([usize; D], f32);
+
+    fn next(&mut self) -> Option<([usize; D], f32)> {
+        let element = self.iter.next()?;
+        let index = self.index;
+        advance_index(&mut self.index, &self.shape);
+
+        Some((index, element))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for ArrayIndexedIter<'_, D> {}
+
+/// An iterator that consumes an array and returns its elements in row-major order
+pub struct ArrayIntoIter<const D: usize> {
+    array: Array<D>,
+    row: usize,
+    column: usize,
+}
+
+impl<const D: usize> Iterator for ArrayIntoIter<D> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let column_count = self.array.shape[D - 1];
+        let offset = self.row * self.array.row_stride() + self.column;
+        let element = *self.array.padded_elements().get(offset)?;
+
+        self.column += 1;
+
+        if self.column == column_count {
+            self.row += 1;
+            self.column = 0;
+        }
+
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining =
+            self.array.number_of_elements() - self.row * self.array.shape[D - 1] - self.column;
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for ArrayIntoIter<D> {}
+
+impl<const D: usize> Array<D> {
+    /// Iterate over the elements in row-major order
+    pub fn iter(&self) -> ArrayIter<'_> {
+        // an empty one-dimensional array has no rows
+        let row_stride = self.row_stride().max(1);
+
+        ArrayIter {
+            rows: self.padded_elements().chunks(row_stride),
+            row: [].iter(),
+            column_count: self.shape[D - 1],
+        }
+    }
+
+    /// Iterate mutably over the elements in row-major order
+    pub fn iter_mut(&mut self) -> ArrayIterMut<'_> {
+        let row_stride = self.row_stride().max(1);
+        let column_count = self.shape[D - 1];
+
+        ArrayIterMut {
+            rows: self.padded_elements_mut().chunks_mut(row_stride),
+            row: [].iter_mut(),
+            column_count,
+        }
+    }
+
+    /// Iterate over the indices and elements in row-major order
+    pub fn indexed_iter(&self) -> ArrayIndexedIter<'_, D> {
+        ArrayIndexedIter {
+            iter: self.iter(),
+            index: [0; D],
+            shape: self.shape,
+        }
+    }
+}
+
+impl<const D: usize> IntoIterator for Array<D> {
+    type Item = f32;
+    type IntoIter = ArrayIntoIter<D>;
+
+    fn into_iter(self) -> ArrayIntoIter<D> {
+        ArrayIntoIter {
+            array: self,
+            row: 0,
+            column: 0,
+        }
+    }
+}
+
+impl<'a, const D: usize> IntoIterator for &'a Array<D> {
+    type Item = f32;
+    type IntoIter = ArrayIter<'a>;
+
+    fn into_iter(self) -> ArrayIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a, const D: usize> IntoIterator for &'a mut Array<D> {
+    type Item = &'a mut f32;
+    type IntoIter = ArrayIterMut<'a>;
+
+    fn into_iter(self) -> ArrayIterMut<'a> {
+        self.iter_mut()
+    }
+}
+
+impl FromIterator<f32> for Array<1> {
+    fn from_iter<I: IntoIterator<Item = f32>>(iter: I) -> Self {
+        iter.into_iter().collect::<Vec<f32>>().into()
+    }
+}
+
+impl<'a> FromIterator<&'a f32> for Array<1> {
+    fn from_iter<I: IntoIterator<Item = &'a f32>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Extend<f32> for Array<1> {
+    /// The elements are appended, which copies the existing elements into a new buffer
+    fn extend<I: IntoIterator<Item = f32>>(&mut self, iter: I) {
+        let mut elements = self.to_elements();
+        elements.extend(iter);
+        *self = elements.into();
+    }
+}
+
+impl<'a> Extend<&'a f32> for Array<1> {
+    fn extend<I: IntoIterator<Item = &'a f32>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
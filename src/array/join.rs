@@ -0,0 +1,198 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::ops::Range;
+
+use crate::{
+    error::{
+        check_axis, check_chunk_size, check_joinable_shapes, check_non_empty_range, unwrap_or_panic,
+    },
+    Array, Error,
+};
+
+/// the number of padded elements of one index along `axis`, which has to be before the last axis
+fn block_length<const D: usize>(shape: &[usize; D], axis: usize, row_stride: usize) -> usize {
+    shape[axis + 1..D - 1].iter().product::<usize>() * row_stride
+}
+
+impl<const D: usize> Array<D> {
+    fn check_split_at(&self, axis: usize, index: usize) -> Result<(), Error> {
+        check_axis(axis, D)?;
+        check_non_empty_range(axis, &(0..index), self.shape[axis])?;
+        check_non_empty_range(axis, &(index..self.shape[axis]), self.shape[axis])
+    }
+
+    /// copy the elements in `range` of `axis`, whole rows are copied including their padding
+    fn slice_axis(&self, axis: usize, range: Range<usize>) -> Array<D> {
+        let mut shape = self.shape;
+        shape[axis] = range.len();
+        let mut output = Array::zeros(&shape);
+
+        // an empty axis has no elements to copy and the blocks would be empty
+        if output.number_of_elements() == 0 {
+            return output;
+        }
+
+        if axis == D - 1 {
+            for row in 0..output.number_of_elements() / shape[D - 1] {
+                output
+                    .row_elements_mut(row)
+                    .copy_from_slice(&self.row_elements(row)[range.clone()]);
+            }
+        } else {
+            let block_length = block_length(&shape, axis, self.row_stride());
+            let source = self.padded_elements();
+            let output_length = range.len() * block_length;
+
+            for (i, destination) in output
+                .padded_elements_mut()
+                .chunks_mut(output_length)
+                .enumerate()
+            {
+                let start = (i * self.shape[axis] + range.start) * block_length;
+                destination.copy_from_slice(&source[start..start + output_length]);
+            }
+        }
+
+        output
+    }
+
+    /// Join the arrays along `axis`, their shapes must match in all other axes.
+    /// Along all axes except the last one whole rows are copied including their padding.
+    #[track_caller]
+    pub fn concatenate(arrays: &[&Array<D>], axis: usize) -> Array<D> {
+        unwrap_or_panic(check_joinable_shapes(arrays.iter().map(|a| a.shape), axis));
+
+        let mut shape = arrays[0].shape;
+        shape[axis] = arrays.iter().map(|a| a.shape[axis]).sum();
+        let mut output = Array::zeros(&shape);
+
+        if output.number_of_elements() == 0 {
+            return output;
+        }
+
+        if axis == D - 1 {
+            for row in 0..output.number_of_elements() / shape[D - 1] {
+                let destination = output.row_elements_mut(row);
+                let mut offset = 0;
+
+                for array in arrays {
+                    let source = array.row_elements(row);
+                    destination[offset..offset + source.len()].copy_from_slice(source);
+                    offset += source.len();
+                }
+            }
+        } else {
+            let block_length = block_length(&shape, axis, output.row_stride());
+            let outer_count = shape[..axis].iter().product();
+            let destination = output.padded_elements_mut();
+            let mut offset = 0;
+
+            for i in 0..outer_count {
+                for array in arrays {
+                    let length = array.shape[axis] * block_length;
+                    destination[offset..offset + length]
+                        .copy_from_slice(&array.padded_elements()[i * length..(i + 1) * length]);
+                    offset += length;
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Split the array along `axis` into the indices before `index` and the remaining ones, both parts must not be empty
+    #[track_caller]
+    pub fn split_at(&self, axis: usize, index: usize) -> (Array<D>, Array<D>) {
+        unwrap_or_panic(self.check_split_at(axis, index));
+
+        (
+            self.slice_axis(axis, 0..index),
+            self.slice_axis(axis, index..self.shape[axis]),
+        )
+    }
+
+    /// Split the array along `axis` into parts of `size` indices, the last part can be smaller
+    #[track_caller]
+    pub fn chunks(&self, axis: usize, size: usize) -> Vec<Array<D>> {
+        unwrap_or_panic(check_axis(axis, D));
+        unwrap_or_panic(check_chunk_size(size));
+
+        let length = self.shape[axis];
+
+        (0..length)
+            .step_by(size)
+            .map(|start| self.slice_axis(axis, start..(start + size).min(length)))
+            .collect()
+    }
+
+    /// fallible version of `concatenate`
+    pub fn try_concatenate(arrays: &[&Array<D>], axis: usize) -> Result<Array<D>, Error> {
+        check_joinable_shapes(arrays.iter().map(|a| a.shape), axis)?;
+
+        Ok(Self::concatenate(arrays, axis))
+    }
+
+    /// fallible version of `split_at`
+    pub fn try_split_at(&self, axis: usize, index: usize) -> Result<(Array<D>, Array<D>), Error> {
+        self.check_split_at(axis, index)?;
+
+        Ok(self.split_at(axis, index))
+    }
+
+    /// fallible version of `chunks`
+    pub fn try_chunks(&self, axis: usize, size: usize) -> Result<Vec<Array<D>>, Error> {
+        check_axis(axis, D)?;
+        check_chunk_size(size)?;
+
+        Ok(self.chunks(axis, size))
+    }
+}
+
+impl Array<2> {
+    /// Stack arrays of the same length as the rows of a matrix, the registers are copied as a whole
+    #[track_caller]
+    pub fn stack(arrays: &[&Array<1>]) -> Array<2> {
+        unwrap_or_panic(check_joinable_shapes(
+            arrays.iter().map(|a| [1, a.shape[0]]),
+            0,
+        ));
+
+        let mut output = Array::zeros(&[arrays.len(), arrays[0].shape[0]]);
+        let row_stride = output.row_stride();
+
+        if row_stride == 0 {
+            return output;
+        }
+
+        for (destination, array) in output
+            .padded_elements_mut()
+            .chunks_mut(row_stride)
+            .zip(arrays)
+        {
+            destination.copy_from_slice(array.padded_elements());
+        }
+
+        output
+    }
+
+    /// fallible version of `stack`
+    pub fn try_stack(arrays: &[&Array<1>]) -> Result<Array<2>, Error> {
+        check_joinable_shapes(arrays.iter().map(|a| [1, a.shape[0]]), 0)?;
+
+        Ok(Self::stack(arrays))
+    }
+}
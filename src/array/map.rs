@@ -0,0 +1,165 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::simd::{f32x16, mask32x16, Select};
+
+use crate::Array;
+
+use super::{assert_same_shape2, assert_same_shape3};
+
+/// the lanes of the register `index` of a row that contain elements
+fn register_lanes(column_count: usize, index: usize) -> mask32x16 {
+    let lane_count = (column_count - index * 16).min(16);
+
+    mask32x16::from_bitmask((1u64 << lane_count) - 1)
+}
+
+/// load a register of a row, the lanes after the end of the row are zero
+fn load(elements: &[f32], lanes: mask32x16) -> f32x16 {
+    lanes.select(f32x16::load_or_default(elements), f32x16::splat(0.0))
+}
+
+/// store a register of a row, the lanes after the end of the row are set to zero if they are part of the padding
+fn store(register: f32x16, lanes: mask32x16, elements: &mut [f32]) {
+    let register = lanes.select(register, f32x16::splat(0.0));
+    let length = elements.len();
+    elements.copy_from_slice(&register.as_array()[..length]);
+}
+
+impl<const D: usize> Array<D> {
+    /// Apply `f` to each register of 16 elements of a row, the lanes after the end of a row are zero
+    /// when `f` is called and are set to zero again afterwards.
+    pub fn map16(&self, f: impl FnMut(f32x16) -> f32x16) -> Self {
+        let mut new_array = self.clone();
+        new_array.map16_in_place(f);
+
+        new_array
+    }
+
+    /// Apply `f` to each register of 16 elements of a row, see `map16`
+    pub fn map16_in_place(&mut self, mut f: impl FnMut(f32x16) -> f32x16) {
+        let column_count = self.shape[D - 1];
+        let row_stride = self.row_stride().max(1);
+
+        for row in self.padded_elements_mut().chunks_mut(row_stride) {
+            for (i, elements) in row.chunks_mut(16).enumerate() {
+                let lanes = register_lanes(column_count, i);
+                store(f(load(elements, lanes)), lanes, elements);
+            }
+        }
+    }
+
+    /// Apply `f` to the corresponding registers of both arrays, see `map16`
+    #[track_caller]
+    pub fn zip_map16(&self, other: &Self, f: impl FnMut(f32x16, f32x16) -> f32x16) -> Self {
+        let mut new_array = self.clone();
+        new_array.zip_map16_in_place(other, f);
+
+        new_array
+    }
+
+    /// Apply `f` to the corresponding registers of both arrays and store the result in `self`, see `map16`
+    #[track_caller]
+    pub fn zip_map16_in_place(
+        &mut self,
+        other: &Self,
+        mut f: impl FnMut(f32x16, f32x16) -> f32x16,
+    ) {
+        assert_same_shape2(self, other);
+
+        let column_count = self.shape[D - 1];
+        let row_stride = self.row_stride().max(1);
+
+        for (row, other_row) in self
+            .padded_elements_mut()
+            .chunks_mut(row_stride)
+            .zip(other.padded_elements().chunks(row_stride))
+        {
+            for (i, (elements, other_elements)) in
+                row.chunks_mut(16).zip(other_row.chunks(16)).enumerate()
+            {
+                let lanes = register_lanes(column_count, i);
+                let result = f(load(elements, lanes), load(other_elements, lanes));
+                store(result, lanes, elements);
+            }
+        }
+    }
+
+    /// Apply `f` to the corresponding registers of three arrays, see `map16`
+    #[track_caller]
+    pub fn zip3_map16(
+        &self,
+        b: &Self,
+        c: &Self,
+        f: impl FnMut(f32x16, f32x16, f32x16) -> f32x16,
+    ) -> Self {
+        let mut new_array = self.clone();
+        new_array.zip3_map16_in_place(b, c, f);
+
+        new_array
+    }
+
+    /// Apply `f` to the corresponding registers of three arrays and store the result in `self`, see `map16`
+    #[track_caller]
+    pub fn zip3_map16_in_place(
+        &mut self,
+        b: &Self,
+        c: &Self,
+        mut f: impl FnMut(f32x16, f32x16, f32x16) -> f32x16,
+    ) {
+        assert_same_shape3(self, b, c);
+
+        let column_count = self.shape[D - 1];
+        let row_stride = self.row_stride().max(1);
+
+        for ((row, b_row), c_row) in self
+            .padded_elements_mut()
+            .chunks_mut(row_stride)
+            .zip(b.padded_elements().chunks(row_stride))
+            .zip(c.padded_elements().chunks(row_stride))
+        {
+            for (i, ((elements, b_elements), c_elements)) in row
+                .chunks_mut(16)
+                .zip(b_row.chunks(16))
+                .zip(c_row.chunks(16))
+                .enumerate()
+            {
+                let lanes = register_lanes(column_count, i);
+                let result = f(
+                    load(elements, lanes),
+                    load(b_elements, lanes),
+                    load(c_elements, lanes),
+                );
+                store(result, lanes, elements);
+            }
+        }
+    }
+
+    /// Apply `f` to each element
+    pub fn map(&self, f: impl FnMut(f32) -> f32) -> Self {
+        let mut new_array = self.clone();
+        new_array.map_in_place(f);
+
+        new_array
+    }
+
+    /// Apply `f` to each element
+    pub fn map_in_place(&mut self, mut f: impl FnMut(f32) -> f32) {
+        for element in self.iter_mut() {
+            *element = f(*element);
+        }
+    }
+}
@@ -14,7 +14,14 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use crate::Array;
+use std::io;
+
+use crate::{
+    error::{
+        check_index, check_repeat, check_same_length, check_same_shape, check_tile, unwrap_or_panic,
+    },
+    Array, Error, Mask,
+};
 
 #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
 mod avx512f;
@@ -22,8 +29,199 @@ mod avx512f;
 #[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
 mod fallback;
 
+mod approx;
+mod constructors;
+mod fallible;
+mod iter;
+mod join;
+mod map;
+mod reshape;
+mod rows;
+
+pub use approx::{Difference, Tolerance};
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+pub(crate) use avx512f::exp_register;
+pub(crate) use iter::advance_index;
+pub use iter::{ArrayIndexedIter, ArrayIntoIter, ArrayIter, ArrayIterMut};
+
+fn check_same_shape2<const D: usize>(a: &Array<D>, b: &Array<D>) -> Result<(), Error> {
+    check_same_shape(&a.shape, &b.shape)
+}
+
+fn check_same_shape3<const D: usize>(
+    a: &Array<D>,
+    b: &Array<D>,
+    c: &Array<D>,
+) -> Result<(), Error> {
+    check_same_shape(&a.shape, &b.shape)?;
+    check_same_shape(&a.shape, &c.shape)
+}
+
+fn check_same_shape_mask<const D: usize>(a: &Array<D>, mask: &Mask<D>) -> Result<(), Error> {
+    check_same_shape(&a.shape, mask.get_shape())
+}
+
+fn check_same_shape_with_mask2<const D: usize>(
+    a: &Array<D>,
+    b: &Array<D>,
+    mask: &Mask<D>,
+) -> Result<(), Error> {
+    check_same_shape2(a, b)?;
+    check_same_shape_mask(a, mask)
+}
+
+fn check_same_shape_with_mask3<const D: usize>(
+    a: &Array<D>,
+    b: &Array<D>,
+    c: &Array<D>,
+    mask: &Mask<D>,
+) -> Result<(), Error> {
+    check_same_shape3(a, b, c)?;
+    check_same_shape_mask(a, mask)
+}
+
+#[track_caller]
+fn assert_same_shape2<const D: usize>(a: &Array<D>, b: &Array<D>) {
+    unwrap_or_panic(check_same_shape2(a, b));
+}
+
+#[track_caller]
+fn assert_same_shape3<const D: usize>(a: &Array<D>, b: &Array<D>, c: &Array<D>) {
+    unwrap_or_panic(check_same_shape3(a, b, c));
+}
+
+#[track_caller]
+fn assert_same_shape_mask<const D: usize>(a: &Array<D>, mask: &Mask<D>) {
+    unwrap_or_panic(check_same_shape_mask(a, mask));
+}
+
+#[track_caller]
+fn assert_same_shape_with_mask2<const D: usize>(a: &Array<D>, b: &Array<D>, mask: &Mask<D>) {
+    unwrap_or_panic(check_same_shape_with_mask2(a, b, mask));
+}
+
+#[track_caller]
+fn assert_same_shape_with_mask3<const D: usize>(
+    a: &Array<D>,
+    b: &Array<D>,
+    c: &Array<D>,
+    mask: &Mask<D>,
+) {
+    unwrap_or_panic(check_same_shape_with_mask3(a, b, c, mask));
+}
+
+impl Array<1> {
+    fn check_index(&self, index: usize) -> Result<(), Error> {
+        check_index(0, index, self.shape[0])
+    }
+
+    fn check_tile(&self, k: usize, output: &Array<1>) -> Result<(), Error> {
+        check_tile(self.shape[0], k, output.shape[0])
+    }
+
+    fn check_repeat(&self, k: usize, output: &Array<1>) -> Result<(), Error> {
+        check_repeat(self.shape[0], k, output.shape[0])
+    }
+
+    fn check_repeat_as_row(&self, k: usize, output: &Array<2>) -> Result<(), Error> {
+        check_same_shape(&[k, self.shape[0]], &output.shape)
+    }
+
+    fn check_repeat_as_column(&self, k: usize, output: &Array<2>) -> Result<(), Error> {
+        check_same_shape(&[self.shape[0], k], &output.shape)
+    }
+}
+
+impl Array<2> {
+    fn check_index(&self, row: usize, column: usize) -> Result<(), Error> {
+        check_index(0, row, self.shape[0])?;
+        check_index(1, column, self.shape[1])
+    }
+
+    fn check_vector_multiplication(&self, other: &Array<1>) -> Result<(), Error> {
+        check_same_length(self.shape[1], other.shape[0])
+    }
+
+    fn check_matrix_multiplication(&self, other: &Array<2>) -> Result<(), Error> {
+        check_same_length(self.shape[1], other.shape[0])
+    }
+
+    fn check_vector_multiplication_out_of_place(
+        &self,
+        other: &Array<1>,
+        output: &Array<1>,
+    ) -> Result<(), Error> {
+        self.check_vector_multiplication(other)?;
+        check_same_length(self.shape[0], output.shape[0])
+    }
+
+    fn check_matrix_multiplication_out_of_place(
+        &self,
+        other: &Array<2>,
+        output: &Array<2>,
+    ) -> Result<(), Error> {
+        self.check_matrix_multiplication(other)?;
+        check_same_shape(&[self.shape[0], other.shape[1]], &output.shape)
+    }
+
+    fn check_matrix_multiplication_transposed_out_of_place(
+        &self,
+        transposed_other: &Array<2>,
+        output: &Array<2>,
+    ) -> Result<(), Error> {
+        check_same_length(self.shape[1], transposed_other.shape[1])?;
+        check_same_shape(&[self.shape[0], transposed_other.shape[0]], &output.shape)
+    }
+
+    fn check_transpose_out_of_place(&self, output: &Array<2>) -> Result<(), Error> {
+        check_same_shape(&[self.shape[1], self.shape[0]], &output.shape)
+    }
+
+    fn check_row(&self, row: usize, values: &Array<1>) -> Result<(), Error> {
+        check_index(0, row, self.shape[0])?;
+        check_same_length(self.shape[1], values.shape[0])
+    }
+
+    fn check_column(&self, column: usize, values: &Array<1>) -> Result<(), Error> {
+        check_index(1, column, self.shape[1])?;
+        check_same_length(self.shape[0], values.shape[0])
+    }
+
+    fn check_row_broadcast(&self, row: &Array<1>) -> Result<(), Error> {
+        check_same_length(self.shape[1], row.shape[0])
+    }
+
+    fn check_column_broadcast(&self, column: &Array<1>) -> Result<(), Error> {
+        check_same_length(self.shape[0], column.shape[0])
+    }
+
+    fn check_sum_to_row_masked(&self, mask: &Mask<2>, output: &Array<1>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+        check_same_length(self.shape[1], output.shape[0])
+    }
+
+    fn check_sum_to_column_masked(&self, mask: &Mask<2>, output: &Array<1>) -> Result<(), Error> {
+        check_same_shape_mask(self, mask)?;
+        check_same_length(self.shape[0], output.shape[0])
+    }
+}
+
 impl<const D: usize> Array<D> {
     pub fn get_shape(&self) -> [usize; D] {
         self.shape
     }
+
+    /// Serialize the array in the compact binary format of `write_to`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_to(&mut bytes)
+            .expect("writing to a Vec should not fail");
+
+        bytes
+    }
+
+    /// Deserialize an array in the compact binary format of `write_to`
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        Self::read_from(bytes)
+    }
 }
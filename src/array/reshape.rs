@@ -0,0 +1,130 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::{
+    error::{check_element_count, check_same_length, unwrap_or_panic},
+    Array, Error,
+};
+
+impl<const D: usize> Array<D> {
+    fn check_reshape<const E: usize>(&self, shape: &[usize; E]) -> Result<(), Error> {
+        check_same_length(check_element_count(shape)?, self.number_of_elements())
+    }
+
+    /// the padded rows of both shapes line up, so the data can be moved without repacking
+    fn has_same_layout<const E: usize>(&self, shape: &[usize; E]) -> bool {
+        let old_column_count = *self.shape.last().unwrap();
+        let new_column_count = shape[E - 1];
+
+        old_column_count == new_column_count
+            || (Self::padded_row_length(old_column_count) == old_column_count
+                && Array::<E>::padded_row_length(new_column_count) == new_column_count)
+    }
+
+    /// copy the elements into the rows of an array with a different number of columns
+    fn repack<const E: usize>(&self, shape: [usize; E]) -> Array<E> {
+        let mut output = Array::zeros(&shape);
+        let old_column_count = *self.shape.last().unwrap();
+        let new_column_count = shape[E - 1];
+
+        // without columns there are no elements to copy
+        if old_column_count == 0 || new_column_count == 0 {
+            return output;
+        }
+
+        let mut old_row = 0;
+        let mut old_column = 0;
+
+        for new_row in 0..self.number_of_elements() / new_column_count {
+            let destination = output.row_elements_mut(new_row);
+            let mut new_column = 0;
+
+            // copy the longest contiguous runs that fit into both rows
+            while new_column < new_column_count {
+                let count = (new_column_count - new_column).min(old_column_count - old_column);
+                destination[new_column..new_column + count]
+                    .copy_from_slice(&self.row_elements(old_row)[old_column..old_column + count]);
+
+                new_column += count;
+                old_column += count;
+
+                if old_column == old_column_count {
+                    old_row += 1;
+                    old_column = 0;
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Copy the elements in row-major order into an array with a different shape, the number of elements must stay the same
+    #[track_caller]
+    pub fn reshape<const E: usize>(&self, shape: [usize; E]) -> Array<E> {
+        unwrap_or_panic(self.check_reshape(&shape));
+
+        if self.has_same_layout(&shape) {
+            Array {
+                data: self.data.clone(),
+                shape,
+            }
+        } else {
+            self.repack(shape)
+        }
+    }
+
+    /// Change the shape without copying if the padded rows line up, which is the case if the last dimension stays
+    /// the same or both last dimensions are multiples of 16. Otherwise the elements are repacked.
+    #[track_caller]
+    pub fn into_shape<const E: usize>(self, shape: [usize; E]) -> Array<E> {
+        unwrap_or_panic(self.check_reshape(&shape));
+
+        if self.has_same_layout(&shape) {
+            Array {
+                data: self.data,
+                shape,
+            }
+        } else {
+            self.repack(shape)
+        }
+    }
+
+    /// Copy the elements in row-major order into a one-dimensional array
+    pub fn flatten(&self) -> Array<1> {
+        self.reshape([self.number_of_elements()])
+    }
+
+    /// Convert into a one-dimensional array, see `into_shape`
+    pub fn into_flattened(self) -> Array<1> {
+        let element_count = self.number_of_elements();
+
+        self.into_shape([element_count])
+    }
+
+    /// fallible version of `reshape`
+    pub fn try_reshape<const E: usize>(&self, shape: [usize; E]) -> Result<Array<E>, Error> {
+        self.check_reshape(&shape)?;
+
+        Ok(self.reshape(shape))
+    }
+
+    /// fallible version of `into_shape`
+    pub fn try_into_shape<const E: usize>(self, shape: [usize; E]) -> Result<Array<E>, Error> {
+        self.check_reshape(&shape)?;
+
+        Ok(self.into_shape(shape))
+    }
+}
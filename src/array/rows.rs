@@ -0,0 +1,81 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::{
+    error::{check_index, unwrap_or_panic},
+    Array,
+};
+
+impl Array<2> {
+    /// the elements of a row including the padding, a whole number of registers
+    fn padded_row(&self, row: usize) -> &[f32] {
+        let row_stride = self.row_stride();
+
+        &self.padded_elements()[row * row_stride..(row + 1) * row_stride]
+    }
+
+    /// Copy a row into a new array, the registers of the row are copied as a whole
+    pub fn get_row(&self, row: usize) -> Array<1> {
+        unwrap_or_panic(check_index(0, row, self.shape[0]));
+
+        let mut output = Array::zeros(&[self.shape[1]]);
+        output
+            .padded_elements_mut()
+            .copy_from_slice(self.padded_row(row));
+
+        output
+    }
+
+    /// Overwrite a row with `values`, the registers of the row are copied as a whole
+    pub fn copy_row_from(&mut self, row: usize, values: &Array<1>) {
+        unwrap_or_panic(self.check_row(row, values));
+
+        let row_stride = self.row_stride();
+
+        self.padded_elements_mut()[row * row_stride..(row + 1) * row_stride]
+            .copy_from_slice(values.padded_elements());
+    }
+
+    /// Copy a column into a new array
+    pub fn column(&self, column: usize) -> Array<1> {
+        unwrap_or_panic(check_index(1, column, self.shape[1]));
+
+        let mut output = Array::zeros(&[self.shape[0]]);
+        let elements = self.padded_elements()[column..]
+            .iter()
+            .step_by(self.row_stride());
+
+        for (output, element) in output.row_elements_mut(0).iter_mut().zip(elements) {
+            *output = *element;
+        }
+
+        output
+    }
+
+    /// Overwrite a column with `values`
+    pub fn set_column(&mut self, column: usize, values: &Array<1>) {
+        unwrap_or_panic(self.check_column(column, values));
+
+        let row_stride = self.row_stride();
+        let elements = self.padded_elements_mut()[column..]
+            .iter_mut()
+            .step_by(row_stride);
+
+        for (element, value) in elements.zip(values.row_elements(0)) {
+            *element = *value;
+        }
+    }
+}
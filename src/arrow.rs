@@ -0,0 +1,258 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+// Conversions between arrays and masks and the corresponding Arrow arrays.
+//
+// Arrow marks missing values as null in a separate validity bitmap. Conversions that accept nulls return
+// this bitmap as a `Mask` with the same shape, where `true` marks a valid element.
+
+use std::sync::Arc;
+
+use arrow_array::{Array as _, BooleanArray, FixedSizeListArray, Float32Array};
+use arrow_buffer::{BooleanBuffer, BooleanBufferBuilder, NullBuffer, ScalarBuffer};
+use arrow_schema::{DataType, Field};
+
+use crate::{
+    error::{check_same_shape, unwrap_or_panic},
+    Array, Error, Mask,
+};
+
+/// create a mask from `shape[0] * shape[1]` bits in row-major order
+fn mask_from_bits<const D: usize>(bits: &BooleanBuffer, shape: [usize; D]) -> Mask<D> {
+    let column_count = shape[D - 1];
+    let row_count: usize = shape[..D - 1].iter().product();
+    let words_per_row = column_count.div_ceil(16);
+    let mut words = Vec::with_capacity(row_count * words_per_row);
+
+    for row in 0..row_count {
+        let chunks = bits
+            .inner()
+            .bit_chunks(bits.offset() + row * column_count, column_count);
+        let end = words.len() + words_per_row;
+
+        // the bits after the end of the row are zero
+        for chunk in chunks.iter_padded() {
+            for i in 0..4 {
+                words.push((chunk >> (16 * i)) as u16);
+            }
+        }
+
+        words.truncate(end);
+    }
+
+    Mask::from_words(words, shape)
+}
+
+/// the elements of the mask as bits in row-major order
+fn bits_from_mask<const D: usize>(mask: &Mask<D>) -> BooleanBuffer {
+    let column_count = mask.get_shape()[D - 1];
+    let words = mask.to_words();
+    let mut builder = BooleanBufferBuilder::new(mask.get_shape().iter().product());
+
+    if column_count > 0 {
+        for row in words.chunks(column_count.div_ceil(16)) {
+            let bytes: Vec<u8> = row.iter().flat_map(|w| w.to_le_bytes()).collect();
+            builder.append_packed_range(0..column_count, &bytes);
+        }
+    }
+
+    builder.finish()
+}
+
+/// the validity of the elements of `shape`, all elements are valid if there are no nulls
+fn validity_mask<const D: usize>(nulls: Option<&NullBuffer>, shape: [usize; D]) -> Mask<D> {
+    match nulls {
+        Some(nulls) => mask_from_bits(nulls.inner(), shape),
+        None => mask_from_bits(&BooleanBuffer::new_set(shape.iter().product()), shape),
+    }
+}
+
+fn check_no_nulls(nulls: Option<&NullBuffer>) -> Result<(), Error> {
+    match nulls {
+        Some(nulls) if nulls.null_count() > 0 => Err(Error::NullValues {
+            count: nulls.null_count(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn float32_values(list: &FixedSizeListArray) -> Result<&Float32Array, Error> {
+    list.values()
+        .as_any()
+        .downcast_ref::<Float32Array>()
+        .ok_or_else(|| Error::UnsupportedDtype {
+            expected: "Float32",
+            found: list.value_type().to_string(),
+        })
+}
+
+/// the values of the list and the validity of each element, which combines the nulls of the lists and the values
+fn list_elements(list: &FixedSizeListArray) -> Result<(&[f32], Option<NullBuffer>), Error> {
+    let values = float32_values(list)?;
+    let column_count = list.value_length() as usize;
+    // slicing a list also slices its values, so they start at the first list
+    let length = list.len() * column_count;
+
+    let value_nulls = values.nulls().map(|n| n.slice(0, length));
+    let list_nulls = list.nulls().map(|n| {
+        let mut builder = BooleanBufferBuilder::new(length);
+
+        for valid in n.iter() {
+            builder.append_n(column_count, valid);
+        }
+
+        NullBuffer::new(builder.finish())
+    });
+
+    Ok((
+        &values.values()[..length],
+        NullBuffer::union(value_nulls.as_ref(), list_nulls.as_ref()),
+    ))
+}
+
+fn nulls_from_mask<const D: usize>(validity: &Mask<D>) -> Option<NullBuffer> {
+    Some(NullBuffer::new(bits_from_mask(validity)))
+}
+
+impl TryFrom<&Float32Array> for Array<1> {
+    type Error = Error;
+
+    /// fails if the Arrow array contains nulls, use `from_arrow_with_validity` for them
+    fn try_from(value: &Float32Array) -> Result<Self, Self::Error> {
+        check_no_nulls(value.nulls())?;
+
+        Ok(Array::from_elements(value.values().to_vec(), [value.len()]))
+    }
+}
+
+impl From<&Array<1>> for Float32Array {
+    fn from(value: &Array<1>) -> Self {
+        Float32Array::new(ScalarBuffer::from(value.to_elements()), None)
+    }
+}
+
+impl Array<1> {
+    /// Convert an Arrow array that might contain nulls, the returned mask is `false` for null elements.
+    /// The elements of the array at null positions are taken from the Arrow buffer as they are.
+    pub fn from_arrow_with_validity(array: &Float32Array) -> (Self, Mask<1>) {
+        let converted = Array::from_elements(array.values().to_vec(), [array.len()]);
+        let validity = validity_mask(array.nulls(), [array.len()]);
+
+        (converted, validity)
+    }
+
+    /// Convert to an Arrow array, where the elements are null if the mask is `false`
+    pub fn to_arrow_with_validity(&self, validity: &Mask<1>) -> Float32Array {
+        unwrap_or_panic(check_same_shape(&self.shape, validity.get_shape()));
+
+        Float32Array::new(
+            ScalarBuffer::from(self.to_elements()),
+            nulls_from_mask(validity),
+        )
+    }
+}
+
+impl TryFrom<&BooleanArray> for Mask<1> {
+    type Error = Error;
+
+    /// fails if the Arrow array contains nulls, use `from_arrow_with_validity` for them
+    fn try_from(value: &BooleanArray) -> Result<Self, Self::Error> {
+        check_no_nulls(value.nulls())?;
+
+        Ok(mask_from_bits(value.values(), [value.len()]))
+    }
+}
+
+impl From<&Mask<1>> for BooleanArray {
+    fn from(value: &Mask<1>) -> Self {
+        BooleanArray::new(bits_from_mask(value), None)
+    }
+}
+
+impl Mask<1> {
+    /// Convert an Arrow array that might contain nulls, the second mask is `false` for null elements.
+    /// The elements of the first mask at null positions are taken from the Arrow buffer as they are.
+    pub fn from_arrow_with_validity(array: &BooleanArray) -> (Self, Mask<1>) {
+        let converted = mask_from_bits(array.values(), [array.len()]);
+        let validity = validity_mask(array.nulls(), [array.len()]);
+
+        (converted, validity)
+    }
+
+    /// Convert to an Arrow array, where the elements are null if `validity` is `false`
+    pub fn to_arrow_with_validity(&self, validity: &Mask<1>) -> BooleanArray {
+        unwrap_or_panic(check_same_shape(self.get_shape(), validity.get_shape()));
+
+        BooleanArray::new(bits_from_mask(self), nulls_from_mask(validity))
+    }
+}
+
+impl TryFrom<&FixedSizeListArray> for Array<2> {
+    type Error = Error;
+
+    /// Each list becomes a row, the values must have the type Float32.
+    /// Fails if a list or a value is null, use `from_arrow_with_validity` for them.
+    fn try_from(value: &FixedSizeListArray) -> Result<Self, Self::Error> {
+        let (elements, nulls) = list_elements(value)?;
+        check_no_nulls(nulls.as_ref())?;
+
+        Ok(Array::from_elements(
+            elements.to_vec(),
+            [value.len(), value.value_length() as usize],
+        ))
+    }
+}
+
+impl From<&Array<2>> for FixedSizeListArray {
+    /// each row becomes a list of Float32 values
+    fn from(value: &Array<2>) -> Self {
+        value.to_arrow(None)
+    }
+}
+
+impl Array<2> {
+    /// Convert an Arrow array that might contain nulls, the returned mask is `false` for elements that are null
+    /// or that belong to a null list. The elements of the array at null positions are taken from the Arrow buffer
+    /// as they are.
+    pub fn from_arrow_with_validity(array: &FixedSizeListArray) -> Result<(Self, Mask<2>), Error> {
+        let (elements, nulls) = list_elements(array)?;
+        let shape = [array.len(), array.value_length() as usize];
+
+        Ok((
+            Array::from_elements(elements.to_vec(), shape),
+            validity_mask(nulls.as_ref(), shape),
+        ))
+    }
+
+    /// Convert to an Arrow array, where the values are null if the mask is `false`
+    pub fn to_arrow_with_validity(&self, validity: &Mask<2>) -> FixedSizeListArray {
+        unwrap_or_panic(check_same_shape(&self.shape, validity.get_shape()));
+
+        self.to_arrow(nulls_from_mask(validity))
+    }
+
+    fn to_arrow(&self, nulls: Option<NullBuffer>) -> FixedSizeListArray {
+        let field = Field::new("item", DataType::Float32, nulls.is_some());
+        let values = Float32Array::new(ScalarBuffer::from(self.to_elements()), nulls);
+
+        FixedSizeListArray::new(
+            Arc::new(field),
+            self.shape[1] as i32,
+            Arc::new(values),
+            None,
+        )
+    }
+}
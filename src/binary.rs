@@ -0,0 +1,245 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+// Helpers for the compact binary format of `Array` and `Mask`.
+//
+// The format starts with a header:
+// - 8 bytes magic `FASTARR\0`
+// - version as u16
+// - kind as u8 (0 for `Array`, 1 for `Mask`, 2 for a padded `Array`)
+// - rank as u8
+// - each axis of the shape as u64
+//
+// followed by the payload. Arrays store their elements in row-major order as f32 without padding.
+// Masks store each row as 16-bit words of packed bits, unused bits at the end of a row are zero.
+// Padded arrays are meant to be memory-mapped, their payload starts at the next multiple of 64 bytes
+// after the header and each row is padded with zeros to a multiple of 16 elements.
+// All numbers are little-endian.
+
+use std::io::{self, Read, Write};
+
+use crate::{
+    error::{check_deserialized_shape, check_element_count},
+    Error,
+};
+
+const MAGIC: &[u8; 8] = b"FASTARR\0";
+const VERSION: u16 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum Kind {
+    Array = 0,
+    Mask = 1,
+    #[cfg(feature = "mmap")]
+    PaddedArray = 2,
+}
+
+pub(crate) fn invalid_data(error: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn invalid_format(reason: &'static str) -> io::Error {
+    invalid_data(Error::InvalidFormat { reason })
+}
+
+pub(crate) fn write_header(writer: &mut impl Write, kind: Kind, shape: &[usize]) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    let rank = u8::try_from(shape.len()).map_err(|_| {
+        invalid_data(Error::InvalidShape {
+            shape: shape.to_vec(),
+            reason: "the binary format supports at most 255 dimensions",
+        })
+    })?;
+    writer.write_all(&[kind as u8, rank])?;
+
+    for s in shape {
+        writer.write_all(&(*s as u64).to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// read and validate the header, returns the shape
+pub(crate) fn read_header<const D: usize>(
+    reader: &mut impl Read,
+    kind: Kind,
+) -> io::Result<[usize; D]> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+
+    if &magic != MAGIC {
+        return Err(invalid_format("the magic bytes don't match"));
+    }
+
+    let mut version = [0u8; 2];
+    reader.read_exact(&mut version)?;
+
+    if u16::from_le_bytes(version) != VERSION {
+        return Err(invalid_format("unsupported version"));
+    }
+
+    let mut kind_and_rank = [0u8; 2];
+    reader.read_exact(&mut kind_and_rank)?;
+
+    if kind_and_rank[0] != kind as u8 {
+        return Err(match kind {
+            Kind::Array => invalid_format("the data doesn't contain an array"),
+            Kind::Mask => invalid_format("the data doesn't contain a mask"),
+            #[cfg(feature = "mmap")]
+            Kind::PaddedArray => invalid_format("the data doesn't contain a padded array"),
+        });
+    }
+
+    let mut shape = Vec::with_capacity(kind_and_rank[1] as usize);
+
+    for _ in 0..kind_and_rank[1] {
+        let mut axis = [0u8; 8];
+        reader.read_exact(&mut axis)?;
+
+        let axis = usize::try_from(u64::from_le_bytes(axis))
+            .map_err(|_| invalid_format("an axis is too large for this platform"))?;
+        shape.push(axis);
+    }
+
+    let element_count = check_element_count(&shape).map_err(invalid_data)?;
+
+    check_deserialized_shape(&shape, element_count).map_err(invalid_data)
+}
+
+/// the offset of the payload of a padded array with `rank` dimensions
+#[cfg(feature = "mmap")]
+pub(crate) fn padded_payload_offset(rank: usize) -> usize {
+    (12 + 8 * rank).next_multiple_of(64)
+}
+
+/// read exactly `len` bytes without allocating them all up front, so that a corrupted shape can't exhaust the memory
+pub(crate) fn read_payload(reader: &mut impl Read, len: usize) -> io::Result<Vec<u8>> {
+    let mut payload = Vec::new();
+    reader.take(len as u64).read_to_end(&mut payload)?;
+
+    if payload.len() != len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the payload is shorter than its shape",
+        ));
+    }
+
+    Ok(payload)
+}
+
+/// the number of bytes of the payload with `count` elements of size `size`
+pub(crate) fn payload_length(count: usize, size: usize) -> io::Result<usize> {
+    count
+        .checked_mul(size)
+        .ok_or_else(|| invalid_format("the payload length overflows usize"))
+}
+
+/// write the elements of an array as little-endian f32
+pub(crate) fn write_f32_payload(writer: &mut impl Write, elements: &[f32]) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(elements.len().min(4096) * 4);
+
+    for chunk in elements.chunks(4096) {
+        bytes.clear();
+
+        for e in chunk {
+            bytes.extend_from_slice(&e.to_le_bytes());
+        }
+
+        writer.write_all(&bytes)?;
+    }
+
+    Ok(())
+}
+
+/// the number of elements that are read at once
+pub(crate) const READ_CHUNK_SIZE: usize = 4096;
+
+/// fill `output` with little-endian f32 from the reader
+pub(crate) fn read_f32s(reader: &mut impl Read, output: &mut [f32]) -> io::Result<()> {
+    let mut bytes = [0u8; READ_CHUNK_SIZE * 4];
+
+    for chunk in output.chunks_mut(READ_CHUNK_SIZE) {
+        let bytes = &mut bytes[..chunk.len() * 4];
+        reader.read_exact(bytes)?;
+
+        for (o, b) in chunk.iter_mut().zip(bytes.chunks_exact(4)) {
+            *o = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+        }
+    }
+
+    Ok(())
+}
+
+/// fill `output` with booleans stored as one byte each, every byte that isn't 0 is true
+pub(crate) fn read_bools(reader: &mut impl Read, output: &mut [bool]) -> io::Result<()> {
+    let mut bytes = [0u8; READ_CHUNK_SIZE];
+
+    for chunk in output.chunks_mut(READ_CHUNK_SIZE) {
+        let bytes = &mut bytes[..chunk.len()];
+        reader.read_exact(bytes)?;
+
+        for (o, b) in chunk.iter_mut().zip(bytes.iter()) {
+            *o = *b != 0;
+        }
+    }
+
+    Ok(())
+}
+
+/// the number of 16-bit words of a mask, each row starts with a new word
+pub(crate) fn mask_word_count(shape: &[usize]) -> usize {
+    let words_per_row = shape.last().unwrap().div_ceil(16);
+    let row_count: usize = shape[..shape.len() - 1].iter().product();
+
+    row_count * words_per_row
+}
+
+pub(crate) fn write_mask_payload(writer: &mut impl Write, words: &[u16]) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(words.len() * 2);
+
+    for w in words {
+        bytes.extend_from_slice(&w.to_le_bytes());
+    }
+
+    writer.write_all(&bytes)
+}
+
+/// read the words of a mask and check that the unused bits at the end of each row are zero
+pub(crate) fn read_mask_payload(reader: &mut impl Read, shape: &[usize]) -> io::Result<Vec<u16>> {
+    let word_count = mask_word_count(shape);
+    let payload = read_payload(reader, payload_length(word_count, 2)?)?;
+    let words: Vec<u16> = payload
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+
+    let column_count = *shape.last().unwrap();
+    let words_per_row = column_count.div_ceil(16);
+
+    if !column_count.is_multiple_of(16) {
+        let unused_bits = !(0xFFFFu16 >> (16 - column_count % 16));
+
+        for row in words.chunks(words_per_row) {
+            if row[words_per_row - 1] & unused_bits != 0 {
+                return Err(invalid_format("unused bits of a mask row are set"));
+            }
+        }
+    }
+
+    Ok(words)
+}
@@ -0,0 +1,286 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::{binary::invalid_data, Array, Error};
+
+/// What to do with empty cells when reading a CSV file
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmptyCellPolicy {
+    /// return a parse error
+    Error,
+    /// read empty cells as NaN
+    Nan,
+    /// read empty cells as the given value
+    Value(f32),
+}
+
+/// How floats are formatted when writing a CSV file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// the shortest representation that is read back as the same value
+    Shortest,
+    /// a fixed number of decimal places
+    Fixed(usize),
+    /// scientific notation with a fixed number of decimal places
+    Scientific(usize),
+}
+
+/// Options for reading CSV files, the default reads comma separated values without header
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvReadOptions {
+    pub delimiter: char,
+    /// skip the first line
+    pub has_header: bool,
+    pub empty_cells: EmptyCellPolicy,
+}
+
+impl Default for CsvReadOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            has_header: false,
+            empty_cells: EmptyCellPolicy::Error,
+        }
+    }
+}
+
+impl CsvReadOptions {
+    /// the default options, but with tabs as delimiter
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Default::default()
+        }
+    }
+}
+
+/// Options for writing CSV files, the default writes comma separated values without header
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvWriteOptions {
+    pub delimiter: char,
+    /// the names of the columns that are written as the first line
+    pub header: Option<Vec<String>>,
+    /// the text that is written for NaN, an empty string creates empty cells
+    pub nan: String,
+    pub float_format: FloatFormat,
+}
+
+impl Default for CsvWriteOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            header: None,
+            nan: "NaN".to_string(),
+            float_format: FloatFormat::Shortest,
+        }
+    }
+}
+
+impl CsvWriteOptions {
+    /// the default options, but with tabs as delimiter
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Default::default()
+        }
+    }
+}
+
+fn parse_error(line: usize, column: usize, reason: String) -> io::Error {
+    invalid_data(Error::Parse {
+        line,
+        column,
+        reason,
+    })
+}
+
+fn parse_cell(cell: &str, line: usize, column: usize, options: &CsvReadOptions) -> io::Result<f32> {
+    let mut cell = cell.trim();
+
+    if cell.len() >= 2 && cell.starts_with('"') && cell.ends_with('"') {
+        cell = cell[1..cell.len() - 1].trim();
+    }
+
+    if cell.is_empty() {
+        return match options.empty_cells {
+            EmptyCellPolicy::Error => {
+                Err(parse_error(line, column, "the cell is empty".to_string()))
+            }
+            EmptyCellPolicy::Nan => Ok(f32::NAN),
+            EmptyCellPolicy::Value(value) => Ok(value),
+        };
+    }
+
+    cell.parse()
+        .map_err(|_| parse_error(line, column, format!("{:?} is not a number", cell)))
+}
+
+/// read all cells in row-major order, returns the cells and the number of rows and columns
+///
+/// Blank lines are skipped if there are several columns. With a single column a blank line is an empty cell, so
+/// that NaN written as an empty cell is read back.
+fn read_cells(
+    reader: impl Read,
+    options: &CsvReadOptions,
+    single_column: bool,
+) -> io::Result<(Vec<f32>, usize, usize)> {
+    let mut cells = Vec::new();
+    let mut row_count = 0;
+    let mut column_count = if single_column { Some(1) } else { None };
+    // the numbers of the blank lines before the number of columns is known
+    let mut blank_lines = Vec::new();
+
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let line_number = index + 1;
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+
+        if index == 0 && options.has_header {
+            column_count = Some(line.split(options.delimiter).count());
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            match column_count {
+                Some(1) => {
+                    cells.push(parse_cell("", line_number, 1, options)?);
+                    row_count += 1;
+                }
+                Some(_) => {}
+                None => blank_lines.push(line_number),
+            }
+
+            continue;
+        }
+
+        let mut columns = 0;
+
+        if single_column && line.contains(options.delimiter) {
+            return Err(parse_error(
+                line_number,
+                2,
+                "expected a single column".to_string(),
+            ));
+        }
+
+        if column_count.is_none() && !line.contains(options.delimiter) {
+            for blank_line in blank_lines.drain(..) {
+                cells.push(parse_cell("", blank_line, 1, options)?);
+                row_count += 1;
+            }
+        }
+
+        for (column, cell) in line.split(options.delimiter).enumerate() {
+            cells.push(parse_cell(cell, line_number, column + 1, options)?);
+            columns += 1;
+        }
+
+        let expected = *column_count.get_or_insert(columns);
+
+        if columns != expected {
+            return Err(parse_error(
+                line_number,
+                columns.min(expected) + 1,
+                format!("expected {} column(s), but found {}", expected, columns),
+            ));
+        }
+
+        row_count += 1;
+    }
+
+    Ok((cells, row_count, column_count.unwrap_or(0)))
+}
+
+fn format_value(value: f32, options: &CsvWriteOptions) -> String {
+    if value.is_nan() {
+        return options.nan.clone();
+    }
+
+    match options.float_format {
+        FloatFormat::Shortest => format!("{}", value),
+        FloatFormat::Fixed(precision) => format!("{:.*}", precision, value),
+        FloatFormat::Scientific(precision) => format!("{:.*e}", precision, value),
+    }
+}
+
+fn write_rows(
+    writer: impl Write,
+    elements: &[f32],
+    column_count: usize,
+    options: &CsvWriteOptions,
+) -> io::Result<()> {
+    let mut writer = io::BufWriter::new(writer);
+    let delimiter = options.delimiter.to_string();
+
+    if let Some(header) = &options.header {
+        writer.write_all(header.join(&delimiter).as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+
+    if column_count > 0 {
+        for row in elements.chunks(column_count) {
+            for (i, value) in row.iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(delimiter.as_bytes())?;
+                }
+
+                writer.write_all(format_value(*value, options).as_bytes())?;
+            }
+
+            writer.write_all(b"\n")?;
+        }
+    }
+
+    writer.flush()
+}
+
+impl Array<1> {
+    /// Read a CSV file with a single column, each line contains one element
+    pub fn from_csv(reader: impl Read, options: &CsvReadOptions) -> io::Result<Self> {
+        let (cells, _, _) = read_cells(reader, options, true)?;
+
+        Ok(cells.into())
+    }
+
+    /// Write the array as a CSV file with a single column
+    pub fn to_csv(&self, writer: impl Write, options: &CsvWriteOptions) -> io::Result<()> {
+        let elements: Vec<f32> = self.clone().into();
+
+        write_rows(writer, &elements, 1, options)
+    }
+}
+
+impl Array<2> {
+    /// Read a CSV file, each line is a row and all rows need the same number of columns
+    pub fn from_csv(reader: impl Read, options: &CsvReadOptions) -> io::Result<Self> {
+        let (cells, row_count, column_count) = read_cells(reader, options, false)?;
+
+        if row_count == 0 {
+            return Ok(Array::zeros(&[0, column_count]));
+        }
+
+        Ok(Array::from_vec(&cells, [row_count, column_count]))
+    }
+
+    /// Write the array as a CSV file with one line per row
+    pub fn to_csv(&self, writer: impl Write, options: &CsvWriteOptions) -> io::Result<()> {
+        let elements: Vec<f32> = self.clone().into();
+
+        write_rows(writer, &elements, self.shape[1], options)
+    }
+}
@@ -0,0 +1,355 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    fmt::{Display, Formatter},
+    ops::Range,
+};
+
+/// The error type of the fallible `try_` functions of `Array` and `Mask`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// the shapes of two operands don't match
+    ShapeMismatch {
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+    /// the number of dimensions doesn't match the expected number
+    DimensionMismatch { expected: usize, found: usize },
+    /// the number of elements doesn't match the expected number
+    LengthMismatch { expected: usize, found: usize },
+    /// an index is larger than the length of its axis
+    IndexOutOfBounds {
+        axis: usize,
+        index: usize,
+        length: usize,
+    },
+    /// the serialized data isn't in the expected format
+    InvalidFormat { reason: &'static str },
+    /// a value in a text file couldn't be parsed, line and column start at 1
+    Parse {
+        line: usize,
+        column: usize,
+        reason: String,
+    },
+    /// the data type of a file isn't supported
+    UnsupportedDtype {
+        expected: &'static str,
+        found: String,
+    },
+    /// the file stores the data in Fortran order, but only C order is supported
+    UnsupportedFortranOrder,
+    /// the shape can't be used, e.g. because it has zero dimensions or an empty last axis
+    InvalidShape {
+        shape: Vec<usize>,
+        reason: &'static str,
+    },
+    /// a range of indices is empty, reversed or extends beyond the length of its axis
+    InvalidRange {
+        axis: usize,
+        start: usize,
+        end: usize,
+        length: usize,
+    },
+    /// the data contains null values, which can only be converted together with a validity mask
+    NullValues { count: usize },
+    /// an axis is larger than the number of dimensions
+    InvalidAxis { axis: usize, dimensions: usize },
+    /// a function that joins arrays or masks was called without any
+    NoInputs,
+    /// an argument has a value that the function doesn't support, e.g. a count that must be a multiple of 16
+    InvalidArgument {
+        name: &'static str,
+        value: usize,
+        reason: &'static str,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ShapeMismatch { expected, found } => {
+                write!(f, "the shapes don't match: {:?} != {:?}", expected, found)
+            }
+            Error::DimensionMismatch { expected, found } => write!(
+                f,
+                "expected {} dimension(s), but found {} dimension(s)",
+                expected, found
+            ),
+            Error::LengthMismatch { expected, found } => write!(
+                f,
+                "expected {} element(s), but found {} element(s)",
+                expected, found
+            ),
+            Error::IndexOutOfBounds {
+                axis,
+                index,
+                length,
+            } => write!(
+                f,
+                "tried to access index {} of axis {}, but the axis has only {} element(s)",
+                index, axis, length
+            ),
+            Error::InvalidFormat { reason } => write!(f, "invalid format: {}", reason),
+            Error::Parse {
+                line,
+                column,
+                reason,
+            } => write!(f, "line {}, column {}: {}", line, column, reason),
+            Error::UnsupportedDtype { expected, found } => write!(
+                f,
+                "unsupported dtype {}, only {} is supported",
+                found, expected
+            ),
+            Error::UnsupportedFortranOrder => {
+                write!(f, "Fortran order is not supported, only C order")
+            }
+            Error::InvalidShape { shape, reason } => {
+                write!(f, "invalid shape {:?}: {}", shape, reason)
+            }
+            Error::InvalidRange {
+                axis,
+                start,
+                end,
+                length,
+            } => write!(
+                f,
+                "the range {}..{} of axis {} is invalid, the axis has {} element(s)",
+                start, end, axis, length
+            ),
+            Error::NullValues { count } => write!(
+                f,
+                "the data contains {} null value(s), use a conversion with a validity mask",
+                count
+            ),
+            Error::InvalidAxis { axis, dimensions } => write!(
+                f,
+                "the axis {} doesn't exist, there are only {} dimension(s)",
+                axis, dimensions
+            ),
+            Error::NoInputs => write!(f, "at least one input is needed"),
+            Error::InvalidArgument {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid {} {}: {}", name, value, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub(crate) fn check_same_shape(expected: &[usize], found: &[usize]) -> Result<(), Error> {
+    if expected != found {
+        return Err(Error::ShapeMismatch {
+            expected: expected.to_vec(),
+            found: found.to_vec(),
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_same_length(expected: usize, found: usize) -> Result<(), Error> {
+    if expected != found {
+        return Err(Error::LengthMismatch { expected, found });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_same_dimensions(expected: usize, found: usize) -> Result<(), Error> {
+    if expected != found {
+        return Err(Error::DimensionMismatch { expected, found });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_index(axis: usize, index: usize, length: usize) -> Result<(), Error> {
+    if index >= length {
+        return Err(Error::IndexOutOfBounds {
+            axis,
+            index,
+            length,
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_axis(axis: usize, dimensions: usize) -> Result<(), Error> {
+    if axis >= dimensions {
+        return Err(Error::InvalidAxis { axis, dimensions });
+    }
+
+    Ok(())
+}
+
+/// check that there are inputs and that their shapes only differ along `axis`
+pub(crate) fn check_joinable_shapes<const D: usize>(
+    shapes: impl IntoIterator<Item = [usize; D]>,
+    axis: usize,
+) -> Result<(), Error> {
+    check_axis(axis, D)?;
+    let mut shapes = shapes.into_iter();
+    let first = shapes.next().ok_or(Error::NoInputs)?;
+
+    for shape in shapes {
+        let mut expected = first;
+        expected[axis] = shape[axis];
+        check_same_shape(&expected, &shape)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_range(axis: usize, range: &Range<usize>, length: usize) -> Result<(), Error> {
+    if range.start > range.end || range.end > length {
+        return Err(Error::InvalidRange {
+            axis,
+            start: range.start,
+            end: range.end,
+            length,
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_non_empty_range(
+    axis: usize,
+    range: &Range<usize>,
+    length: usize,
+) -> Result<(), Error> {
+    if range.is_empty() {
+        return Err(Error::InvalidRange {
+            axis,
+            start: range.start,
+            end: range.end,
+            length,
+        });
+    }
+
+    check_range(axis, range, length)
+}
+
+/// check that the shape has at least one dimension and that no axis is empty
+/// check a length or count that is processed in whole registers of 16 elements
+pub(crate) fn check_multiple_of_16(name: &'static str, value: usize) -> Result<(), Error> {
+    if !value.is_multiple_of(16) {
+        return Err(Error::InvalidArgument {
+            name,
+            value,
+            reason: "it must be a multiple of 16",
+        });
+    }
+
+    Ok(())
+}
+
+/// check the lengths of `tile_in_place`, `output` has to hold `k` copies of the input
+pub(crate) fn check_tile(length: usize, k: usize, output_length: usize) -> Result<(), Error> {
+    check_multiple_of_16("number of elements", length)?;
+    check_same_length(length.saturating_mul(k), output_length)
+}
+
+/// check the lengths of `repeat_in_place`, `output` has to hold every element `k` times
+pub(crate) fn check_repeat(length: usize, k: usize, output_length: usize) -> Result<(), Error> {
+    check_multiple_of_16("k", k)?;
+    check_tile(length, k, output_length)
+}
+
+pub(crate) fn check_chunk_size(size: usize) -> Result<(), Error> {
+    if size == 0 {
+        return Err(Error::InvalidArgument {
+            name: "chunk size",
+            value: size,
+            reason: "it must be greater than 0",
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_non_empty_shape(shape: &[usize]) -> Result<(), Error> {
+    if shape.is_empty() {
+        return Err(Error::InvalidShape {
+            shape: shape.to_vec(),
+            reason: "the shape needs at least one dimension",
+        });
+    }
+
+    if shape.contains(&0) {
+        return Err(Error::InvalidShape {
+            shape: shape.to_vec(),
+            reason: "the shape must not contain empty axes",
+        });
+    }
+
+    Ok(())
+}
+
+/// check that `data_len` elements fill a non-empty shape
+pub(crate) fn check_data_length(data_len: usize, shape: &[usize]) -> Result<(), Error> {
+    check_non_empty_shape(shape)?;
+    check_same_length(shape.iter().product(), data_len)
+}
+
+/// calculate the number of elements of the shape and check that it doesn't overflow
+pub(crate) fn check_element_count(shape: &[usize]) -> Result<usize, Error> {
+    shape
+        .iter()
+        .try_fold(1usize, |count, s| count.checked_mul(*s))
+        .ok_or_else(|| Error::InvalidShape {
+            shape: shape.to_vec(),
+            reason: "the number of elements overflows usize",
+        })
+}
+
+/// check a shape read from serialized data and convert it to an array
+pub(crate) fn check_deserialized_shape<const D: usize>(
+    shape: &[usize],
+    data_len: usize,
+) -> Result<[usize; D], Error> {
+    if D == 0 {
+        return Err(Error::InvalidShape {
+            shape: shape.to_vec(),
+            reason: "the shape needs at least one dimension",
+        });
+    }
+
+    check_same_dimensions(D, shape.len())?;
+
+    let element_count = check_element_count(shape)?;
+
+    check_same_length(element_count, data_len)?;
+
+    let mut checked_shape = [0; D];
+    checked_shape.copy_from_slice(shape);
+
+    Ok(checked_shape)
+}
+
+/// panic with the message of the error
+#[track_caller]
+pub(crate) fn unwrap_or_panic<T>(result: Result<T, Error>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{}", error),
+    }
+}
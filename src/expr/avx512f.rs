@@ -0,0 +1,82 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+// Register kernels for the AVX-512 backend, a register holds 16 lanes of a padded row.
+
+use std::arch::x86_64::{
+    __m512, __mmask16, _mm512_abs_ps, _mm512_add_ps, _mm512_div_ps, _mm512_fmadd_ps,
+    _mm512_mask_blend_ps, _mm512_max_ps, _mm512_min_ps, _mm512_mul_ps, _mm512_set1_ps,
+    _mm512_sqrt_ps, _mm512_sub_ps,
+};
+
+pub type Register = __m512;
+pub type MaskRegister = __mmask16;
+
+pub(super) fn splat(value: f32) -> Register {
+    unsafe { _mm512_set1_ps(value) }
+}
+
+#[inline(always)]
+pub(super) fn add(a: Register, b: Register) -> Register {
+    unsafe { _mm512_add_ps(a, b) }
+}
+
+#[inline(always)]
+pub(super) fn sub(a: Register, b: Register) -> Register {
+    unsafe { _mm512_sub_ps(a, b) }
+}
+
+#[inline(always)]
+pub(super) fn mul(a: Register, b: Register) -> Register {
+    unsafe { _mm512_mul_ps(a, b) }
+}
+
+#[inline(always)]
+pub(super) fn div(a: Register, b: Register) -> Register {
+    unsafe { _mm512_div_ps(a, b) }
+}
+
+#[inline(always)]
+pub(super) fn max(a: Register, b: Register) -> Register {
+    unsafe { _mm512_max_ps(a, b) }
+}
+
+#[inline(always)]
+pub(super) fn min(a: Register, b: Register) -> Register {
+    unsafe { _mm512_min_ps(a, b) }
+}
+
+#[inline(always)]
+pub(super) fn sqrt(a: Register) -> Register {
+    unsafe { _mm512_sqrt_ps(a) }
+}
+
+#[inline(always)]
+pub(super) fn abs(a: Register) -> Register {
+    unsafe { _mm512_abs_ps(a) }
+}
+
+/// `a * b + c`
+#[inline(always)]
+pub(super) fn fmadd(a: Register, b: Register, c: Register) -> Register {
+    unsafe { _mm512_fmadd_ps(a, b, c) }
+}
+
+/// the lanes of `if_true` where `mask` is set, the lanes of `if_false` otherwise
+#[inline(always)]
+pub(super) fn select(mask: MaskRegister, if_true: Register, if_false: Register) -> Register {
+    unsafe { _mm512_mask_blend_ps(mask, if_false, if_true) }
+}
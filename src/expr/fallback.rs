@@ -0,0 +1,70 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+// Register kernels for the fallback backend, a register holds a single element.
+
+pub type Register = f32;
+pub type MaskRegister = bool;
+
+pub(super) fn splat(value: f32) -> Register {
+    value
+}
+
+pub(super) fn add(a: Register, b: Register) -> Register {
+    a + b
+}
+
+pub(super) fn sub(a: Register, b: Register) -> Register {
+    a - b
+}
+
+pub(super) fn mul(a: Register, b: Register) -> Register {
+    a * b
+}
+
+pub(super) fn div(a: Register, b: Register) -> Register {
+    a / b
+}
+
+pub(super) fn max(a: Register, b: Register) -> Register {
+    a.max(b)
+}
+
+pub(super) fn min(a: Register, b: Register) -> Register {
+    a.min(b)
+}
+
+pub(super) fn sqrt(a: Register) -> Register {
+    a.sqrt()
+}
+
+pub(super) fn abs(a: Register) -> Register {
+    a.abs()
+}
+
+/// `a * b + c`
+pub(super) fn fmadd(a: Register, b: Register, c: Register) -> Register {
+    a.mul_add(b, c)
+}
+
+/// `if_true` if `mask` is set, `if_false` otherwise
+pub(super) fn select(mask: MaskRegister, if_true: Register, if_false: Register) -> Register {
+    if mask {
+        if_true
+    } else {
+        if_false
+    }
+}
@@ -0,0 +1,434 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+// Lazy elementwise expressions. Every node of an expression is its own type, so evaluating an expression is
+// monomorphized into a single loop over the registers of the output, in which the whole tree is computed in
+// registers without intermediate arrays. Like in the eager operations, the padding lanes are computed as well.
+
+use std::marker::PhantomData;
+
+use crate::{
+    error::{check_same_shape, unwrap_or_panic},
+    Array, Mask,
+};
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+mod avx512f;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+use avx512f as kernels;
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+mod fallback;
+#[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+use fallback as kernels;
+
+use kernels::Register;
+
+/// the evaluation of a node one register at a time, it isn't exported so `ExpressionNode` is sealed
+pub trait Node<const D: usize> {
+    fn shape(&self) -> [usize; D];
+
+    /// the value of the register with the given index in the data of an array with the shape of this node
+    fn register(&self, index: usize) -> Register;
+}
+
+/// A node of a lazy expression. It can't be implemented outside of this crate, the nodes are created with the
+/// methods of [`Expression`].
+pub trait ExpressionNode<const D: usize>: Node<D> {}
+
+impl<const D: usize, N: Node<D>> ExpressionNode<D> for N {}
+
+/// Everything that can be used as an operand of an expression: arrays and other expressions.
+pub trait IntoExpression<const D: usize> {
+    type Node: ExpressionNode<D>;
+
+    fn into_expression(self) -> Expression<Self::Node, D>;
+}
+
+/// A lazily evaluated elementwise expression, built with [`Array::expr`].
+///
+/// Nothing is computed until the expression is evaluated with [`Expression::eval`], [`Expression::eval_into`] or
+/// [`Expression::eval_into_masked`], which evaluate the whole expression in a single pass over the operands.
+pub struct Expression<N, const D: usize> {
+    node: N,
+}
+
+trait BinaryOperation {
+    fn apply(a: Register, b: Register) -> Register;
+}
+
+trait UnaryOperation {
+    fn apply(a: Register) -> Register;
+}
+
+struct Add;
+struct Sub;
+struct Mul;
+struct Div;
+struct Max;
+struct Min;
+struct Sqrt;
+struct Square;
+struct Abs;
+
+impl BinaryOperation for Add {
+    #[inline(always)]
+    fn apply(a: Register, b: Register) -> Register {
+        kernels::add(a, b)
+    }
+}
+
+impl BinaryOperation for Sub {
+    #[inline(always)]
+    fn apply(a: Register, b: Register) -> Register {
+        kernels::sub(a, b)
+    }
+}
+
+impl BinaryOperation for Mul {
+    #[inline(always)]
+    fn apply(a: Register, b: Register) -> Register {
+        kernels::mul(a, b)
+    }
+}
+
+impl BinaryOperation for Div {
+    #[inline(always)]
+    fn apply(a: Register, b: Register) -> Register {
+        kernels::div(a, b)
+    }
+}
+
+impl BinaryOperation for Max {
+    #[inline(always)]
+    fn apply(a: Register, b: Register) -> Register {
+        kernels::max(a, b)
+    }
+}
+
+impl BinaryOperation for Min {
+    #[inline(always)]
+    fn apply(a: Register, b: Register) -> Register {
+        kernels::min(a, b)
+    }
+}
+
+impl UnaryOperation for Sqrt {
+    #[inline(always)]
+    fn apply(a: Register) -> Register {
+        kernels::sqrt(a)
+    }
+}
+
+impl UnaryOperation for Square {
+    #[inline(always)]
+    fn apply(a: Register) -> Register {
+        kernels::mul(a, a)
+    }
+}
+
+impl UnaryOperation for Abs {
+    #[inline(always)]
+    fn apply(a: Register) -> Register {
+        kernels::abs(a)
+    }
+}
+
+/// the registers of an array
+pub struct Leaf<'a, const D: usize> {
+    array: &'a Array<D>,
+}
+
+/// an operation on two expressions
+struct Binary<A, B, O> {
+    a: A,
+    b: B,
+    operation: PhantomData<O>,
+}
+
+/// an operation on an expression and a scalar
+struct BinaryScalar<A, O> {
+    a: A,
+    scalar: Register,
+    operation: PhantomData<O>,
+}
+
+/// an operation on a single expression
+struct Unary<A, O> {
+    a: A,
+    operation: PhantomData<O>,
+}
+
+/// `c + a * b`
+struct Fmadd<C, A, B> {
+    c: C,
+    a: A,
+    b: B,
+}
+
+impl<'a, const D: usize> Node<D> for Leaf<'a, D> {
+    fn shape(&self) -> [usize; D] {
+        self.array.shape
+    }
+
+    #[inline(always)]
+    fn register(&self, index: usize) -> Register {
+        self.array.data[index]
+    }
+}
+
+impl<const D: usize, A: ExpressionNode<D>, B: ExpressionNode<D>, O: BinaryOperation> Node<D>
+    for Binary<A, B, O>
+{
+    fn shape(&self) -> [usize; D] {
+        self.a.shape()
+    }
+
+    #[inline(always)]
+    fn register(&self, index: usize) -> Register {
+        O::apply(self.a.register(index), self.b.register(index))
+    }
+}
+
+impl<const D: usize, A: ExpressionNode<D>, O: BinaryOperation> Node<D> for BinaryScalar<A, O> {
+    fn shape(&self) -> [usize; D] {
+        self.a.shape()
+    }
+
+    #[inline(always)]
+    fn register(&self, index: usize) -> Register {
+        O::apply(self.a.register(index), self.scalar)
+    }
+}
+
+impl<const D: usize, A: ExpressionNode<D>, O: UnaryOperation> Node<D> for Unary<A, O> {
+    fn shape(&self) -> [usize; D] {
+        self.a.shape()
+    }
+
+    #[inline(always)]
+    fn register(&self, index: usize) -> Register {
+        O::apply(self.a.register(index))
+    }
+}
+
+impl<const D: usize, C: ExpressionNode<D>, A: ExpressionNode<D>, B: ExpressionNode<D>> Node<D>
+    for Fmadd<C, A, B>
+{
+    fn shape(&self) -> [usize; D] {
+        self.c.shape()
+    }
+
+    #[inline(always)]
+    fn register(&self, index: usize) -> Register {
+        kernels::fmadd(
+            self.a.register(index),
+            self.b.register(index),
+            self.c.register(index),
+        )
+    }
+}
+
+impl<'a, const D: usize> IntoExpression<D> for &'a Array<D> {
+    type Node = Leaf<'a, D>;
+
+    fn into_expression(self) -> Expression<Self::Node, D> {
+        Expression {
+            node: Leaf { array: self },
+        }
+    }
+}
+
+impl<const D: usize, N: ExpressionNode<D>> IntoExpression<D> for Expression<N, D> {
+    type Node = N;
+
+    fn into_expression(self) -> Expression<Self::Node, D> {
+        self
+    }
+}
+
+impl<const D: usize> Array<D> {
+    /// Starts a lazy expression with this array as its first operand.
+    pub fn expr(&self) -> Expression<impl ExpressionNode<D> + '_, D> {
+        self.into_expression()
+    }
+}
+
+// the methods are named like the eager operations of `Array`
+#[allow(clippy::should_implement_trait)]
+impl<const D: usize, N: ExpressionNode<D>> Expression<N, D> {
+    pub fn get_shape(&self) -> [usize; D] {
+        self.node.shape()
+    }
+
+    #[track_caller]
+    fn binary<O: BinaryOperation, B: IntoExpression<D>>(
+        self,
+        other: B,
+    ) -> Expression<Binary<N, B::Node, O>, D> {
+        let other = other.into_expression();
+        unwrap_or_panic(check_same_shape(&self.node.shape(), &other.node.shape()));
+
+        Expression {
+            node: Binary {
+                a: self.node,
+                b: other.node,
+                operation: PhantomData,
+            },
+        }
+    }
+
+    fn binary_scalar<O: BinaryOperation>(self, scalar: f32) -> Expression<BinaryScalar<N, O>, D> {
+        Expression {
+            node: BinaryScalar {
+                a: self.node,
+                scalar: kernels::splat(scalar),
+                operation: PhantomData,
+            },
+        }
+    }
+
+    fn unary<O: UnaryOperation>(self) -> Expression<Unary<N, O>, D> {
+        Expression {
+            node: Unary {
+                a: self.node,
+                operation: PhantomData,
+            },
+        }
+    }
+
+    #[track_caller]
+    pub fn add<B: IntoExpression<D>>(self, other: B) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary::<Add, B>(other)
+    }
+
+    #[track_caller]
+    pub fn sub<B: IntoExpression<D>>(self, other: B) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary::<Sub, B>(other)
+    }
+
+    #[track_caller]
+    pub fn mul<B: IntoExpression<D>>(self, other: B) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary::<Mul, B>(other)
+    }
+
+    #[track_caller]
+    pub fn div<B: IntoExpression<D>>(self, other: B) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary::<Div, B>(other)
+    }
+
+    #[track_caller]
+    pub fn max<B: IntoExpression<D>>(self, other: B) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary::<Max, B>(other)
+    }
+
+    #[track_caller]
+    pub fn min<B: IntoExpression<D>>(self, other: B) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary::<Min, B>(other)
+    }
+
+    pub fn add_scalar(self, scalar: f32) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary_scalar::<Add>(scalar)
+    }
+
+    pub fn sub_scalar(self, scalar: f32) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary_scalar::<Sub>(scalar)
+    }
+
+    pub fn mul_scalar(self, scalar: f32) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary_scalar::<Mul>(scalar)
+    }
+
+    pub fn div_scalar(self, scalar: f32) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary_scalar::<Div>(scalar)
+    }
+
+    pub fn max_scalar(self, scalar: f32) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary_scalar::<Max>(scalar)
+    }
+
+    pub fn min_scalar(self, scalar: f32) -> Expression<impl ExpressionNode<D>, D> {
+        self.binary_scalar::<Min>(scalar)
+    }
+
+    /// `self + a * b`, with a single rounding like [`Array::fmadd`]
+    #[track_caller]
+    pub fn fmadd<A: IntoExpression<D>, B: IntoExpression<D>>(
+        self,
+        a: A,
+        b: B,
+    ) -> Expression<impl ExpressionNode<D>, D> {
+        let a = a.into_expression();
+        let b = b.into_expression();
+        unwrap_or_panic(check_same_shape(&self.node.shape(), &a.node.shape()));
+        unwrap_or_panic(check_same_shape(&self.node.shape(), &b.node.shape()));
+
+        Expression {
+            node: Fmadd {
+                c: self.node,
+                a: a.node,
+                b: b.node,
+            },
+        }
+    }
+
+    pub fn sqrt(self) -> Expression<impl ExpressionNode<D>, D> {
+        self.unary::<Sqrt>()
+    }
+
+    pub fn square(self) -> Expression<impl ExpressionNode<D>, D> {
+        self.unary::<Square>()
+    }
+
+    pub fn abs(self) -> Expression<impl ExpressionNode<D>, D> {
+        self.unary::<Abs>()
+    }
+
+    /// Evaluates the expression into a new array.
+    pub fn eval(self) -> Array<D> {
+        let mut output = Array::zeros(&self.node.shape());
+        self.eval_into(&mut output);
+
+        output
+    }
+
+    /// Evaluates the expression into `output`, which has to have the shape of the expression.
+    #[track_caller]
+    pub fn eval_into(self, output: &mut Array<D>) {
+        unwrap_or_panic(check_same_shape(&self.node.shape(), &output.shape));
+
+        for (index, register) in output.data.iter_mut().enumerate() {
+            *register = self.node.register(index);
+        }
+    }
+
+    /// Evaluates the expression into the elements of `output` where `mask` is set, the other elements are left
+    /// unchanged.
+    #[track_caller]
+    pub fn eval_into_masked(self, output: &mut Array<D>, mask: &Mask<D>) {
+        unwrap_or_panic(check_same_shape(&self.node.shape(), &output.shape));
+        unwrap_or_panic(check_same_shape(&output.shape, mask.get_shape()));
+
+        for (index, (register, mask)) in output
+            .data
+            .iter_mut()
+            .zip(mask.get_masks().iter())
+            .enumerate()
+        {
+            *register = kernels::select(*mask, self.node.register(index), *register);
+        }
+    }
+}
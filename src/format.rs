@@ -0,0 +1,187 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+// `Debug` and `Display` for `Array` and `Mask`.
+//
+// `Debug` shows the shape and all elements in row-major order. `Display` shows the elements nested like NumPy,
+// all elements are right-aligned to the same width and if there are more than `THRESHOLD` elements, only the first
+// and last `EDGE_ITEMS` indices of long axes are shown.
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use crate::{Array, Mask};
+
+const THRESHOLD: usize = 1000;
+const EDGE_ITEMS: usize = 3;
+
+/// the indices of an axis that are shown, `None` stands for the omitted indices
+fn shown_indices(length: usize, truncate: bool) -> Vec<Option<usize>> {
+    if truncate && length > 2 * EDGE_ITEMS {
+        (0..EDGE_ITEMS)
+            .map(Some)
+            .chain([None])
+            .chain((length - EDGE_ITEMS..length).map(Some))
+            .collect()
+    } else {
+        (0..length).map(Some).collect()
+    }
+}
+
+struct Nested<'a> {
+    shape: &'a [usize],
+    axes: Vec<Vec<Option<usize>>>,
+    elements: Vec<String>,
+    width: usize,
+}
+
+impl<'a> Nested<'a> {
+    fn new(shape: &'a [usize], format_element: impl Fn(usize) -> String) -> Self {
+        let truncate = shape.iter().product::<usize>() > THRESHOLD;
+        let axes: Vec<_> = shape.iter().map(|s| shown_indices(*s, truncate)).collect();
+
+        // only the shown elements are formatted, they are stored in the order they are written
+        let mut flat_indices = vec![0];
+
+        for (axis, indices) in axes.iter().enumerate() {
+            flat_indices = flat_indices
+                .iter()
+                .flat_map(|prefix| {
+                    indices
+                        .iter()
+                        .flatten()
+                        .map(move |i| prefix * shape[axis] + i)
+                })
+                .collect();
+        }
+
+        let elements: Vec<String> = flat_indices.into_iter().map(format_element).collect();
+        let width = elements
+            .iter()
+            .map(|e| e.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            shape,
+            axes,
+            elements,
+            width,
+        }
+    }
+
+    fn write(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_axis(f, 0, &mut 0)
+    }
+
+    fn write_axis(&self, f: &mut Formatter<'_>, axis: usize, next: &mut usize) -> fmt::Result {
+        let last_axis = axis + 1 == self.shape.len();
+
+        write!(f, "[")?;
+
+        for (i, index) in self.axes[axis].iter().enumerate() {
+            if i > 0 {
+                if last_axis {
+                    write!(f, " ")?;
+                } else {
+                    // one newline between rows, an additional one for each higher axis
+                    let newlines = "\n".repeat(self.shape.len() - axis - 1);
+                    write!(f, "{}{:indent$}", newlines, "", indent = axis + 1)?;
+                }
+            }
+
+            match index {
+                Some(_) if last_axis => {
+                    write!(f, "{:>width$}", self.elements[*next], width = self.width)?;
+                    *next += 1;
+                }
+                Some(_) => self.write_axis(f, axis + 1, next)?,
+                None => write!(f, "...")?,
+            }
+        }
+
+        write!(f, "]")
+    }
+}
+
+impl<const D: usize> Debug for Array<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Array")
+            .field("shape", &self.shape)
+            .field("elements", &self.to_elements())
+            .finish()
+    }
+}
+
+/// NumPy-like output, the precision is used for all elements, e.g. `{:.3}`
+impl<const D: usize> Display for Array<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let elements = self.to_elements();
+        let nested = Nested::new(&self.shape, |i| match f.precision() {
+            Some(precision) => format!("{:.*}", precision, elements[i]),
+            None => format!("{}", elements[i]),
+        });
+
+        nested.write(f)
+    }
+}
+
+/// the elements of the mask in row-major order
+fn mask_elements<const D: usize>(mask: &Mask<D>) -> Vec<bool> {
+    let column_count = mask.get_shape()[D - 1];
+    let words = mask.to_words();
+    let mut elements = Vec::with_capacity(mask.get_shape().iter().product());
+
+    if column_count > 0 {
+        for row in words.chunks(column_count.div_ceil(16)) {
+            for column in 0..column_count {
+                elements.push(row[column / 16] & (1 << (column % 16)) != 0);
+            }
+        }
+    }
+
+    elements
+}
+
+impl<const D: usize> Debug for Mask<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mask")
+            .field("shape", self.get_shape())
+            .field("elements", &mask_elements(self))
+            .finish()
+    }
+}
+
+/// NumPy-like output with `1` and `0` for the elements, or `T` and `F` with `{:#}`
+impl<const D: usize> Display for Mask<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let elements = mask_elements(self);
+        let (true_symbol, false_symbol) = if f.alternate() {
+            ("T", "F")
+        } else {
+            ("1", "0")
+        };
+        let nested = Nested::new(self.get_shape(), |i| {
+            if elements[i] {
+                true_symbol
+            } else {
+                false_symbol
+            }
+            .to_string()
+        });
+
+        nested.write(f)
+    }
+}
@@ -18,12 +18,43 @@ limitations under the License.
 */
 
 mod array;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod binary;
+mod csv;
+mod error;
+mod expr;
+mod format;
+#[cfg(feature = "mmap")]
+mod mapped;
 mod mask;
+mod npy;
+#[cfg(feature = "npz")]
+mod npz;
+#[cfg(feature = "safetensors")]
+mod safetensors;
+mod view;
+mod workspace;
 
 #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
 use std::arch::x86_64::__m512;
 
-pub use mask::Mask;
+pub use array::{ArrayIndexedIter, ArrayIntoIter, ArrayIter, ArrayIterMut, Difference, Tolerance};
+pub use csv::{CsvReadOptions, CsvWriteOptions, EmptyCellPolicy, FloatFormat};
+pub use error::Error;
+pub use expr::{Expression, ExpressionNode, IntoExpression};
+#[cfg(feature = "mmap")]
+pub use mapped::MappedArray;
+pub use mask::{Mask, MaskIndexedIter, MaskIntoIter, MaskIter};
+#[cfg(feature = "npz")]
+pub use npz::{NpzReader, NpzWriter};
+#[cfg(feature = "safetensors")]
+pub use safetensors::{
+    read_safetensors, read_safetensors_tensors, write_safetensors, write_safetensors_tensors,
+    Tensor,
+};
+pub use view::{ArrayView, ArrayViewMut};
+pub use workspace::Workspace;
 
 #[derive(Clone)]
 pub struct Array<const D: usize> {
@@ -0,0 +1,141 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use memmap2::Mmap;
+
+use crate::{
+    binary::{invalid_data, padded_payload_offset, read_header, write_header, Kind},
+    Array, ArrayView, Error,
+};
+
+/// A read-only array backed by a memory-mapped file written by `Array::write_mapped`
+///
+/// The elements can be read through a view. With AVX-512 the view uses the elements directly from the file,
+/// so the array can be larger than the available memory. The fallback implementation copies the elements into
+/// memory.
+pub struct MappedArray<const D: usize> {
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+    mmap: Mmap,
+    #[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+    array: Array<D>,
+    shape: [usize; D],
+}
+
+impl<const D: usize> MappedArray<D> {
+    /// Map the file at `path`, see `map`
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated as long as the returned array exists.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::map(&File::open(path)?)
+    }
+
+    /// Map a file written by `Array::write_mapped`, the number of dimensions must be `D`
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated as long as the returned array exists.
+    pub unsafe fn map(file: &File) -> io::Result<Self> {
+        let mmap = Mmap::map(file)?;
+        let shape = read_header::<D>(&mut &mmap[..], Kind::PaddedArray)?;
+
+        if mmap.len() < padded_payload_offset(D) {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the file ends in the padding of the header",
+            ));
+        }
+
+        let row_count: usize = shape[..D - 1].iter().product();
+        let payload_length = row_count
+            .checked_mul(shape[D - 1].div_ceil(16))
+            .and_then(|r| r.checked_mul(64))
+            .ok_or_else(|| {
+                invalid_data(Error::InvalidFormat {
+                    reason: "the payload length overflows usize",
+                })
+            })?;
+        let payload = &mmap[padded_payload_offset(D)..];
+
+        if payload.len() < payload_length {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the payload is shorter than its shape",
+            ));
+        }
+
+        if payload.len() > payload_length {
+            return Err(invalid_data(Error::InvalidFormat {
+                reason: "the payload is longer than its shape",
+            }));
+        }
+
+        // the mapping starts at a page boundary and the payload at a multiple of 64 bytes,
+        // so the registers are aligned
+        #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+        return Ok(Self { mmap, shape });
+
+        #[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+        Ok(Self {
+            array: Array::from_mapped(payload, shape),
+            shape,
+        })
+    }
+
+    pub fn get_shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// A view of all elements, it supports the operations that only read the array
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+    pub fn view(&self) -> ArrayView<'_, D> {
+        // `map` checked the length, the clamping only keeps this from panicking
+        let payload = &self.mmap[padded_payload_offset(D).min(self.mmap.len())..];
+
+        ArrayView::from_padded_elements(
+            Array::<D>::mapped_elements(payload),
+            Array::<D>::padded_row_length(self.shape[D - 1]),
+            self.shape,
+        )
+    }
+
+    /// A view of all elements, it supports the operations that only read the array
+    #[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+    pub fn view(&self) -> ArrayView<'_, D> {
+        self.array.as_view()
+    }
+
+    /// Copy the elements into a new array
+    pub fn to_array(&self) -> Array<D> {
+        self.view().to_array()
+    }
+}
+
+impl<const D: usize> Array<D> {
+    /// Write the array in a padded binary format, which can be memory-mapped with `MappedArray`
+    pub fn write_mapped(&self, mut writer: impl Write) -> io::Result<()> {
+        write_header(&mut writer, Kind::PaddedArray, &self.shape)?;
+        writer.write_all(&vec![0; padded_payload_offset(D) - (12 + 8 * D)])?;
+        self.write_padded_elements(&mut writer)
+    }
+}
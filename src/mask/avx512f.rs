@@ -18,9 +18,21 @@ use serde::{
     ser::{Serialize, SerializeSeq, SerializeStruct},
     Deserialize,
 };
-use std::{arch::x86_64::__mmask16, slice::IterMut};
+use std::{
+    arch::x86_64::__mmask16,
+    borrow::Cow,
+    io::{self, Read, Write},
+    slice::IterMut,
+};
 
-use crate::Mask;
+use crate::{
+    binary::{
+        read_bools, read_header, read_mask_payload, write_header, write_mask_payload, Kind,
+        READ_CHUNK_SIZE,
+    },
+    error::{check_data_length, check_deserialized_shape, check_index, unwrap_or_panic},
+    Mask,
+};
 
 impl From<Mask<1>> for Vec<bool> {
     fn from(value: Mask<1>) -> Self {
@@ -95,6 +107,48 @@ impl<'a, const D: usize> Serialize for DataSerializeWrapper<'a, D> {
     }
 }
 
+impl<const D: usize> PartialEq for Mask<D> {
+    /// compares the shapes and the elements, the unused bits at the end of the rows are ignored
+    fn eq(&self, other: &Self) -> bool {
+        if self.shape != other.shape {
+            return false;
+        }
+
+        let column_count = *self.shape.last().unwrap();
+        let masks_per_row = column_count.div_ceil(16);
+
+        if masks_per_row == 0 {
+            return true;
+        }
+
+        let last_bits: __mmask16 = if column_count.is_multiple_of(16) {
+            0xFFFF
+        } else {
+            (1 << (column_count % 16)) - 1
+        };
+
+        for (row, other_row) in self
+            .masks
+            .chunks(masks_per_row)
+            .zip(other.masks.chunks(masks_per_row))
+        {
+            for (i, (a, b)) in row.iter().zip(other_row.iter()).enumerate() {
+                let bits = if i + 1 == masks_per_row {
+                    last_bits
+                } else {
+                    0xFFFF
+                };
+
+                if (a ^ b) & bits != 0 {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
 impl<const D: usize> Serialize for Mask<D> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -122,11 +176,16 @@ impl<'de, const D: usize> Deserialize<'de> for Mask<D> {
         De: serde::Deserializer<'de>,
     {
         let proxy = ArrayDeserializerProxy::deserialize(deserializer)?;
-        assert_eq!(proxy.shape.len(), D);
+        let shape: [usize; D] = check_deserialized_shape(&proxy.shape, proxy.masks.len())
+            .map_err(serde::de::Error::custom)?;
 
-        let mut shape = [0; D];
-        shape.copy_from_slice(&proxy.shape[..D]);
+        Ok(Mask::from_elements(proxy.masks, shape))
+    }
+}
 
+impl<const D: usize> Mask<D> {
+    /// create a mask from elements in row-major order, the number of elements must match the shape
+    pub(crate) fn from_elements(elements: Vec<bool>, shape: [usize; D]) -> Self {
         let registers_per_row = shape.last().unwrap().div_ceil(16);
         let mut register_count = registers_per_row;
 
@@ -145,7 +204,7 @@ impl<'de, const D: usize> Deserialize<'de> for Mask<D> {
                 limit = ((shape.last().unwrap() - 1) % 16) + 1;
             }
 
-            let content = &proxy.masks[element_index..element_index + limit];
+            let content = &elements[element_index..element_index + limit];
             element_index += limit;
 
             let mut new_mask = 0u16;
@@ -159,12 +218,150 @@ impl<'de, const D: usize> Deserialize<'de> for Mask<D> {
             masks[register_index] = new_mask;
         }
 
+        Mask { masks, shape }
+    }
+
+    /// read elements in row-major order with one byte per element directly into the masks
+    pub(crate) fn read_elements(reader: &mut impl Read, shape: [usize; D]) -> io::Result<Self> {
+        let column_count = *shape.last().unwrap();
+        let row_count: usize = shape[..D - 1].iter().product();
+        let mut masks = Vec::new();
+        let mut buffer = [false; READ_CHUNK_SIZE];
+
+        if column_count == 0 {
+            return Ok(Mask { masks, shape });
+        }
+
+        for _ in 0..row_count {
+            for start in (0..column_count).step_by(READ_CHUNK_SIZE) {
+                let chunk = &mut buffer[..(column_count - start).min(READ_CHUNK_SIZE)];
+                read_bools(reader, chunk)?;
+
+                for elements in chunk.chunks(16) {
+                    let mut mask = 0u16;
+
+                    for (i, e) in elements.iter().enumerate() {
+                        mask |= (*e as u16) << i;
+                    }
+
+                    masks.push(mask);
+                }
+            }
+        }
+
         Ok(Mask { masks, shape })
     }
+
+    /// write the elements in row-major order with one byte per element
+    pub(crate) fn write_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        let column_count = *self.shape.last().unwrap();
+        let masks_per_row = column_count.div_ceil(16);
+
+        if masks_per_row == 0 {
+            return Ok(());
+        }
+
+        let mut row_elements = Vec::with_capacity(masks_per_row * 16);
+
+        for row in self.masks.chunks(masks_per_row) {
+            row_elements.clear();
+
+            for mask in row {
+                for i in 0..16 {
+                    row_elements.push(((mask >> i) & 1) as u8);
+                }
+            }
+
+            writer.write_all(&row_elements[..column_count])?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the mask in a compact binary format with 16 packed bits per word, which can be read with `read_from`
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        write_header(&mut writer, Kind::Mask, &self.shape)?;
+        write_mask_payload(&mut writer, &self.masks)
+    }
+
+    /// Read a mask in the binary format written by `write_to`
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let shape = read_header(&mut reader, Kind::Mask)?;
+        let words = read_mask_payload(&mut reader, &shape)?;
+
+        Ok(Mask::from_words(words, shape))
+    }
+
+    /// create a mask from 16-bit words of packed bits, each row starts with a new word and unused bits must be zero
+    pub(crate) fn from_words(words: Vec<u16>, shape: [usize; D]) -> Self {
+        Mask {
+            masks: words,
+            shape,
+        }
+    }
+
+    /// the 16-bit words of packed bits, each row starts with a new word and unused bits are zero
+    pub(crate) fn to_words(&self) -> Vec<u16> {
+        self.masks.clone()
+    }
+
+    /// the words of `to_words` without copying them
+    pub(crate) fn words(&self) -> Cow<'_, [u16]> {
+        Cow::Borrowed(&self.masks)
+    }
+
+    /// the element in `column` of a row, the rows are counted over all axes except the last one
+    pub(crate) fn element(&self, row: usize, column: usize) -> bool {
+        let masks_per_row = self.shape.last().unwrap().div_ceil(16);
+
+        (self.masks[row * masks_per_row + column / 16] >> (column % 16)) & 1 != 0
+    }
+
+    /// the number of bits of a row including the unused bits, a whole number of masks
+    pub(crate) fn padded_row_length(column_count: usize) -> usize {
+        column_count.div_ceil(16) * 16
+    }
+
+    /// copy the bits into the rows of a mask with a different number of columns
+    pub(crate) fn repack<const E: usize>(&self, shape: [usize; E]) -> Mask<E> {
+        let old_column_count = *self.shape.last().unwrap();
+        let new_column_count = shape[E - 1];
+
+        // without columns there are no bits to copy
+        if old_column_count == 0 || new_column_count == 0 {
+            return Mask::zeros(&shape);
+        }
+
+        let old_masks_per_row = old_column_count.div_ceil(16);
+        let new_masks_per_row = new_column_count.div_ceil(16);
+        let row_count = self.number_of_elements() / new_column_count;
+        let mut masks = vec![0; row_count * new_masks_per_row];
+        let mut new_row = 0;
+        let mut new_column = 0;
+
+        for old_row in 0..self.number_of_elements() / old_column_count {
+            for old_column in 0..old_column_count {
+                let mask = self.masks[old_row * old_masks_per_row + old_column / 16];
+                let bit = (mask >> (old_column % 16)) & 1;
+                masks[new_row * new_masks_per_row + new_column / 16] |= bit << (new_column % 16);
+
+                new_column += 1;
+
+                if new_column == new_column_count {
+                    new_row += 1;
+                    new_column = 0;
+                }
+            }
+        }
+
+        Mask { masks, shape }
+    }
 }
 
 impl Mask<1> {
     pub fn get(&self, index: usize) -> bool {
+        unwrap_or_panic(self.check_index(index));
+
         let mask = self.masks[index / 16];
 
         mask & (1 << (index % 16)) > 0
@@ -172,15 +369,7 @@ impl Mask<1> {
 
     /// Copy the mask `k`-times into `output`
     pub fn tile_in_place(&self, k: usize, output: &mut Mask<1>) {
-        assert!(
-            self.shape[0] % 16 == 0,
-            "the number of elements needs to be a multiple of 16"
-        );
-        assert_eq!(
-            self.shape[0] * k,
-            output.shape[0],
-            "the number of elements in output must be k-times more than the elements in this mask"
-        );
+        unwrap_or_panic(self.check_tile(k, output));
 
         let self_masks = self.masks.len();
 
@@ -191,18 +380,7 @@ impl Mask<1> {
 
     /// Repeat each element of the mask `k`-times and store the result in `output`
     pub fn repeat_in_place(&self, k: usize, output: &mut Mask<1>) {
-        let self_len = self.shape[0];
-
-        assert!(
-            self_len % 16 == 0,
-            "the number of elements needs to be a multiple of 16"
-        );
-        assert!(k % 16 == 0, "k needs to be a multiple of 16");
-        assert_eq!(
-            self_len * k,
-            output.shape[0],
-            "the number of elements in output must be k-times more than the elements in this array"
-        );
+        unwrap_or_panic(self.check_repeat(k, output));
 
         let mut index = 0;
 
@@ -221,8 +399,7 @@ impl Mask<1> {
     }
 
     pub fn repeat_as_row_in_place(&self, k: usize, output: &mut Mask<2>) {
-        assert_eq!(output.shape[0], k);
-        assert_eq!(output.shape[1], self.shape[0]);
+        unwrap_or_panic(self.check_repeat_as_row(k, output));
 
         let masks_per_row = self.shape[0].div_ceil(16);
 
@@ -232,8 +409,7 @@ impl Mask<1> {
     }
 
     pub fn repeat_as_column_in_place(&self, k: usize, output: &mut Mask<2>) {
-        assert_eq!(output.shape[0], self.shape[0]);
-        assert_eq!(output.shape[1], k);
+        unwrap_or_panic(self.check_repeat_as_column(k, output));
 
         let masks_per_row = k.div_ceil(16);
 
@@ -251,9 +427,7 @@ impl Mask<1> {
 
 impl Mask<2> {
     pub fn from_vec(data: &Vec<bool>, shape: [usize; 2]) -> Self {
-        assert!(shape[0] > 0);
-        assert!(shape[1] > 0);
-        assert_eq!(data.len(), shape[0] * shape[1]);
+        unwrap_or_panic(check_data_length(data.len(), &shape));
 
         let row_count = shape[0];
         let column_count = shape[1];
@@ -287,10 +461,64 @@ impl Mask<2> {
     }
 
     pub fn get(&self, row: usize, column: usize) -> bool {
+        unwrap_or_panic(self.check_index(row, column));
+
         let masks_per_row = self.shape[1].div_ceil(16);
 
         self.masks[row * masks_per_row + (column / 16)] & (1 << (column % 16)) > 0
     }
+
+    /// Copy a row into a new mask, the masks of the row are copied as a whole
+    pub fn get_row(&self, row: usize) -> Mask<1> {
+        unwrap_or_panic(check_index(0, row, self.shape[0]));
+
+        let masks_per_row = self.shape[1].div_ceil(16);
+
+        Mask {
+            masks: self.masks[row * masks_per_row..(row + 1) * masks_per_row].to_vec(),
+            shape: [self.shape[1]],
+        }
+    }
+
+    /// Overwrite a row with `values`, the masks of the row are copied as a whole
+    pub fn copy_row_from(&mut self, row: usize, values: &Mask<1>) {
+        unwrap_or_panic(self.check_row(row, values));
+
+        let masks_per_row = self.shape[1].div_ceil(16);
+
+        self.masks[row * masks_per_row..(row + 1) * masks_per_row].copy_from_slice(&values.masks);
+    }
+
+    /// Copy a column into a new mask
+    pub fn column(&self, column: usize) -> Mask<1> {
+        unwrap_or_panic(check_index(1, column, self.shape[1]));
+
+        let masks_per_row = self.shape[1].div_ceil(16);
+        let mut masks = vec![0; self.shape[0].div_ceil(16)];
+
+        for row in 0..self.shape[0] {
+            let bit = (self.masks[row * masks_per_row + column / 16] >> (column % 16)) & 1;
+            masks[row / 16] |= bit << (row % 16);
+        }
+
+        Mask {
+            masks,
+            shape: [self.shape[0]],
+        }
+    }
+
+    /// Overwrite a column with `values`
+    pub fn set_column(&mut self, column: usize, values: &Mask<1>) {
+        unwrap_or_panic(self.check_column(column, values));
+
+        let masks_per_row = self.shape[1].div_ceil(16);
+
+        for row in 0..self.shape[0] {
+            let bit = (values.masks[row / 16] >> (row % 16)) & 1;
+            let mask = &mut self.masks[row * masks_per_row + column / 16];
+            *mask = (*mask & !(1 << (column % 16))) | (bit << (column % 16));
+        }
+    }
 }
 
 /// This struct is used to create a mutable iterator over the masks and automatically zero out unused elements afterwards.
@@ -326,6 +554,26 @@ impl<const D: usize> Mask<D> {
         }
     }
 
+    /// the number of masks of a mask with the given shape
+    pub(crate) fn buffer_length(shape: &[usize; D]) -> usize {
+        shape[..D - 1].iter().product::<usize>() * shape[D - 1].div_ceil(16)
+    }
+
+    /// a mask with all elements false, that reuses the allocation of `masks`
+    pub(crate) fn from_buffer(shape: &[usize; D], mut masks: Vec<__mmask16>) -> Self {
+        masks.clear();
+        masks.resize(Self::buffer_length(shape), 0);
+
+        Self {
+            masks,
+            shape: *shape,
+        }
+    }
+
+    pub(crate) fn into_buffer(self) -> Vec<__mmask16> {
+        self.masks
+    }
+
     pub fn new_from_data(shape: [usize; D], masks: Vec<__mmask16>) -> Mask<D> {
         let masks_per_row = shape.last().unwrap().div_ceil(16);
         let mut n_masks = masks_per_row;
@@ -350,7 +598,7 @@ impl<const D: usize> Mask<D> {
     }
 
     pub fn copy(&mut self, other: &Mask<D>) {
-        assert_eq!(self.shape, other.shape);
+        unwrap_or_panic(self.check_same_shape(other));
 
         for (old_mask, new_mask) in self.masks.iter_mut().zip(other.masks.iter()) {
             *old_mask = *new_mask;
@@ -361,6 +609,17 @@ impl<const D: usize> Mask<D> {
         &self.shape
     }
 
+    /// number of elements that are set to 1
+    pub fn number_of_true_elements(&self) -> usize {
+        let mut count = 0;
+
+        for m in self.masks.iter() {
+            count += m.count_ones() as usize;
+        }
+
+        count
+    }
+
     pub(crate) fn get_masks(&self) -> &Vec<__mmask16> {
         &self.masks
     }
@@ -435,7 +694,7 @@ impl<const D: usize> Mask<D> {
     }
 
     pub fn and_in_place(&mut self, other: &Self) {
-        assert_eq!(self.shape, other.shape);
+        unwrap_or_panic(self.check_same_shape(other));
 
         for (m1, m2) in self.masks.iter_mut().zip(other.masks.iter()) {
             *m1 = *m1 & *m2;
@@ -450,7 +709,7 @@ impl<const D: usize> Mask<D> {
     }
 
     pub fn or_in_place(&mut self, other: &Self) {
-        assert_eq!(self.shape, other.shape);
+        unwrap_or_panic(self.check_same_shape(other));
 
         for (m1, m2) in self.masks.iter_mut().zip(other.masks.iter()) {
             *m1 = *m1 | *m2;
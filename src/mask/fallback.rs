@@ -14,14 +14,25 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::slice::IterMut;
+use std::{
+    borrow::Cow,
+    io::{self, Read, Write},
+    slice::IterMut,
+};
 
 use serde::{
     ser::{Serialize, SerializeStruct},
     Deserialize,
 };
 
-use crate::Mask;
+use crate::{
+    binary::{
+        mask_word_count, read_bools, read_header, read_mask_payload, write_header,
+        write_mask_payload, Kind, READ_CHUNK_SIZE,
+    },
+    error::{check_data_length, check_deserialized_shape, check_index, unwrap_or_panic},
+    Mask,
+};
 
 impl From<Mask<1>> for Vec<bool> {
     fn from(value: Mask<1>) -> Self {
@@ -39,6 +50,13 @@ impl From<Vec<bool>> for Mask<1> {
     }
 }
 
+impl<const D: usize> PartialEq for Mask<D> {
+    /// compares the shapes and the elements
+    fn eq(&self, other: &Self) -> bool {
+        self.shape == other.shape && self.masks == other.masks
+    }
+}
+
 impl<const D: usize> Serialize for Mask<D> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -66,10 +84,8 @@ impl<'de, const D: usize> Deserialize<'de> for Mask<D> {
         De: serde::Deserializer<'de>,
     {
         let proxy = ArrayDeserializerProxy::deserialize(deserializer)?;
-        assert_eq!(proxy.shape.len(), D);
-
-        let mut shape = [0; D];
-        shape.copy_from_slice(&proxy.shape[..D]);
+        let shape: [usize; D] = check_deserialized_shape(&proxy.shape, proxy.masks.len())
+            .map_err(serde::de::Error::custom)?;
 
         Ok(Mask {
             masks: proxy.masks,
@@ -78,18 +94,116 @@ impl<'de, const D: usize> Deserialize<'de> for Mask<D> {
     }
 }
 
+impl<const D: usize> Mask<D> {
+    /// read elements in row-major order with one byte per element
+    pub(crate) fn read_elements(reader: &mut impl Read, shape: [usize; D]) -> io::Result<Self> {
+        let element_count: usize = shape.iter().product();
+        let mut masks = Vec::new();
+
+        // the data is read in chunks, so that a corrupted shape can't exhaust the memory
+        while masks.len() < element_count {
+            let start = masks.len();
+            masks.resize(start + (element_count - start).min(READ_CHUNK_SIZE), false);
+            read_bools(reader, &mut masks[start..])?;
+        }
+
+        Ok(Mask { masks, shape })
+    }
+
+    /// write the elements in row-major order with one byte per element
+    pub(crate) fn write_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        let bytes: Vec<u8> = self.masks.iter().map(|m| *m as u8).collect();
+
+        writer.write_all(&bytes)
+    }
+
+    /// Write the mask in a compact binary format with 16 packed bits per word, which can be read with `read_from`
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        write_header(&mut writer, Kind::Mask, &self.shape)?;
+        write_mask_payload(&mut writer, &self.to_words())
+    }
+
+    /// Read a mask in the binary format written by `write_to`
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let shape: [usize; D] = read_header(&mut reader, Kind::Mask)?;
+        let words = read_mask_payload(&mut reader, &shape)?;
+
+        Ok(Mask::from_words(words, shape))
+    }
+
+    /// create a mask from 16-bit words of packed bits, each row starts with a new word and unused bits must be zero
+    pub(crate) fn from_words(words: Vec<u16>, shape: [usize; D]) -> Self {
+        let column_count = *shape.last().unwrap();
+        let words_per_row = column_count.div_ceil(16);
+        let mut masks = Vec::with_capacity(shape.iter().product());
+
+        if words_per_row > 0 {
+            for row in words.chunks(words_per_row) {
+                for column in 0..column_count {
+                    masks.push(row[column / 16] & (1 << (column % 16)) != 0);
+                }
+            }
+        }
+
+        Mask { masks, shape }
+    }
+
+    /// the element in `column` of a row, the rows are counted over all axes except the last one
+    pub(crate) fn element(&self, row: usize, column: usize) -> bool {
+        self.masks[row * self.shape.last().unwrap() + column]
+    }
+
+    /// the number of elements of a row, there is no padding
+    pub(crate) fn padded_row_length(column_count: usize) -> usize {
+        column_count
+    }
+
+    /// copy the elements into a mask with a different shape, there is no padding to remove
+    pub(crate) fn repack<const E: usize>(&self, shape: [usize; E]) -> Mask<E> {
+        Mask {
+            masks: self.masks.clone(),
+            shape,
+        }
+    }
+
+    /// the words of `to_words`, they are packed from the bools
+    pub(crate) fn words(&self) -> Cow<'_, [u16]> {
+        Cow::Owned(self.to_words())
+    }
+
+    /// the 16-bit words of packed bits, each row starts with a new word and unused bits are zero
+    pub(crate) fn to_words(&self) -> Vec<u16> {
+        let column_count = *self.shape.last().unwrap();
+        let mut words = Vec::with_capacity(mask_word_count(&self.shape));
+
+        if column_count > 0 {
+            for row in self.masks.chunks(column_count) {
+                for chunk in row.chunks(16) {
+                    let mut word = 0u16;
+
+                    for (i, m) in chunk.iter().enumerate() {
+                        word |= (*m as u16) << i;
+                    }
+
+                    words.push(word);
+                }
+            }
+        }
+
+        words
+    }
+}
+
 impl Mask<1> {
     pub fn get(&self, index: usize) -> bool {
+        unwrap_or_panic(self.check_index(index));
+
         self.masks[index]
     }
 
     /// Copy the mask `k`-times into `output`
     pub fn tile_in_place(&self, k: usize, output: &mut Mask<1>) {
-        assert_eq!(
-            self.shape[0] * k,
-            output.shape[0],
-            "the number of elements in output must be k-times more than the elements in this mask"
-        );
+        unwrap_or_panic(self.check_tile(k, output));
         let self_masks = self.masks.len();
 
         for (i, d) in output.masks.iter_mut().enumerate() {
@@ -99,11 +213,7 @@ impl Mask<1> {
 
     /// Repeat each element of the mask `k`-times and store the result in `output`
     pub fn repeat_in_place(&self, k: usize, output: &mut Mask<1>) {
-        assert_eq!(
-            self.shape[0] * k,
-            output.shape[0],
-            "the number of elements in output must be k-times more than the elements in this array"
-        );
+        unwrap_or_panic(self.check_repeat(k, output));
         let mut index = 0;
 
         for i in 0..self.masks.len() {
@@ -117,8 +227,7 @@ impl Mask<1> {
     }
 
     pub fn repeat_as_row_in_place(&self, k: usize, output: &mut Mask<2>) {
-        assert_eq!(output.shape[0], k);
-        assert_eq!(output.shape[1], self.shape[0]);
+        unwrap_or_panic(self.check_repeat_as_row(k, output));
 
         for (i, m) in output.masks.iter_mut().enumerate() {
             *m = self.masks[i % self.shape[0]];
@@ -126,8 +235,7 @@ impl Mask<1> {
     }
 
     pub fn repeat_as_column_in_place(&self, k: usize, output: &mut Mask<2>) {
-        assert_eq!(output.shape[0], self.shape[0]);
-        assert_eq!(output.shape[1], k);
+        unwrap_or_panic(self.check_repeat_as_column(k, output));
 
         for i in 0..output.shape[0] {
             let mask = self.get(i);
@@ -141,9 +249,7 @@ impl Mask<1> {
 
 impl Mask<2> {
     pub fn from_vec(data: &Vec<bool>, shape: [usize; 2]) -> Self {
-        assert!(shape[0] > 0);
-        assert!(shape[1] > 0);
-        assert_eq!(data.len(), shape[0] * shape[1]);
+        unwrap_or_panic(check_data_length(data.len(), &shape));
 
         Self {
             masks: data.clone(),
@@ -152,8 +258,60 @@ impl Mask<2> {
     }
 
     pub fn get(&self, row: usize, column: usize) -> bool {
+        unwrap_or_panic(self.check_index(row, column));
+
         self.masks[row * self.shape[1] + column]
     }
+
+    /// Copy a row into a new mask
+    pub fn get_row(&self, row: usize) -> Mask<1> {
+        unwrap_or_panic(check_index(0, row, self.shape[0]));
+
+        let column_count = self.shape[1];
+
+        Mask {
+            masks: self.masks[row * column_count..(row + 1) * column_count].to_vec(),
+            shape: [column_count],
+        }
+    }
+
+    /// Overwrite a row with `values`
+    pub fn copy_row_from(&mut self, row: usize, values: &Mask<1>) {
+        unwrap_or_panic(self.check_row(row, values));
+
+        let column_count = self.shape[1];
+
+        self.masks[row * column_count..(row + 1) * column_count].copy_from_slice(&values.masks);
+    }
+
+    /// Copy a column into a new mask
+    pub fn column(&self, column: usize) -> Mask<1> {
+        unwrap_or_panic(check_index(1, column, self.shape[1]));
+
+        Mask {
+            masks: self.masks[column..]
+                .iter()
+                .step_by(self.shape[1])
+                .copied()
+                .collect(),
+            shape: [self.shape[0]],
+        }
+    }
+
+    /// Overwrite a column with `values`
+    pub fn set_column(&mut self, column: usize, values: &Mask<1>) {
+        unwrap_or_panic(self.check_column(column, values));
+
+        let column_count = self.shape[1];
+
+        for (mask, value) in self.masks[column..]
+            .iter_mut()
+            .step_by(column_count)
+            .zip(&values.masks)
+        {
+            *mask = *value;
+        }
+    }
 }
 
 /// This struct is used to create a mutable iterator over the masks and automatically zero out unused elements afterwards.
@@ -181,6 +339,26 @@ impl<const D: usize> Mask<D> {
         }
     }
 
+    /// the number of masks of a mask with the given shape
+    pub(crate) fn buffer_length(shape: &[usize; D]) -> usize {
+        shape.iter().product()
+    }
+
+    /// a mask with all elements false, that reuses the allocation of `masks`
+    pub(crate) fn from_buffer(shape: &[usize; D], mut masks: Vec<bool>) -> Self {
+        masks.clear();
+        masks.resize(Self::buffer_length(shape), false);
+
+        Self {
+            masks,
+            shape: *shape,
+        }
+    }
+
+    pub(crate) fn into_buffer(self) -> Vec<bool> {
+        self.masks
+    }
+
     pub(crate) fn new_from_data(shape: [usize; D], masks: Vec<bool>) -> Mask<D> {
         let mut n_masks = 1;
 
@@ -201,7 +379,7 @@ impl<const D: usize> Mask<D> {
     }
 
     pub fn copy(&mut self, other: &Mask<D>) {
-        assert_eq!(self.shape, other.shape);
+        unwrap_or_panic(self.check_same_shape(other));
 
         for (old_mask, new_mask) in self.masks.iter_mut().zip(other.masks.iter()) {
             *old_mask = *new_mask;
@@ -212,6 +390,19 @@ impl<const D: usize> Mask<D> {
         &self.shape
     }
 
+    /// number of elements that are set to 1
+    pub fn number_of_true_elements(&self) -> usize {
+        let mut count = 0;
+
+        for m in self.masks.iter() {
+            if *m {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
     pub(crate) fn get_masks(&self) -> &Vec<bool> {
         &self.masks
     }
@@ -230,7 +421,7 @@ impl<const D: usize> Mask<D> {
     }
 
     pub fn and_in_place(&mut self, other: &Self) {
-        assert_eq!(self.shape, other.shape);
+        unwrap_or_panic(self.check_same_shape(other));
 
         for (m1, m2) in self.masks.iter_mut().zip(other.masks.iter()) {
             *m1 = *m1 & *m2;
@@ -245,7 +436,7 @@ impl<const D: usize> Mask<D> {
     }
 
     pub fn or_in_place(&mut self, other: &Self) {
-        assert_eq!(self.shape, other.shape);
+        unwrap_or_panic(self.check_same_shape(other));
 
         for (m1, m2) in self.masks.iter_mut().zip(other.masks.iter()) {
             *m1 = *m1 | *m2;
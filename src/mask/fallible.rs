@@ -0,0 +1,160 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::{
+    error::{check_data_length, check_index},
+    Error, Mask,
+};
+
+impl<const D: usize> Mask<D> {
+    /// fallible version of `copy`
+    pub fn try_copy(&mut self, other: &Mask<D>) -> Result<(), Error> {
+        self.check_same_shape(other)?;
+
+        self.copy(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `and`
+    pub fn try_and(&self, other: &Self) -> Result<Self, Error> {
+        self.check_same_shape(other)?;
+
+        Ok(self.and(other))
+    }
+
+    /// fallible version of `and_in_place`
+    pub fn try_and_in_place(&mut self, other: &Self) -> Result<(), Error> {
+        self.check_same_shape(other)?;
+
+        self.and_in_place(other);
+
+        Ok(())
+    }
+
+    /// fallible version of `or`
+    pub fn try_or(&self, other: &Self) -> Result<Self, Error> {
+        self.check_same_shape(other)?;
+
+        Ok(self.or(other))
+    }
+
+    /// fallible version of `or_in_place`
+    pub fn try_or_in_place(&mut self, other: &Self) -> Result<(), Error> {
+        self.check_same_shape(other)?;
+
+        self.or_in_place(other);
+
+        Ok(())
+    }
+}
+
+impl Mask<1> {
+    /// fallible version of `get`
+    pub fn try_get(&self, index: usize) -> Result<bool, Error> {
+        self.check_index(index)?;
+
+        Ok(self.get(index))
+    }
+
+    /// fallible version of `tile_in_place`
+    pub fn try_tile_in_place(&self, k: usize, output: &mut Mask<1>) -> Result<(), Error> {
+        self.check_tile(k, output)?;
+
+        self.tile_in_place(k, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `repeat_in_place`
+    pub fn try_repeat_in_place(&self, k: usize, output: &mut Mask<1>) -> Result<(), Error> {
+        self.check_repeat(k, output)?;
+
+        self.repeat_in_place(k, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `repeat_as_row_in_place`
+    pub fn try_repeat_as_row_in_place(&self, k: usize, output: &mut Mask<2>) -> Result<(), Error> {
+        self.check_repeat_as_row(k, output)?;
+
+        self.repeat_as_row_in_place(k, output);
+
+        Ok(())
+    }
+
+    /// fallible version of `repeat_as_column_in_place`
+    pub fn try_repeat_as_column_in_place(
+        &self,
+        k: usize,
+        output: &mut Mask<2>,
+    ) -> Result<(), Error> {
+        self.check_repeat_as_column(k, output)?;
+
+        self.repeat_as_column_in_place(k, output);
+
+        Ok(())
+    }
+}
+
+impl Mask<2> {
+    /// fallible version of `from_vec`
+    pub fn try_from_vec(data: &Vec<bool>, shape: [usize; 2]) -> Result<Self, Error> {
+        check_data_length(data.len(), &shape)?;
+
+        Ok(Self::from_vec(data, shape))
+    }
+
+    /// fallible version of `get`
+    pub fn try_get(&self, row: usize, column: usize) -> Result<bool, Error> {
+        self.check_index(row, column)?;
+
+        Ok(self.get(row, column))
+    }
+
+    /// fallible version of `get_row`
+    pub fn try_get_row(&self, row: usize) -> Result<Mask<1>, Error> {
+        check_index(0, row, self.shape[0])?;
+
+        Ok(self.get_row(row))
+    }
+
+    /// fallible version of `copy_row_from`
+    pub fn try_copy_row_from(&mut self, row: usize, values: &Mask<1>) -> Result<(), Error> {
+        self.check_row(row, values)?;
+
+        self.copy_row_from(row, values);
+
+        Ok(())
+    }
+
+    /// fallible version of `column`
+    pub fn try_column(&self, column: usize) -> Result<Mask<1>, Error> {
+        check_index(1, column, self.shape[1])?;
+
+        Ok(self.column(column))
+    }
+
+    /// fallible version of `set_column`
+    pub fn try_set_column(&mut self, column: usize, values: &Mask<1>) -> Result<(), Error> {
+        self.check_column(column, values)?;
+
+        self.set_column(column, values);
+
+        Ok(())
+    }
+}
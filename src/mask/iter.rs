@@ -0,0 +1,182 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::{array::advance_index, Mask};
+
+/// the position of a mask iterator
+struct Position {
+    row: usize,
+    column: usize,
+    remaining: usize,
+}
+
+impl Position {
+    fn new<const D: usize>(mask: &Mask<D>) -> Self {
+        Position {
+            row: 0,
+            column: 0,
+            remaining: mask.number_of_elements(),
+        }
+    }
+
+    fn next<const D: usize>(&mut self, mask: &Mask<D>) -> Option<bool> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let element = mask.element(self.row, self.column);
+        self.remaining -= 1;
+        self.column += 1;
+
+        if self.column == mask.shape[D - 1] {
+            self.row += 1;
+            self.column = 0;
+        }
+
+        Some(element)
+    }
+}
+
+/// An iterator over the elements of a mask in row-major order, the unused bits of the rows are skipped
+pub struct MaskIter<'a, const D: usize> {
+    mask: &'a Mask<D>,
+    position: Position,
+}
+
+impl<const D: usize> Iterator for MaskIter<'_, D> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.position.next(self.mask)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.position.remaining, Some(self.position.remaining))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for MaskIter<'_, D> {}
+
+/// An iterator over the elements of a mask in row-major order together with their indices
+pub struct MaskIndexedIter<'a, const D: usize> {
+    iter: MaskIter<'a, D>,
+    index: [usize; D],
+}
+
+impl<const D: usize> Iterator for MaskIndexedIter<'_, D> {
+    type Item = ([usize; D], bool);
+
+    fn next(&mut self) -> Option<([usize; D], bool)> {
+        let element = self.iter.next()?;
+        let index = self.index;
+        advance_index(&mut self.index, &self.iter.mask.shape);
+
+        Some((index, element))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for MaskIndexedIter<'_, D> {}
+
+/// An iterator that consumes a mask and returns its elements in row-major order
+pub struct MaskIntoIter<const D: usize> {
+    mask: Mask<D>,
+    position: Position,
+}
+
+impl<const D: usize> Iterator for MaskIntoIter<D> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.position.next(&self.mask)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.position.remaining, Some(self.position.remaining))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for MaskIntoIter<D> {}
+
+impl<const D: usize> Mask<D> {
+    /// Iterate over the elements in row-major order.
+    /// There is no mutable iterator, because the elements are stored as packed bits.
+    pub fn iter(&self) -> MaskIter<'_, D> {
+        MaskIter {
+            mask: self,
+            position: Position::new(self),
+        }
+    }
+
+    /// Iterate over the indices and elements in row-major order
+    pub fn indexed_iter(&self) -> MaskIndexedIter<'_, D> {
+        MaskIndexedIter {
+            iter: self.iter(),
+            index: [0; D],
+        }
+    }
+}
+
+impl<const D: usize> IntoIterator for Mask<D> {
+    type Item = bool;
+    type IntoIter = MaskIntoIter<D>;
+
+    fn into_iter(self) -> MaskIntoIter<D> {
+        MaskIntoIter {
+            position: Position::new(&self),
+            mask: self,
+        }
+    }
+}
+
+impl<'a, const D: usize> IntoIterator for &'a Mask<D> {
+    type Item = bool;
+    type IntoIter = MaskIter<'a, D>;
+
+    fn into_iter(self) -> MaskIter<'a, D> {
+        self.iter()
+    }
+}
+
+impl FromIterator<bool> for Mask<1> {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        iter.into_iter().collect::<Vec<bool>>().into()
+    }
+}
+
+impl<'a> FromIterator<&'a bool> for Mask<1> {
+    fn from_iter<I: IntoIterator<Item = &'a bool>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Extend<bool> for Mask<1> {
+    /// The elements are appended, which copies the existing elements into a new buffer
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        let mut elements: Vec<bool> = self.iter().collect();
+        elements.extend(iter);
+        *self = elements.into();
+    }
+}
+
+impl<'a> Extend<&'a bool> for Mask<1> {
+    fn extend<I: IntoIterator<Item = &'a bool>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
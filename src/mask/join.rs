@@ -0,0 +1,216 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::ops::Range;
+
+use crate::{
+    error::{
+        check_axis, check_chunk_size, check_joinable_shapes, check_non_empty_range, unwrap_or_panic,
+    },
+    Error, Mask,
+};
+
+/// the number of words of one index along `axis`, which has to be before the last axis
+fn block_length<const D: usize>(shape: &[usize; D], axis: usize) -> usize {
+    shape[axis + 1..D - 1].iter().product::<usize>() * shape[D - 1].div_ceil(16)
+}
+
+/// the number of words of the row-padded packed bits of a shape
+fn word_count<const D: usize>(shape: &[usize; D]) -> usize {
+    shape[..D - 1].iter().product::<usize>() * shape[D - 1].div_ceil(16)
+}
+
+/// copy `count` bits into a row whose bits are still zero
+fn copy_bits(
+    source: &[u16],
+    source_start: usize,
+    destination: &mut [u16],
+    destination_start: usize,
+    count: usize,
+) {
+    for i in 0..count {
+        let (s, d) = (source_start + i, destination_start + i);
+        let bit = (source[s / 16] >> (s % 16)) & 1;
+        destination[d / 16] |= bit << (d % 16);
+    }
+}
+
+impl<const D: usize> Mask<D> {
+    fn check_split_at(&self, axis: usize, index: usize) -> Result<(), Error> {
+        check_axis(axis, D)?;
+        check_non_empty_range(axis, &(0..index), self.shape[axis])?;
+        check_non_empty_range(axis, &(index..self.shape[axis]), self.shape[axis])
+    }
+
+    /// copy the elements in `range` of `axis`, whole rows are copied as words
+    fn slice_axis(&self, axis: usize, range: Range<usize>) -> Mask<D> {
+        let mut shape = self.shape;
+        shape[axis] = range.len();
+
+        // an empty axis has no words to copy and the blocks would be empty
+        if word_count(&shape) == 0 {
+            return Mask::zeros(&shape);
+        }
+
+        let source = self.to_words();
+        let mut words = vec![0; word_count(&shape)];
+
+        if axis == D - 1 {
+            let source_words_per_row = self.shape[D - 1].div_ceil(16);
+            let words_per_row = shape[D - 1].div_ceil(16);
+
+            for (source, destination) in source
+                .chunks(source_words_per_row)
+                .zip(words.chunks_mut(words_per_row))
+            {
+                copy_bits(source, range.start, destination, 0, range.len());
+            }
+        } else {
+            let block_length = block_length(&shape, axis);
+            let output_length = range.len() * block_length;
+
+            for (i, destination) in words.chunks_mut(output_length).enumerate() {
+                let start = (i * self.shape[axis] + range.start) * block_length;
+                destination.copy_from_slice(&source[start..start + output_length]);
+            }
+        }
+
+        Mask::from_words(words, shape)
+    }
+
+    /// Join the masks along `axis`, their shapes must match in all other axes.
+    /// Along all axes except the last one whole rows are copied as words.
+    #[track_caller]
+    pub fn concatenate(masks: &[&Mask<D>], axis: usize) -> Mask<D> {
+        unwrap_or_panic(check_joinable_shapes(masks.iter().map(|m| m.shape), axis));
+
+        let mut shape = masks[0].shape;
+        shape[axis] = masks.iter().map(|m| m.shape[axis]).sum();
+
+        if word_count(&shape) == 0 {
+            return Mask::zeros(&shape);
+        }
+
+        let sources: Vec<Vec<u16>> = masks.iter().map(|m| m.to_words()).collect();
+        let mut words = vec![0; word_count(&shape)];
+
+        if axis == D - 1 {
+            let words_per_row = shape[D - 1].div_ceil(16);
+
+            for (row, destination) in words.chunks_mut(words_per_row).enumerate() {
+                let mut offset = 0;
+
+                for (mask, source) in masks.iter().zip(&sources) {
+                    let column_count = mask.shape[D - 1];
+                    let source_words_per_row = column_count.div_ceil(16);
+                    let source =
+                        &source[row * source_words_per_row..(row + 1) * source_words_per_row];
+
+                    copy_bits(source, 0, destination, offset, column_count);
+                    offset += column_count;
+                }
+            }
+        } else {
+            let block_length = block_length(&shape, axis);
+            let outer_count = shape[..axis].iter().product();
+            let mut offset = 0;
+
+            for i in 0..outer_count {
+                for (mask, source) in masks.iter().zip(&sources) {
+                    let length = mask.shape[axis] * block_length;
+                    words[offset..offset + length]
+                        .copy_from_slice(&source[i * length..(i + 1) * length]);
+                    offset += length;
+                }
+            }
+        }
+
+        Mask::from_words(words, shape)
+    }
+
+    /// Split the mask along `axis` into the indices before `index` and the remaining ones, both parts must not be empty
+    #[track_caller]
+    pub fn split_at(&self, axis: usize, index: usize) -> (Mask<D>, Mask<D>) {
+        unwrap_or_panic(self.check_split_at(axis, index));
+
+        (
+            self.slice_axis(axis, 0..index),
+            self.slice_axis(axis, index..self.shape[axis]),
+        )
+    }
+
+    /// Split the mask along `axis` into parts of `size` indices, the last part can be smaller
+    #[track_caller]
+    pub fn chunks(&self, axis: usize, size: usize) -> Vec<Mask<D>> {
+        unwrap_or_panic(check_axis(axis, D));
+        unwrap_or_panic(check_chunk_size(size));
+
+        let length = self.shape[axis];
+
+        (0..length)
+            .step_by(size)
+            .map(|start| self.slice_axis(axis, start..(start + size).min(length)))
+            .collect()
+    }
+
+    /// fallible version of `concatenate`
+    pub fn try_concatenate(masks: &[&Mask<D>], axis: usize) -> Result<Mask<D>, Error> {
+        check_joinable_shapes(masks.iter().map(|m| m.shape), axis)?;
+
+        Ok(Self::concatenate(masks, axis))
+    }
+
+    /// fallible version of `split_at`
+    pub fn try_split_at(&self, axis: usize, index: usize) -> Result<(Mask<D>, Mask<D>), Error> {
+        self.check_split_at(axis, index)?;
+
+        Ok(self.split_at(axis, index))
+    }
+
+    /// fallible version of `chunks`
+    pub fn try_chunks(&self, axis: usize, size: usize) -> Result<Vec<Mask<D>>, Error> {
+        check_axis(axis, D)?;
+        check_chunk_size(size)?;
+
+        Ok(self.chunks(axis, size))
+    }
+}
+
+impl Mask<2> {
+    /// Stack masks of the same length as the rows of a matrix, the words are copied as a whole
+    #[track_caller]
+    pub fn stack(masks: &[&Mask<1>]) -> Mask<2> {
+        unwrap_or_panic(check_joinable_shapes(
+            masks.iter().map(|m| [1, m.shape[0]]),
+            0,
+        ));
+
+        let mut words = Vec::with_capacity(masks.len() * masks[0].shape[0].div_ceil(16));
+
+        for mask in masks {
+            words.extend(mask.to_words());
+        }
+
+        Mask::from_words(words, [masks.len(), masks[0].shape[0]])
+    }
+
+    /// fallible version of `stack`
+    pub fn try_stack(masks: &[&Mask<1>]) -> Result<Mask<2>, Error> {
+        check_joinable_shapes(masks.iter().map(|m| [1, m.shape[0]]), 0)?;
+
+        Ok(Self::stack(masks))
+    }
+}
@@ -23,6 +23,20 @@ mod avx512f;
 #[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
 mod fallback;
 
+mod fallible;
+mod iter;
+mod join;
+mod reshape;
+
+use std::io;
+
+pub use iter::{MaskIndexedIter, MaskIntoIter, MaskIter};
+
+use crate::{
+    error::{check_index, check_repeat, check_same_length, check_same_shape, check_tile},
+    Error,
+};
+
 #[derive(Clone)]
 pub struct Mask<const D: usize> {
     #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
@@ -42,4 +56,61 @@ impl<const D: usize> Mask<D> {
 
         elements_count
     }
+
+    fn check_same_shape(&self, other: &Self) -> Result<(), Error> {
+        check_same_shape(&self.shape, &other.shape)
+    }
+
+    /// Serialize the mask in the compact binary format of `write_to`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_to(&mut bytes)
+            .expect("writing to a Vec should not fail");
+
+        bytes
+    }
+
+    /// Deserialize a mask in the compact binary format of `write_to`
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        Self::read_from(bytes)
+    }
+}
+
+impl Mask<1> {
+    fn check_index(&self, index: usize) -> Result<(), Error> {
+        check_index(0, index, self.shape[0])
+    }
+
+    fn check_tile(&self, k: usize, output: &Mask<1>) -> Result<(), Error> {
+        check_tile(self.shape[0], k, output.shape[0])
+    }
+
+    fn check_repeat(&self, k: usize, output: &Mask<1>) -> Result<(), Error> {
+        check_repeat(self.shape[0], k, output.shape[0])
+    }
+
+    fn check_repeat_as_row(&self, k: usize, output: &Mask<2>) -> Result<(), Error> {
+        check_same_shape(&[k, self.shape[0]], &output.shape)
+    }
+
+    fn check_repeat_as_column(&self, k: usize, output: &Mask<2>) -> Result<(), Error> {
+        check_same_shape(&[self.shape[0], k], &output.shape)
+    }
+}
+
+impl Mask<2> {
+    fn check_index(&self, row: usize, column: usize) -> Result<(), Error> {
+        check_index(0, row, self.shape[0])?;
+        check_index(1, column, self.shape[1])
+    }
+
+    fn check_row(&self, row: usize, values: &Mask<1>) -> Result<(), Error> {
+        check_index(0, row, self.shape[0])?;
+        check_same_length(self.shape[1], values.shape[0])
+    }
+
+    fn check_column(&self, column: usize, values: &Mask<1>) -> Result<(), Error> {
+        check_index(1, column, self.shape[1])?;
+        check_same_length(self.shape[0], values.shape[0])
+    }
 }
@@ -0,0 +1,93 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::{
+    error::{check_element_count, check_same_length, unwrap_or_panic},
+    Error, Mask,
+};
+
+impl<const D: usize> Mask<D> {
+    fn check_reshape<const E: usize>(&self, shape: &[usize; E]) -> Result<(), Error> {
+        check_same_length(check_element_count(shape)?, self.number_of_elements())
+    }
+
+    /// the padded rows of both shapes line up, so the masks can be moved without repacking
+    fn has_same_layout<const E: usize>(&self, shape: &[usize; E]) -> bool {
+        let old_column_count = *self.shape.last().unwrap();
+        let new_column_count = shape[E - 1];
+
+        old_column_count == new_column_count
+            || (Self::padded_row_length(old_column_count) == old_column_count
+                && Mask::<E>::padded_row_length(new_column_count) == new_column_count)
+    }
+
+    /// Copy the elements in row-major order into a mask with a different shape, the number of elements must stay the same
+    #[track_caller]
+    pub fn reshape<const E: usize>(&self, shape: [usize; E]) -> Mask<E> {
+        unwrap_or_panic(self.check_reshape(&shape));
+
+        if self.has_same_layout(&shape) {
+            Mask {
+                masks: self.masks.clone(),
+                shape,
+            }
+        } else {
+            self.repack(shape)
+        }
+    }
+
+    /// Change the shape without copying if the padded rows line up, which is the case if the last dimension stays
+    /// the same or both last dimensions are multiples of 16. Otherwise the elements are repacked.
+    #[track_caller]
+    pub fn into_shape<const E: usize>(self, shape: [usize; E]) -> Mask<E> {
+        unwrap_or_panic(self.check_reshape(&shape));
+
+        if self.has_same_layout(&shape) {
+            Mask {
+                masks: self.masks,
+                shape,
+            }
+        } else {
+            self.repack(shape)
+        }
+    }
+
+    /// Copy the elements in row-major order into a one-dimensional mask
+    pub fn flatten(&self) -> Mask<1> {
+        self.reshape([self.number_of_elements()])
+    }
+
+    /// Convert into a one-dimensional mask, see `into_shape`
+    pub fn into_flattened(self) -> Mask<1> {
+        let element_count = self.number_of_elements();
+
+        self.into_shape([element_count])
+    }
+
+    /// fallible version of `reshape`
+    pub fn try_reshape<const E: usize>(&self, shape: [usize; E]) -> Result<Mask<E>, Error> {
+        self.check_reshape(&shape)?;
+
+        Ok(self.reshape(shape))
+    }
+
+    /// fallible version of `into_shape`
+    pub fn try_into_shape<const E: usize>(self, shape: [usize; E]) -> Result<Mask<E>, Error> {
+        self.check_reshape(&shape)?;
+
+        Ok(self.into_shape(shape))
+    }
+}
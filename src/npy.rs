@@ -0,0 +1,209 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::io::{self, Read, Write};
+
+use crate::{
+    binary::{invalid_data, read_payload},
+    error::{check_deserialized_shape, check_element_count},
+    Array, Error, Mask,
+};
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+pub(crate) const ARRAY_DTYPE: &str = "<f4";
+pub(crate) const MASK_DTYPE: &str = "|b1";
+
+fn invalid_header(reason: &'static str) -> io::Error {
+    invalid_data(Error::InvalidFormat { reason })
+}
+
+/// create the header dictionary, padded so that the data starts at a multiple of 64 bytes
+pub(crate) fn header(dtype: &str, shape: &[usize]) -> Vec<u8> {
+    let shape = if shape.len() == 1 {
+        format!("({},)", shape[0])
+    } else {
+        let axes: Vec<String> = shape.iter().map(|s| s.to_string()).collect();
+        format!("({})", axes.join(", "))
+    };
+
+    let mut dictionary = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        dtype, shape
+    );
+
+    // version 1.0 stores the length of the header in 2 bytes, version 2.0 in 4 bytes
+    let prefix_length = if dictionary.len() + 1 + 10 + 63 <= u16::MAX as usize {
+        10
+    } else {
+        12
+    };
+    let unpadded_length = prefix_length + dictionary.len() + 1;
+    let padding = unpadded_length.next_multiple_of(64) - unpadded_length;
+    dictionary.extend(std::iter::repeat_n(' ', padding));
+    dictionary.push('\n');
+
+    let mut header = MAGIC.to_vec();
+
+    if prefix_length == 10 {
+        header.extend_from_slice(&[1, 0]);
+        header.extend_from_slice(&(dictionary.len() as u16).to_le_bytes());
+    } else {
+        header.extend_from_slice(&[2, 0]);
+        header.extend_from_slice(&(dictionary.len() as u32).to_le_bytes());
+    }
+
+    header.extend_from_slice(dictionary.as_bytes());
+
+    header
+}
+
+/// find the value of `key` in the header dictionary and return the rest of the dictionary starting at the value
+fn find_value<'a>(dictionary: &'a str, key: &str) -> io::Result<&'a str> {
+    let start = dictionary
+        .find(&format!("'{}'", key))
+        .or_else(|| dictionary.find(&format!("\"{}\"", key)))
+        .ok_or_else(|| invalid_header("the header is missing a key"))?;
+    let rest = &dictionary[start + key.len() + 2..];
+    let rest = rest
+        .trim_start()
+        .strip_prefix(':')
+        .ok_or_else(|| invalid_header("the header dictionary is malformed"))?;
+
+    Ok(rest.trim_start())
+}
+
+fn parse_dtype(dictionary: &str) -> io::Result<&str> {
+    let value = find_value(dictionary, "descr")?;
+    let quote = value
+        .chars()
+        .next()
+        .filter(|c| *c == '\'' || *c == '"')
+        .ok_or_else(|| invalid_header("the dtype is not a string"))?;
+    let end = value[1..]
+        .find(quote)
+        .ok_or_else(|| invalid_header("the dtype is not a string"))?;
+
+    Ok(&value[1..end + 1])
+}
+
+fn parse_fortran_order(dictionary: &str) -> io::Result<bool> {
+    let value = find_value(dictionary, "fortran_order")?;
+
+    if value.starts_with("True") {
+        Ok(true)
+    } else if value.starts_with("False") {
+        Ok(false)
+    } else {
+        Err(invalid_header("fortran_order is not a boolean"))
+    }
+}
+
+fn parse_shape(dictionary: &str) -> io::Result<Vec<usize>> {
+    let value = find_value(dictionary, "shape")?;
+    let end = value
+        .find(')')
+        .filter(|_| value.starts_with('('))
+        .ok_or_else(|| invalid_header("the shape is not a tuple"))?;
+
+    value[1..end]
+        .split(',')
+        .map(|axis| axis.trim().trim_end_matches('L'))
+        .filter(|axis| !axis.is_empty())
+        .map(|axis| {
+            axis.parse()
+                .map_err(|_| invalid_header("the shape contains an invalid axis"))
+        })
+        .collect()
+}
+
+/// read the header of a .npy file, check the dtype and the order and return the shape
+fn read_header<const D: usize>(
+    reader: &mut impl Read,
+    dtype: &'static str,
+) -> io::Result<[usize; D]> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+
+    if &magic[..6] != MAGIC {
+        return Err(invalid_header("the magic bytes don't match"));
+    }
+
+    let dictionary_length = match magic[6] {
+        1 => {
+            let mut length = [0u8; 2];
+            reader.read_exact(&mut length)?;
+            u16::from_le_bytes(length) as usize
+        }
+        2 | 3 => {
+            let mut length = [0u8; 4];
+            reader.read_exact(&mut length)?;
+            u32::from_le_bytes(length) as usize
+        }
+        _ => return Err(invalid_header("unsupported version")),
+    };
+
+    let dictionary = read_payload(reader, dictionary_length)?;
+    let dictionary = std::str::from_utf8(&dictionary)
+        .map_err(|_| invalid_header("the header is not valid UTF-8"))?;
+
+    let found_dtype = parse_dtype(dictionary)?;
+
+    if found_dtype != dtype {
+        return Err(invalid_data(Error::UnsupportedDtype {
+            expected: dtype,
+            found: found_dtype.to_string(),
+        }));
+    }
+
+    if parse_fortran_order(dictionary)? {
+        return Err(invalid_data(Error::UnsupportedFortranOrder));
+    }
+
+    let shape = parse_shape(dictionary)?;
+    let element_count = check_element_count(&shape).map_err(invalid_data)?;
+
+    check_deserialized_shape(&shape, element_count).map_err(invalid_data)
+}
+
+impl<const D: usize> Array<D> {
+    /// Write the array as a NumPy .npy file with dtype `<f4` in C order
+    pub fn write_npy(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(&header(ARRAY_DTYPE, &self.shape))?;
+        self.write_elements(&mut writer)
+    }
+
+    /// Read a NumPy .npy file with dtype `<f4` in C order, the number of dimensions must be `D`
+    pub fn read_npy(mut reader: impl Read) -> io::Result<Self> {
+        let shape = read_header(&mut reader, ARRAY_DTYPE)?;
+
+        Array::read_elements(&mut reader, shape)
+    }
+}
+
+impl<const D: usize> Mask<D> {
+    /// Write the mask as a NumPy .npy file with dtype `|b1` in C order
+    pub fn write_npy(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(&header(MASK_DTYPE, self.get_shape()))?;
+        self.write_elements(&mut writer)
+    }
+
+    /// Read a NumPy .npy file with dtype `|b1` in C order, the number of dimensions must be `D`
+    pub fn read_npy(mut reader: impl Read) -> io::Result<Self> {
+        let shape = read_header(&mut reader, MASK_DTYPE)?;
+
+        Mask::read_elements(&mut reader, shape)
+    }
+}
@@ -0,0 +1,113 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::io::{self, Read, Seek, Write};
+
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{
+    npy::{header, ARRAY_DTYPE, MASK_DTYPE},
+    Array, Mask,
+};
+
+/// Writes several named arrays and masks into a NumPy .npz archive
+pub struct NpzWriter<W: Write + Seek> {
+    zip: ZipWriter<W>,
+    compression_method: CompressionMethod,
+}
+
+impl<W: Write + Seek> NpzWriter<W> {
+    /// create an archive without compression like `numpy.savez`
+    pub fn new(writer: W) -> Self {
+        Self {
+            zip: ZipWriter::new(writer),
+            compression_method: CompressionMethod::Stored,
+        }
+    }
+
+    /// create an archive with deflate compression like `numpy.savez_compressed`
+    pub fn new_compressed(writer: W) -> Self {
+        Self {
+            zip: ZipWriter::new(writer),
+            compression_method: CompressionMethod::Deflated,
+        }
+    }
+
+    fn start_file(
+        &mut self,
+        name: &str,
+        dtype: &str,
+        shape: &[usize],
+        element_size: usize,
+    ) -> io::Result<()> {
+        let length = header(dtype, shape).len() + shape.iter().product::<usize>() * element_size;
+        let options = SimpleFileOptions::default()
+            .compression_method(self.compression_method)
+            .large_file(length >= u32::MAX as usize);
+
+        self.zip.start_file(format!("{}.npy", name), options)?;
+
+        Ok(())
+    }
+
+    /// add an array, it can be loaded in NumPy with `archive[name]`
+    pub fn add_array<const D: usize>(&mut self, name: &str, array: &Array<D>) -> io::Result<()> {
+        self.start_file(name, ARRAY_DTYPE, &array.get_shape(), 4)?;
+        array.write_npy(&mut self.zip)
+    }
+
+    /// add a mask, it can be loaded in NumPy with `archive[name]`
+    pub fn add_mask<const D: usize>(&mut self, name: &str, mask: &Mask<D>) -> io::Result<()> {
+        self.start_file(name, MASK_DTYPE, mask.get_shape(), 1)?;
+        mask.write_npy(&mut self.zip)
+    }
+
+    /// write the central directory of the archive and return the writer
+    pub fn finish(self) -> io::Result<W> {
+        Ok(self.zip.finish()?)
+    }
+}
+
+/// Reads named arrays and masks from a NumPy .npz archive
+pub struct NpzReader<R: Read + Seek> {
+    zip: ZipArchive<R>,
+}
+
+impl<R: Read + Seek> NpzReader<R> {
+    pub fn new(reader: R) -> io::Result<Self> {
+        Ok(Self {
+            zip: ZipArchive::new(reader)?,
+        })
+    }
+
+    /// the names of the entries in the archive without the .npy extension
+    pub fn names(&self) -> Vec<String> {
+        self.zip
+            .file_names()
+            .map(|name| name.strip_suffix(".npy").unwrap_or(name).to_string())
+            .collect()
+    }
+
+    /// read the array with the given name, its dtype has to be `<f4`
+    pub fn array<const D: usize>(&mut self, name: &str) -> io::Result<Array<D>> {
+        Array::read_npy(self.zip.by_name(&format!("{}.npy", name))?)
+    }
+
+    /// read the mask with the given name, its dtype has to be `|b1`
+    pub fn mask<const D: usize>(&mut self, name: &str) -> io::Result<Mask<D>> {
+        Mask::read_npy(self.zip.by_name(&format!("{}.npy", name))?)
+    }
+}
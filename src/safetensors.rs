@@ -0,0 +1,318 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Write},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    binary::{invalid_data, read_payload},
+    error::{check_deserialized_shape, check_element_count},
+    Array, Error, Mask,
+};
+
+const ARRAY_DTYPE: &str = "F32";
+const MASK_DTYPE: &str = "BOOL";
+
+/// the same limit as in the reference implementation, protects against corrupted header sizes
+const MAX_HEADER_SIZE: u64 = 100_000_000;
+
+/// An array or mask with one or two dimensions, used for safetensors files with tensors of different ranks
+pub enum Tensor {
+    Array1(Array<1>),
+    Array2(Array<2>),
+    Mask1(Mask<1>),
+    Mask2(Mask<2>),
+}
+
+impl From<Array<1>> for Tensor {
+    fn from(value: Array<1>) -> Self {
+        Tensor::Array1(value)
+    }
+}
+
+impl From<Array<2>> for Tensor {
+    fn from(value: Array<2>) -> Self {
+        Tensor::Array2(value)
+    }
+}
+
+impl From<Mask<1>> for Tensor {
+    fn from(value: Mask<1>) -> Self {
+        Tensor::Mask1(value)
+    }
+}
+
+impl From<Mask<2>> for Tensor {
+    fn from(value: Mask<2>) -> Self {
+        Tensor::Mask2(value)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TensorInfo {
+    dtype: String,
+    shape: Vec<usize>,
+    data_offsets: [usize; 2],
+}
+
+trait Entry {
+    fn dtype(&self) -> &'static str;
+    fn shape(&self) -> Vec<usize>;
+    fn write_elements(&self, writer: &mut impl Write) -> io::Result<()>;
+}
+
+impl<const D: usize> Entry for Array<D> {
+    fn dtype(&self) -> &'static str {
+        ARRAY_DTYPE
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.to_vec()
+    }
+
+    fn write_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        Array::write_elements(self, writer)
+    }
+}
+
+impl<const D: usize> Entry for Mask<D> {
+    fn dtype(&self) -> &'static str {
+        MASK_DTYPE
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.get_shape().to_vec()
+    }
+
+    fn write_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        Mask::write_elements(self, writer)
+    }
+}
+
+impl Entry for Tensor {
+    fn dtype(&self) -> &'static str {
+        match self {
+            Tensor::Array1(_) | Tensor::Array2(_) => ARRAY_DTYPE,
+            Tensor::Mask1(_) | Tensor::Mask2(_) => MASK_DTYPE,
+        }
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        match self {
+            Tensor::Array1(a) => Entry::shape(a),
+            Tensor::Array2(a) => Entry::shape(a),
+            Tensor::Mask1(m) => Entry::shape(m),
+            Tensor::Mask2(m) => Entry::shape(m),
+        }
+    }
+
+    fn write_elements(&self, writer: &mut impl Write) -> io::Result<()> {
+        match self {
+            Tensor::Array1(a) => a.write_elements(writer),
+            Tensor::Array2(a) => a.write_elements(writer),
+            Tensor::Mask1(m) => m.write_elements(writer),
+            Tensor::Mask2(m) => m.write_elements(writer),
+        }
+    }
+}
+
+fn invalid_format(reason: &'static str) -> io::Error {
+    invalid_data(Error::InvalidFormat { reason })
+}
+
+fn element_size(dtype: &str) -> usize {
+    if dtype == ARRAY_DTYPE {
+        4
+    } else {
+        1
+    }
+}
+
+fn write<T: Entry>(tensors: &BTreeMap<String, T>, mut writer: impl Write) -> io::Result<()> {
+    let mut infos = BTreeMap::new();
+    let mut offset = 0;
+
+    for (name, tensor) in tensors {
+        let shape = tensor.shape();
+        let length = shape.iter().product::<usize>() * element_size(tensor.dtype());
+
+        infos.insert(
+            name,
+            TensorInfo {
+                dtype: tensor.dtype().to_string(),
+                shape,
+                data_offsets: [offset, offset + length],
+            },
+        );
+
+        offset += length;
+    }
+
+    let mut header = serde_json::to_vec(&infos).map_err(io::Error::other)?;
+
+    // the buffer should start at a multiple of 8 bytes
+    while !header.len().is_multiple_of(8) {
+        header.push(b' ');
+    }
+
+    writer.write_all(&(header.len() as u64).to_le_bytes())?;
+    writer.write_all(&header)?;
+
+    for tensor in tensors.values() {
+        tensor.write_elements(&mut writer)?;
+    }
+
+    Ok(())
+}
+
+/// read the header and return the tensors in the order of their data
+fn read_header(
+    reader: &mut impl Read,
+    dtypes: &[&str],
+    expected: &'static str,
+) -> io::Result<Vec<(String, TensorInfo)>> {
+    let mut header_size = [0u8; 8];
+    reader.read_exact(&mut header_size)?;
+    let header_size = u64::from_le_bytes(header_size);
+
+    if header_size > MAX_HEADER_SIZE {
+        return Err(invalid_format("the header is too large"));
+    }
+
+    let header = read_payload(reader, header_size as usize)?;
+    let header: BTreeMap<String, serde_json::Value> = serde_json::from_slice(&header)
+        .map_err(|_| invalid_format("the header is not a JSON object"))?;
+
+    let mut tensors = Vec::new();
+
+    for (name, value) in header {
+        if name == "__metadata__" {
+            continue;
+        }
+
+        let info: TensorInfo = serde_json::from_value(value)
+            .map_err(|_| invalid_format("the tensor information is malformed"))?;
+
+        if !dtypes.contains(&info.dtype.as_str()) {
+            return Err(invalid_data(Error::UnsupportedDtype {
+                expected,
+                found: info.dtype,
+            }));
+        }
+
+        tensors.push((name, info));
+    }
+
+    tensors.sort_by_key(|(_, info)| info.data_offsets);
+
+    let mut offset = 0;
+
+    for (_, info) in &tensors {
+        let [begin, end] = info.data_offsets;
+        let length = check_element_count(&info.shape)
+            .ok()
+            .and_then(|count| count.checked_mul(element_size(&info.dtype)))
+            .ok_or_else(|| invalid_format("the tensor is too large"))?;
+
+        if begin != offset || end < begin {
+            return Err(invalid_format(
+                "the data offsets must cover the buffer without gaps or overlaps",
+            ));
+        }
+
+        if end - begin != length {
+            return Err(invalid_format(
+                "the data offsets don't match the shape and dtype",
+            ));
+        }
+
+        offset = end;
+    }
+
+    Ok(tensors)
+}
+
+fn read_array<const D: usize>(reader: &mut impl Read, info: &TensorInfo) -> io::Result<Array<D>> {
+    let element_count = info.shape.iter().product();
+    let shape = check_deserialized_shape(&info.shape, element_count).map_err(invalid_data)?;
+
+    Array::read_elements(reader, shape)
+}
+
+fn read_mask<const D: usize>(reader: &mut impl Read, info: &TensorInfo) -> io::Result<Mask<D>> {
+    let element_count = info.shape.iter().product();
+    let shape = check_deserialized_shape(&info.shape, element_count).map_err(invalid_data)?;
+
+    Mask::read_elements(reader, shape)
+}
+
+/// Write arrays with the same number of dimensions into a safetensors file with dtype F32
+pub fn write_safetensors<const D: usize>(
+    arrays: &BTreeMap<String, Array<D>>,
+    writer: impl Write,
+) -> io::Result<()> {
+    write(arrays, writer)
+}
+
+/// Read a safetensors file, all tensors must have dtype F32 and `D` dimensions
+pub fn read_safetensors<const D: usize>(
+    mut reader: impl Read,
+) -> io::Result<BTreeMap<String, Array<D>>> {
+    let mut arrays = BTreeMap::new();
+
+    for (name, info) in read_header(&mut reader, &[ARRAY_DTYPE], ARRAY_DTYPE)? {
+        let array = read_array(&mut reader, &info)?;
+        arrays.insert(name, array);
+    }
+
+    Ok(arrays)
+}
+
+/// Write arrays and masks with one or two dimensions into a safetensors file with dtypes F32 and BOOL
+pub fn write_safetensors_tensors(
+    tensors: &BTreeMap<String, Tensor>,
+    writer: impl Write,
+) -> io::Result<()> {
+    write(tensors, writer)
+}
+
+/// Read a safetensors file, all tensors must have dtype F32 or BOOL and one or two dimensions
+pub fn read_safetensors_tensors(mut reader: impl Read) -> io::Result<BTreeMap<String, Tensor>> {
+    let mut tensors = BTreeMap::new();
+
+    for (name, info) in read_header(&mut reader, &[ARRAY_DTYPE, MASK_DTYPE], "F32 or BOOL")? {
+        let tensor = match (info.dtype.as_str(), info.shape.len()) {
+            (ARRAY_DTYPE, 1) => Tensor::Array1(read_array(&mut reader, &info)?),
+            (ARRAY_DTYPE, 2) => Tensor::Array2(read_array(&mut reader, &info)?),
+            (_, 1) => Tensor::Mask1(read_mask(&mut reader, &info)?),
+            (_, 2) => Tensor::Mask2(read_mask(&mut reader, &info)?),
+            _ => {
+                return Err(invalid_data(Error::InvalidShape {
+                    shape: info.shape,
+                    reason: "only tensors with one or two dimensions are supported",
+                }))
+            }
+        };
+
+        tensors.insert(name, tensor);
+    }
+
+    Ok(tensors)
+}
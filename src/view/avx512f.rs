@@ -0,0 +1,282 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+// Kernels for the rows of views. A row of a view can start anywhere in a row of the array. If it starts at a
+// register boundary, it is loaded and stored with aligned instructions, otherwise with unaligned ones. The end of
+// a row is loaded and stored with a mask, so that the elements after the view are never read or written.
+
+use std::arch::x86_64::{
+    __m512, __mmask16, _mm512_abs_ps, _mm512_add_ps, _mm512_div_ps, _mm512_fmadd_ps,
+    _mm512_load_ps, _mm512_loadu_ps, _mm512_mask_cmpeq_ps_mask, _mm512_mask_cmple_ps_mask,
+    _mm512_mask_cmplt_ps_mask, _mm512_mask_cmpneq_ps_mask, _mm512_mask_cmpnle_ps_mask,
+    _mm512_mask_cmpnlt_ps_mask, _mm512_mask_loadu_ps, _mm512_mask_storeu_ps, _mm512_max_ps,
+    _mm512_min_ps, _mm512_mul_ps, _mm512_reduce_add_ps, _mm512_reduce_max_ps, _mm512_reduce_min_ps,
+    _mm512_reduce_mul_ps, _mm512_set1_ps, _mm512_sqrt_ps, _mm512_store_ps, _mm512_storeu_ps,
+    _mm512_sub_ps,
+};
+
+use super::{BinaryOp, CompareOp, ReduceOp, UnaryOp};
+use crate::array::exp_register;
+
+/// the lanes that contain elements, if there are `remaining` elements left
+fn lanes(remaining: usize) -> __mmask16 {
+    if remaining >= 16 {
+        0xFFFF
+    } else {
+        (1 << remaining) - 1
+    }
+}
+
+/// load the elements from `start`, lanes after the end of `elements` are set to `default_value`
+#[inline(always)]
+unsafe fn load(elements: &[f32], start: usize, default_value: f32) -> __m512 {
+    let pointer = elements.as_ptr().add(start);
+
+    if elements.len() - start >= 16 {
+        if (pointer as usize).is_multiple_of(64) {
+            _mm512_load_ps(pointer)
+        } else {
+            _mm512_loadu_ps(pointer)
+        }
+    } else {
+        _mm512_mask_loadu_ps(
+            _mm512_set1_ps(default_value),
+            lanes(elements.len() - start),
+            pointer,
+        )
+    }
+}
+
+/// store the lanes of `value` that are before the end of `elements`
+#[inline(always)]
+unsafe fn store(elements: &mut [f32], start: usize, value: __m512) {
+    let remaining = elements.len() - start;
+    let pointer = elements.as_mut_ptr().add(start);
+
+    if remaining >= 16 {
+        if (pointer as usize).is_multiple_of(64) {
+            _mm512_store_ps(pointer, value)
+        } else {
+            _mm512_storeu_ps(pointer, value)
+        }
+    } else {
+        _mm512_mask_storeu_ps(pointer, lanes(remaining), value)
+    }
+}
+
+/// store the lanes of `value` that are before the end of `elements` and set in `word`
+#[inline(always)]
+unsafe fn store_masked(elements: &mut [f32], start: usize, value: __m512, word: __mmask16) {
+    if word == 0xFFFF {
+        store(elements, start, value)
+    } else {
+        let remaining = elements.len() - start;
+        _mm512_mask_storeu_ps(
+            elements.as_mut_ptr().add(start),
+            lanes(remaining) & word,
+            value,
+        )
+    }
+}
+
+/// the word of `mask` for the register at `start`, without a mask all lanes are set
+fn word(mask: Option<&[u16]>, start: usize) -> __mmask16 {
+    mask.map_or(0xFFFF, |mask| mask[start / 16])
+}
+
+fn binary_function(op: BinaryOp) -> unsafe fn(__m512, __m512) -> __m512 {
+    match op {
+        BinaryOp::Add => _mm512_add_ps,
+        BinaryOp::Sub => _mm512_sub_ps,
+        BinaryOp::Mul => _mm512_mul_ps,
+        BinaryOp::Div => _mm512_div_ps,
+        BinaryOp::Max => _mm512_max_ps,
+        BinaryOp::Min => _mm512_min_ps,
+    }
+}
+
+fn compare_function(op: CompareOp) -> unsafe fn(__mmask16, __m512, __m512) -> __mmask16 {
+    match op {
+        CompareOp::Equal => _mm512_mask_cmpeq_ps_mask,
+        CompareOp::NotEqual => _mm512_mask_cmpneq_ps_mask,
+        CompareOp::GreaterThan => _mm512_mask_cmpnle_ps_mask,
+        CompareOp::GreaterThanOrEqual => _mm512_mask_cmpnlt_ps_mask,
+        CompareOp::LessThan => _mm512_mask_cmplt_ps_mask,
+        CompareOp::LessThanOrEqual => _mm512_mask_cmple_ps_mask,
+    }
+}
+
+pub(super) fn binary(a: &[f32], b: &[f32], output: &mut [f32], op: BinaryOp) {
+    let func = binary_function(op);
+
+    unsafe {
+        for start in (0..a.len()).step_by(16) {
+            store(
+                output,
+                start,
+                func(load(a, start, 0.0), load(b, start, 0.0)),
+            );
+        }
+    }
+}
+
+pub(super) fn binary_scalar(a: &[f32], scalar: f32, output: &mut [f32], op: BinaryOp) {
+    let func = binary_function(op);
+
+    unsafe {
+        let scalar = _mm512_set1_ps(scalar);
+
+        for start in (0..a.len()).step_by(16) {
+            store(output, start, func(load(a, start, 0.0), scalar));
+        }
+    }
+}
+
+/// the in-place kernels only change the elements that are set in `mask`, the words of the row
+pub(super) fn binary_in_place(a: &mut [f32], b: &[f32], op: BinaryOp, mask: Option<&[u16]>) {
+    let func = binary_function(op);
+
+    unsafe {
+        for start in (0..a.len()).step_by(16) {
+            let result = func(load(a, start, 0.0), load(b, start, 0.0));
+            store_masked(a, start, result, word(mask, start));
+        }
+    }
+}
+
+pub(super) fn binary_scalar_in_place(
+    a: &mut [f32],
+    scalar: f32,
+    op: BinaryOp,
+    mask: Option<&[u16]>,
+) {
+    let func = binary_function(op);
+
+    unsafe {
+        let scalar = _mm512_set1_ps(scalar);
+
+        for start in (0..a.len()).step_by(16) {
+            let result = func(load(a, start, 0.0), scalar);
+            store_masked(a, start, result, word(mask, start));
+        }
+    }
+}
+
+pub(super) fn unary_in_place(a: &mut [f32], op: UnaryOp, mask: Option<&[u16]>) {
+    unsafe {
+        for start in (0..a.len()).step_by(16) {
+            let value = load(a, start, 0.0);
+            let result = match op {
+                UnaryOp::Sqrt => _mm512_sqrt_ps(value),
+                UnaryOp::Square => _mm512_mul_ps(value, value),
+                UnaryOp::Abs => _mm512_abs_ps(value),
+                UnaryOp::Exp => exp_register(value),
+            };
+
+            store_masked(a, start, result, word(mask, start));
+        }
+    }
+}
+
+/// `c + a * b` with a single rounding, stored in `c`
+pub(super) fn fmadd_in_place(c: &mut [f32], a: &[f32], b: &[f32], mask: Option<&[u16]>) {
+    unsafe {
+        for start in (0..c.len()).step_by(16) {
+            let result = _mm512_fmadd_ps(
+                load(a, start, 0.0),
+                load(b, start, 0.0),
+                load(c, start, 0.0),
+            );
+            store_masked(c, start, result, word(mask, start));
+        }
+    }
+}
+
+/// `c + a * scalar` with a single rounding, stored in `c`
+pub(super) fn fmadd_scalar_in_place(c: &mut [f32], a: &[f32], scalar: f32, mask: Option<&[u16]>) {
+    unsafe {
+        let scalar = _mm512_set1_ps(scalar);
+
+        for start in (0..c.len()).step_by(16) {
+            let result = _mm512_fmadd_ps(load(a, start, 0.0), scalar, load(c, start, 0.0));
+            store_masked(c, start, result, word(mask, start));
+        }
+    }
+}
+
+/// the sum of the products, the lanes after the end are zero and don't change it
+pub(super) fn dot(a: &[f32], b: &[f32]) -> f32 {
+    unsafe {
+        let mut sum = _mm512_set1_ps(0.0);
+
+        for start in (0..a.len()).step_by(16) {
+            sum = _mm512_fmadd_ps(load(a, start, 0.0), load(b, start, 0.0), sum);
+        }
+
+        _mm512_reduce_add_ps(sum)
+    }
+}
+
+/// reduce the elements, the lanes after the end are filled with `default_value`, so that they don't change the result
+pub(super) fn reduce(a: &[f32], op: ReduceOp, default_value: f32) -> f32 {
+    unsafe {
+        let mut result = _mm512_set1_ps(default_value);
+
+        for start in (0..a.len()).step_by(16) {
+            let value = load(a, start, default_value);
+
+            result = match op {
+                ReduceOp::Sum => _mm512_add_ps(result, value),
+                ReduceOp::Product => _mm512_mul_ps(result, value),
+                ReduceOp::Max => _mm512_max_ps(result, value),
+                ReduceOp::Min => _mm512_min_ps(result, value),
+            };
+        }
+
+        match op {
+            ReduceOp::Sum => _mm512_reduce_add_ps(result),
+            ReduceOp::Product => _mm512_reduce_mul_ps(result),
+            ReduceOp::Max => _mm512_reduce_max_ps(result),
+            ReduceOp::Min => _mm512_reduce_min_ps(result),
+        }
+    }
+}
+
+/// push a word for every 16 elements, the bits after the end are zero
+pub(super) fn compare(a: &[f32], b: &[f32], words: &mut Vec<u16>, op: CompareOp) {
+    let func = compare_function(op);
+
+    unsafe {
+        for start in (0..a.len()).step_by(16) {
+            let lanes = lanes(a.len() - start);
+            words.push(func(lanes, load(a, start, 0.0), load(b, start, 0.0)));
+        }
+    }
+}
+
+/// push a word for every 16 elements, the bits after the end are zero
+pub(super) fn compare_scalar(a: &[f32], scalar: f32, words: &mut Vec<u16>, op: CompareOp) {
+    let func = compare_function(op);
+
+    unsafe {
+        let scalar = _mm512_set1_ps(scalar);
+
+        for start in (0..a.len()).step_by(16) {
+            let lanes = lanes(a.len() - start);
+            words.push(func(lanes, load(a, start, 0.0), scalar));
+        }
+    }
+}
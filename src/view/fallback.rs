@@ -0,0 +1,160 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use super::{BinaryOp, CompareOp, ReduceOp, UnaryOp};
+
+fn binary_function(op: BinaryOp) -> fn(f32, f32) -> f32 {
+    match op {
+        BinaryOp::Add => |a, b| a + b,
+        BinaryOp::Sub => |a, b| a - b,
+        BinaryOp::Mul => |a, b| a * b,
+        BinaryOp::Div => |a, b| a / b,
+        BinaryOp::Max => f32::max,
+        BinaryOp::Min => f32::min,
+    }
+}
+
+fn compare_function(op: CompareOp) -> fn(&f32, &f32) -> bool {
+    match op {
+        CompareOp::Equal => f32::eq,
+        CompareOp::NotEqual => f32::ne,
+        CompareOp::GreaterThan => f32::gt,
+        CompareOp::GreaterThanOrEqual => f32::ge,
+        CompareOp::LessThan => f32::lt,
+        CompareOp::LessThanOrEqual => f32::le,
+    }
+}
+
+pub(super) fn binary(a: &[f32], b: &[f32], output: &mut [f32], op: BinaryOp) {
+    let func = binary_function(op);
+
+    for ((o, a), b) in output.iter_mut().zip(a.iter()).zip(b.iter()) {
+        *o = func(*a, *b);
+    }
+}
+
+pub(super) fn binary_scalar(a: &[f32], scalar: f32, output: &mut [f32], op: BinaryOp) {
+    let func = binary_function(op);
+
+    for (o, a) in output.iter_mut().zip(a.iter()) {
+        *o = func(*a, scalar);
+    }
+}
+
+/// whether the element at `index` is set in the words of `mask`, without a mask all elements are set
+fn is_set(mask: Option<&[u16]>, index: usize) -> bool {
+    mask.is_none_or(|mask| mask[index / 16] & (1 << (index % 16)) != 0)
+}
+
+/// the in-place kernels only change the elements that are set in `mask`, the words of the row
+pub(super) fn binary_in_place(a: &mut [f32], b: &[f32], op: BinaryOp, mask: Option<&[u16]>) {
+    let func = binary_function(op);
+
+    for (i, (a, b)) in a.iter_mut().zip(b.iter()).enumerate() {
+        if is_set(mask, i) {
+            *a = func(*a, *b);
+        }
+    }
+}
+
+pub(super) fn binary_scalar_in_place(
+    a: &mut [f32],
+    scalar: f32,
+    op: BinaryOp,
+    mask: Option<&[u16]>,
+) {
+    let func = binary_function(op);
+
+    for (i, a) in a.iter_mut().enumerate() {
+        if is_set(mask, i) {
+            *a = func(*a, scalar);
+        }
+    }
+}
+
+pub(super) fn unary_in_place(a: &mut [f32], op: UnaryOp, mask: Option<&[u16]>) {
+    for (i, a) in a.iter_mut().enumerate() {
+        if is_set(mask, i) {
+            *a = match op {
+                UnaryOp::Sqrt => a.sqrt(),
+                UnaryOp::Square => *a * *a,
+                UnaryOp::Abs => a.abs(),
+                UnaryOp::Exp => a.exp(),
+            };
+        }
+    }
+}
+
+/// `c + a * b`, stored in `c`
+pub(super) fn fmadd_in_place(c: &mut [f32], a: &[f32], b: &[f32], mask: Option<&[u16]>) {
+    for (i, (c, (a, b))) in c.iter_mut().zip(a.iter().zip(b.iter())).enumerate() {
+        if is_set(mask, i) {
+            *c += *a * *b;
+        }
+    }
+}
+
+/// `c + a * scalar`, stored in `c`
+pub(super) fn fmadd_scalar_in_place(c: &mut [f32], a: &[f32], scalar: f32, mask: Option<&[u16]>) {
+    for (i, (c, a)) in c.iter_mut().zip(a.iter()).enumerate() {
+        if is_set(mask, i) {
+            *c += *a * scalar;
+        }
+    }
+}
+
+pub(super) fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+}
+
+pub(super) fn reduce(a: &[f32], op: ReduceOp, default_value: f32) -> f32 {
+    a.iter().fold(default_value, |result, a| match op {
+        ReduceOp::Sum => result + a,
+        ReduceOp::Product => result * a,
+        ReduceOp::Max => result.max(*a),
+        ReduceOp::Min => result.min(*a),
+    })
+}
+
+/// push a word for every 16 elements, the bits after the end are zero
+pub(super) fn compare(a: &[f32], b: &[f32], words: &mut Vec<u16>, op: CompareOp) {
+    let func = compare_function(op);
+
+    for (a, b) in a.chunks(16).zip(b.chunks(16)) {
+        let mut word = 0u16;
+
+        for (i, (a, b)) in a.iter().zip(b.iter()).enumerate() {
+            word |= (func(a, b) as u16) << i;
+        }
+
+        words.push(word);
+    }
+}
+
+/// push a word for every 16 elements, the bits after the end are zero
+pub(super) fn compare_scalar(a: &[f32], scalar: f32, words: &mut Vec<u16>, op: CompareOp) {
+    let func = compare_function(op);
+
+    for a in a.chunks(16) {
+        let mut word = 0u16;
+
+        for (i, a) in a.iter().enumerate() {
+            word |= (func(a, &scalar) as u16) << i;
+        }
+
+        words.push(word);
+    }
+}
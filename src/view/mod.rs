@@ -0,0 +1,889 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{borrow::Cow, ops::Range};
+
+use crate::{
+    error::{check_index, check_range, check_same_length, check_same_shape, unwrap_or_panic},
+    Array, Error, Mask,
+};
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+mod avx512f;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+use avx512f as kernels;
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+mod fallback;
+#[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+use fallback as kernels;
+
+#[derive(Clone, Copy)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Max,
+    Min,
+}
+
+#[derive(Clone, Copy)]
+enum UnaryOp {
+    Sqrt,
+    Square,
+    Abs,
+    Exp,
+}
+
+#[derive(Clone, Copy)]
+enum ReduceOp {
+    Sum,
+    Product,
+    Max,
+    Min,
+}
+
+#[derive(Clone, Copy)]
+enum CompareOp {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+}
+
+/// A read-only view of a rectangular region of an array
+///
+/// The operations work directly on the elements of the array. They are fastest if the region starts at a
+/// multiple of 16 columns, otherwise the elements are loaded unaligned.
+#[derive(Clone, Copy)]
+pub struct ArrayView<'a, const D: usize> {
+    elements: &'a [f32],
+    row_stride: usize,
+    start: [usize; D],
+    shape: [usize; D],
+}
+
+/// A mutable view of a rectangular region of an array, the elements outside of the region are never changed
+///
+/// The masks of the masked operations have the shape of the region, not of the array.
+pub struct ArrayViewMut<'a, const D: usize> {
+    elements: &'a mut [f32],
+    row_stride: usize,
+    start: [usize; D],
+    shape: [usize; D],
+}
+
+/// the number of rows of a region, the rows are counted over all axes except the last one
+fn row_count<const D: usize>(shape: &[usize; D]) -> usize {
+    shape[..D - 1].iter().product()
+}
+
+/// the offset of the first element of a row of a region, only one and two dimensions are supported
+fn row_offset<const D: usize>(start: &[usize; D], row_stride: usize, row: usize) -> usize {
+    if D == 1 {
+        start[0]
+    } else {
+        (start[0] + row) * row_stride + start[1]
+    }
+}
+
+/// the packed words of a mask with the same shape as a region, the masked operations only change the set elements
+fn mask_words<'m, const D: usize>(
+    shape: &[usize; D],
+    mask: Option<&'m Mask<D>>,
+) -> Option<Cow<'m, [u16]>> {
+    mask.map(|mask| {
+        unwrap_or_panic(check_same_shape(shape, mask.get_shape()));
+        mask.words()
+    })
+}
+
+/// the words of a row of the mask, each row starts with a new word
+fn mask_row<'m, const D: usize>(
+    words: &'m Option<Cow<'_, [u16]>>,
+    shape: &[usize; D],
+    row: usize,
+) -> Option<&'m [u16]> {
+    let words_per_row = shape[D - 1].div_ceil(16);
+
+    words
+        .as_deref()
+        .map(|words| &words[row * words_per_row..(row + 1) * words_per_row])
+}
+
+impl<const D: usize> Array<D> {
+    /// Create a view of the whole array
+    pub fn as_view(&self) -> ArrayView<'_, D> {
+        ArrayView {
+            elements: self.padded_elements(),
+            row_stride: self.row_stride(),
+            start: [0; D],
+            shape: self.shape,
+        }
+    }
+
+    /// Create a mutable view of the whole array
+    pub fn as_view_mut(&mut self) -> ArrayViewMut<'_, D> {
+        ArrayViewMut {
+            row_stride: self.row_stride(),
+            shape: self.shape,
+            elements: self.padded_elements_mut(),
+            start: [0; D],
+        }
+    }
+}
+
+impl Array<1> {
+    fn check_view(&self, range: &Range<usize>) -> Result<(), Error> {
+        check_range(0, range, self.shape[0])
+    }
+
+    /// Create a view of the elements in `range`, the range can be empty
+    #[track_caller]
+    pub fn view(&self, range: Range<usize>) -> ArrayView<'_, 1> {
+        unwrap_or_panic(self.check_view(&range));
+
+        ArrayView {
+            elements: self.padded_elements(),
+            row_stride: self.row_stride(),
+            start: [range.start],
+            shape: [range.len()],
+        }
+    }
+
+    /// Create a mutable view of the elements in `range`
+    #[track_caller]
+    pub fn view_mut(&mut self, range: Range<usize>) -> ArrayViewMut<'_, 1> {
+        unwrap_or_panic(self.check_view(&range));
+
+        ArrayViewMut {
+            row_stride: self.row_stride(),
+            elements: self.padded_elements_mut(),
+            start: [range.start],
+            shape: [range.len()],
+        }
+    }
+
+    /// fallible version of `view`
+    pub fn try_view(&self, range: Range<usize>) -> Result<ArrayView<'_, 1>, Error> {
+        self.check_view(&range)?;
+
+        Ok(self.view(range))
+    }
+
+    /// fallible version of `view_mut`
+    pub fn try_view_mut(&mut self, range: Range<usize>) -> Result<ArrayViewMut<'_, 1>, Error> {
+        self.check_view(&range)?;
+
+        Ok(self.view_mut(range))
+    }
+}
+
+impl Array<2> {
+    fn check_view(&self, rows: &Range<usize>, columns: &Range<usize>) -> Result<(), Error> {
+        check_range(0, rows, self.shape[0])?;
+        check_range(1, columns, self.shape[1])
+    }
+
+    /// Create a view of the elements in the `rows` and `columns`, the ranges can be empty
+    #[track_caller]
+    pub fn view(&self, rows: Range<usize>, columns: Range<usize>) -> ArrayView<'_, 2> {
+        unwrap_or_panic(self.check_view(&rows, &columns));
+
+        ArrayView {
+            elements: self.padded_elements(),
+            row_stride: self.row_stride(),
+            start: [rows.start, columns.start],
+            shape: [rows.len(), columns.len()],
+        }
+    }
+
+    /// Create a mutable view of the elements in the `rows` and `columns`
+    #[track_caller]
+    pub fn view_mut(&mut self, rows: Range<usize>, columns: Range<usize>) -> ArrayViewMut<'_, 2> {
+        unwrap_or_panic(self.check_view(&rows, &columns));
+
+        ArrayViewMut {
+            row_stride: self.row_stride(),
+            elements: self.padded_elements_mut(),
+            start: [rows.start, columns.start],
+            shape: [rows.len(), columns.len()],
+        }
+    }
+
+    /// fallible version of `view`
+    pub fn try_view(
+        &self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> Result<ArrayView<'_, 2>, Error> {
+        self.check_view(&rows, &columns)?;
+
+        Ok(self.view(rows, columns))
+    }
+
+    /// fallible version of `view_mut`
+    pub fn try_view_mut(
+        &mut self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> Result<ArrayViewMut<'_, 2>, Error> {
+        self.check_view(&rows, &columns)?;
+
+        Ok(self.view_mut(rows, columns))
+    }
+
+    /// Create a view of a row, the view starts at a register boundary so all loads are aligned
+    #[track_caller]
+    pub fn row(&self, row: usize) -> ArrayView<'_, 1> {
+        unwrap_or_panic(check_index(0, row, self.shape[0]));
+        let row_stride = self.row_stride();
+        let offset = row * row_stride;
+
+        ArrayView {
+            elements: &self.padded_elements()[offset..offset + row_stride],
+            row_stride,
+            start: [0],
+            shape: [self.shape[1]],
+        }
+    }
+
+    /// Create a mutable view of a row, the view starts at a register boundary so all loads are aligned
+    #[track_caller]
+    pub fn row_mut(&mut self, row: usize) -> ArrayViewMut<'_, 1> {
+        unwrap_or_panic(check_index(0, row, self.shape[0]));
+        let row_stride = self.row_stride();
+        let offset = row * row_stride;
+        let column_count = self.shape[1];
+
+        ArrayViewMut {
+            elements: &mut self.padded_elements_mut()[offset..offset + row_stride],
+            row_stride,
+            start: [0],
+            shape: [column_count],
+        }
+    }
+
+    /// Iterate over views of the rows
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = ArrayView<'_, 1>> {
+        let row_stride = self.row_stride();
+        let row_count = self.shape[0];
+        let column_count = self.shape[1];
+        // without columns there are no elements to split, so every row is an empty slice
+        let mut rows = self.padded_elements().chunks(row_stride.max(1));
+
+        (0..row_count).map(move |_| ArrayView {
+            elements: rows.next().unwrap_or_default(),
+            row_stride,
+            start: [0],
+            shape: [column_count],
+        })
+    }
+
+    /// Iterate over mutable views of the rows
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = ArrayViewMut<'_, 1>> {
+        let row_stride = self.row_stride();
+        let row_count = self.shape[0];
+        let column_count = self.shape[1];
+        let mut rows = self.padded_elements_mut().chunks_mut(row_stride.max(1));
+
+        (0..row_count).map(move |_| ArrayViewMut {
+            elements: rows.next().unwrap_or_default(),
+            row_stride,
+            start: [0],
+            shape: [column_count],
+        })
+    }
+}
+
+impl<'a, const D: usize> ArrayView<'a, D> {
+    /// a view of all elements of padded rows, e.g. of a memory-mapped file
+    #[cfg(all(feature = "mmap", target_arch = "x86_64", target_feature = "avx512f"))]
+    pub(crate) fn from_padded_elements(
+        elements: &'a [f32],
+        row_stride: usize,
+        shape: [usize; D],
+    ) -> Self {
+        ArrayView {
+            elements,
+            row_stride,
+            start: [0; D],
+            shape,
+        }
+    }
+
+    pub fn get_shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// the elements of a row of the region
+    fn row(&self, row: usize) -> &'a [f32] {
+        let offset = row_offset(&self.start, self.row_stride, row);
+
+        &self.elements[offset..offset + self.shape[D - 1]]
+    }
+
+    /// Copy the elements of the view into a new array
+    pub fn to_array(&self) -> Array<D> {
+        let mut array = Array::zeros(&self.shape);
+
+        for row in 0..row_count(&self.shape) {
+            array.row_elements_mut(row).copy_from_slice(self.row(row));
+        }
+
+        array
+    }
+
+    fn binary(&self, other: &ArrayView<D>, op: BinaryOp) -> Array<D> {
+        unwrap_or_panic(check_same_shape(&self.shape, &other.shape));
+        let mut output = Array::zeros(&self.shape);
+
+        for row in 0..row_count(&self.shape) {
+            kernels::binary(
+                self.row(row),
+                other.row(row),
+                output.row_elements_mut(row),
+                op,
+            );
+        }
+
+        output
+    }
+
+    fn binary_scalar(&self, scalar: f32, op: BinaryOp) -> Array<D> {
+        let mut output = Array::zeros(&self.shape);
+
+        for row in 0..row_count(&self.shape) {
+            kernels::binary_scalar(self.row(row), scalar, output.row_elements_mut(row), op);
+        }
+
+        output
+    }
+
+    fn unary(&self, op: UnaryOp) -> Array<D> {
+        let mut output = self.to_array();
+
+        for row in 0..row_count(&self.shape) {
+            kernels::unary_in_place(output.row_elements_mut(row), op, None);
+        }
+
+        output
+    }
+
+    fn reduce(&self, op: ReduceOp, default_value: f32) -> f32 {
+        let mut result = default_value;
+
+        for row in 0..row_count(&self.shape) {
+            let row_result = kernels::reduce(self.row(row), op, default_value);
+
+            result = match op {
+                ReduceOp::Sum => result + row_result,
+                ReduceOp::Product => result * row_result,
+                ReduceOp::Max => result.max(row_result),
+                ReduceOp::Min => result.min(row_result),
+            };
+        }
+
+        result
+    }
+
+    fn compare(&self, other: &ArrayView<D>, op: CompareOp) -> Mask<D> {
+        unwrap_or_panic(check_same_shape(&self.shape, &other.shape));
+        let mut words = Vec::new();
+
+        for row in 0..row_count(&self.shape) {
+            kernels::compare(self.row(row), other.row(row), &mut words, op);
+        }
+
+        Mask::from_words(words, self.shape)
+    }
+
+    fn compare_scalar(&self, scalar: f32, op: CompareOp) -> Mask<D> {
+        let mut words = Vec::new();
+
+        for row in 0..row_count(&self.shape) {
+            kernels::compare_scalar(self.row(row), scalar, &mut words, op);
+        }
+
+        Mask::from_words(words, self.shape)
+    }
+
+    pub fn add(&self, other: &ArrayView<D>) -> Array<D> {
+        self.binary(other, BinaryOp::Add)
+    }
+
+    pub fn sub(&self, other: &ArrayView<D>) -> Array<D> {
+        self.binary(other, BinaryOp::Sub)
+    }
+
+    pub fn mul(&self, other: &ArrayView<D>) -> Array<D> {
+        self.binary(other, BinaryOp::Mul)
+    }
+
+    pub fn div(&self, other: &ArrayView<D>) -> Array<D> {
+        self.binary(other, BinaryOp::Div)
+    }
+
+    pub fn max(&self, other: &ArrayView<D>) -> Array<D> {
+        self.binary(other, BinaryOp::Max)
+    }
+
+    pub fn min(&self, other: &ArrayView<D>) -> Array<D> {
+        self.binary(other, BinaryOp::Min)
+    }
+
+    pub fn add_scalar(&self, scalar: f32) -> Array<D> {
+        self.binary_scalar(scalar, BinaryOp::Add)
+    }
+
+    pub fn sub_scalar(&self, scalar: f32) -> Array<D> {
+        self.binary_scalar(scalar, BinaryOp::Sub)
+    }
+
+    pub fn mul_scalar(&self, scalar: f32) -> Array<D> {
+        self.binary_scalar(scalar, BinaryOp::Mul)
+    }
+
+    pub fn div_scalar(&self, scalar: f32) -> Array<D> {
+        self.binary_scalar(scalar, BinaryOp::Div)
+    }
+
+    pub fn max_scalar(&self, scalar: f32) -> Array<D> {
+        self.binary_scalar(scalar, BinaryOp::Max)
+    }
+
+    pub fn min_scalar(&self, scalar: f32) -> Array<D> {
+        self.binary_scalar(scalar, BinaryOp::Min)
+    }
+
+    pub fn sqrt(&self) -> Array<D> {
+        self.unary(UnaryOp::Sqrt)
+    }
+
+    pub fn square(&self) -> Array<D> {
+        self.unary(UnaryOp::Square)
+    }
+
+    pub fn abs(&self) -> Array<D> {
+        self.unary(UnaryOp::Abs)
+    }
+
+    pub fn exp(&self) -> Array<D> {
+        self.unary(UnaryOp::Exp)
+    }
+
+    /// Compute `self + a * b` into a new array
+    pub fn fmadd(&self, a: &ArrayView<D>, b: &ArrayView<D>) -> Array<D> {
+        unwrap_or_panic(check_same_shape(&self.shape, &a.shape));
+        unwrap_or_panic(check_same_shape(&self.shape, &b.shape));
+        let mut output = self.to_array();
+
+        for row in 0..row_count(&self.shape) {
+            kernels::fmadd_in_place(output.row_elements_mut(row), a.row(row), b.row(row), None);
+        }
+
+        output
+    }
+
+    /// Compute `self + a * scalar` into a new array
+    pub fn fmadd_scalar(&self, a: &ArrayView<D>, scalar: f32) -> Array<D> {
+        unwrap_or_panic(check_same_shape(&self.shape, &a.shape));
+        let mut output = self.to_array();
+
+        for row in 0..row_count(&self.shape) {
+            kernels::fmadd_scalar_in_place(output.row_elements_mut(row), a.row(row), scalar, None);
+        }
+
+        output
+    }
+
+    pub fn sum(&self) -> f32 {
+        self.reduce(ReduceOp::Sum, 0.0)
+    }
+
+    pub fn product(&self) -> f32 {
+        self.reduce(ReduceOp::Product, 1.0)
+    }
+
+    pub fn max_reduce(&self) -> f32 {
+        self.reduce(ReduceOp::Max, f32::MIN)
+    }
+
+    pub fn min_reduce(&self) -> f32 {
+        self.reduce(ReduceOp::Min, f32::MAX)
+    }
+
+    pub fn compare_equal(&self, other: &ArrayView<D>) -> Mask<D> {
+        self.compare(other, CompareOp::Equal)
+    }
+
+    pub fn compare_not_equal(&self, other: &ArrayView<D>) -> Mask<D> {
+        self.compare(other, CompareOp::NotEqual)
+    }
+
+    pub fn compare_greater_than(&self, other: &ArrayView<D>) -> Mask<D> {
+        self.compare(other, CompareOp::GreaterThan)
+    }
+
+    pub fn compare_greater_than_or_equal(&self, other: &ArrayView<D>) -> Mask<D> {
+        self.compare(other, CompareOp::GreaterThanOrEqual)
+    }
+
+    pub fn compare_less_than(&self, other: &ArrayView<D>) -> Mask<D> {
+        self.compare(other, CompareOp::LessThan)
+    }
+
+    pub fn compare_less_than_or_equal(&self, other: &ArrayView<D>) -> Mask<D> {
+        self.compare(other, CompareOp::LessThanOrEqual)
+    }
+
+    pub fn compare_scalar_equal(&self, scalar: f32) -> Mask<D> {
+        self.compare_scalar(scalar, CompareOp::Equal)
+    }
+
+    pub fn compare_scalar_not_equal(&self, scalar: f32) -> Mask<D> {
+        self.compare_scalar(scalar, CompareOp::NotEqual)
+    }
+
+    pub fn compare_scalar_greater_than(&self, scalar: f32) -> Mask<D> {
+        self.compare_scalar(scalar, CompareOp::GreaterThan)
+    }
+
+    pub fn compare_scalar_greater_than_or_equal(&self, scalar: f32) -> Mask<D> {
+        self.compare_scalar(scalar, CompareOp::GreaterThanOrEqual)
+    }
+
+    pub fn compare_scalar_less_than(&self, scalar: f32) -> Mask<D> {
+        self.compare_scalar(scalar, CompareOp::LessThan)
+    }
+
+    pub fn compare_scalar_less_than_or_equal(&self, scalar: f32) -> Mask<D> {
+        self.compare_scalar(scalar, CompareOp::LessThanOrEqual)
+    }
+}
+
+impl ArrayView<'_, 1> {
+    #[track_caller]
+    pub fn get(&self, index: usize) -> f32 {
+        unwrap_or_panic(check_index(0, index, self.shape[0]));
+
+        self.row(0)[index]
+    }
+
+    pub fn dot_product(&self, other: &ArrayView<1>) -> f32 {
+        unwrap_or_panic(check_same_shape(&self.shape, &other.shape));
+
+        kernels::dot(self.row(0), other.row(0))
+    }
+}
+
+impl ArrayView<'_, 2> {
+    #[track_caller]
+    pub fn get(&self, row: usize, column: usize) -> f32 {
+        unwrap_or_panic(check_index(0, row, self.shape[0]));
+        unwrap_or_panic(check_index(1, column, self.shape[1]));
+
+        self.row(row)[column]
+    }
+
+    /// Multiply the rows of the view with a vector, the result has one element per row
+    pub fn vector_multiplication(&self, vector: &ArrayView<1>) -> Array<1> {
+        unwrap_or_panic(check_same_length(self.shape[1], vector.shape[0]));
+        let mut output = Array::zeros(&[self.shape[0]]);
+
+        for (row, element) in output.row_elements_mut(0).iter_mut().enumerate() {
+            *element = kernels::dot(self.row(row), vector.row(0));
+        }
+
+        output
+    }
+}
+
+impl<const D: usize> ArrayViewMut<'_, D> {
+    pub fn get_shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// Create a read-only view of the same region
+    pub fn as_view(&self) -> ArrayView<'_, D> {
+        ArrayView {
+            elements: &*self.elements,
+            row_stride: self.row_stride,
+            start: self.start,
+            shape: self.shape,
+        }
+    }
+
+    /// the elements of a row of the region
+    fn row_mut(&mut self, row: usize) -> &mut [f32] {
+        let offset = row_offset(&self.start, self.row_stride, row);
+
+        &mut self.elements[offset..offset + self.shape[D - 1]]
+    }
+
+    fn binary_in_place(&mut self, other: &ArrayView<D>, op: BinaryOp, mask: Option<&Mask<D>>) {
+        unwrap_or_panic(check_same_shape(&self.shape, &other.shape));
+        let shape = self.shape;
+        let words = mask_words(&shape, mask);
+
+        for row in 0..row_count(&shape) {
+            let mask = mask_row(&words, &shape, row);
+            kernels::binary_in_place(self.row_mut(row), other.row(row), op, mask);
+        }
+    }
+
+    fn binary_scalar_in_place(&mut self, scalar: f32, op: BinaryOp, mask: Option<&Mask<D>>) {
+        let shape = self.shape;
+        let words = mask_words(&shape, mask);
+
+        for row in 0..row_count(&shape) {
+            let mask = mask_row(&words, &shape, row);
+            kernels::binary_scalar_in_place(self.row_mut(row), scalar, op, mask);
+        }
+    }
+
+    fn unary_in_place(&mut self, op: UnaryOp, mask: Option<&Mask<D>>) {
+        let shape = self.shape;
+        let words = mask_words(&shape, mask);
+
+        for row in 0..row_count(&shape) {
+            let mask = mask_row(&words, &shape, row);
+            kernels::unary_in_place(self.row_mut(row), op, mask);
+        }
+    }
+
+    fn fmadd_in_place_with_mask(
+        &mut self,
+        a: &ArrayView<D>,
+        b: &ArrayView<D>,
+        mask: Option<&Mask<D>>,
+    ) {
+        unwrap_or_panic(check_same_shape(&self.shape, &a.shape));
+        unwrap_or_panic(check_same_shape(&self.shape, &b.shape));
+        let shape = self.shape;
+        let words = mask_words(&shape, mask);
+
+        for row in 0..row_count(&shape) {
+            let mask = mask_row(&words, &shape, row);
+            kernels::fmadd_in_place(self.row_mut(row), a.row(row), b.row(row), mask);
+        }
+    }
+
+    fn fmadd_scalar_in_place_with_mask(
+        &mut self,
+        a: &ArrayView<D>,
+        scalar: f32,
+        mask: Option<&Mask<D>>,
+    ) {
+        unwrap_or_panic(check_same_shape(&self.shape, &a.shape));
+        let shape = self.shape;
+        let words = mask_words(&shape, mask);
+
+        for row in 0..row_count(&shape) {
+            let mask = mask_row(&words, &shape, row);
+            kernels::fmadd_scalar_in_place(self.row_mut(row), a.row(row), scalar, mask);
+        }
+    }
+
+    pub fn set_all(&mut self, value: f32) {
+        for row in 0..row_count(&self.shape) {
+            self.row_mut(row).fill(value);
+        }
+    }
+
+    /// Copy the elements of another view with the same shape into this view
+    pub fn copy(&mut self, other: &ArrayView<D>) {
+        unwrap_or_panic(check_same_shape(&self.shape, &other.shape));
+
+        for row in 0..row_count(&self.shape) {
+            self.row_mut(row).copy_from_slice(other.row(row));
+        }
+    }
+
+    pub fn add_in_place(&mut self, other: &ArrayView<D>) {
+        self.binary_in_place(other, BinaryOp::Add, None);
+    }
+
+    pub fn add_in_place_masked(&mut self, other: &ArrayView<D>, mask: &Mask<D>) {
+        self.binary_in_place(other, BinaryOp::Add, Some(mask));
+    }
+
+    pub fn sub_in_place(&mut self, other: &ArrayView<D>) {
+        self.binary_in_place(other, BinaryOp::Sub, None);
+    }
+
+    pub fn sub_in_place_masked(&mut self, other: &ArrayView<D>, mask: &Mask<D>) {
+        self.binary_in_place(other, BinaryOp::Sub, Some(mask));
+    }
+
+    pub fn mul_in_place(&mut self, other: &ArrayView<D>) {
+        self.binary_in_place(other, BinaryOp::Mul, None);
+    }
+
+    pub fn mul_in_place_masked(&mut self, other: &ArrayView<D>, mask: &Mask<D>) {
+        self.binary_in_place(other, BinaryOp::Mul, Some(mask));
+    }
+
+    pub fn div_in_place(&mut self, other: &ArrayView<D>) {
+        self.binary_in_place(other, BinaryOp::Div, None);
+    }
+
+    pub fn div_in_place_masked(&mut self, other: &ArrayView<D>, mask: &Mask<D>) {
+        self.binary_in_place(other, BinaryOp::Div, Some(mask));
+    }
+
+    pub fn max_in_place(&mut self, other: &ArrayView<D>) {
+        self.binary_in_place(other, BinaryOp::Max, None);
+    }
+
+    pub fn max_in_place_masked(&mut self, other: &ArrayView<D>, mask: &Mask<D>) {
+        self.binary_in_place(other, BinaryOp::Max, Some(mask));
+    }
+
+    pub fn min_in_place(&mut self, other: &ArrayView<D>) {
+        self.binary_in_place(other, BinaryOp::Min, None);
+    }
+
+    pub fn min_in_place_masked(&mut self, other: &ArrayView<D>, mask: &Mask<D>) {
+        self.binary_in_place(other, BinaryOp::Min, Some(mask));
+    }
+
+    pub fn add_scalar_in_place(&mut self, scalar: f32) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Add, None);
+    }
+
+    pub fn add_scalar_in_place_masked(&mut self, scalar: f32, mask: &Mask<D>) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Add, Some(mask));
+    }
+
+    pub fn sub_scalar_in_place(&mut self, scalar: f32) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Sub, None);
+    }
+
+    pub fn sub_scalar_in_place_masked(&mut self, scalar: f32, mask: &Mask<D>) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Sub, Some(mask));
+    }
+
+    pub fn mul_scalar_in_place(&mut self, scalar: f32) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Mul, None);
+    }
+
+    pub fn mul_scalar_in_place_masked(&mut self, scalar: f32, mask: &Mask<D>) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Mul, Some(mask));
+    }
+
+    pub fn div_scalar_in_place(&mut self, scalar: f32) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Div, None);
+    }
+
+    pub fn div_scalar_in_place_masked(&mut self, scalar: f32, mask: &Mask<D>) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Div, Some(mask));
+    }
+
+    pub fn max_scalar_in_place(&mut self, scalar: f32) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Max, None);
+    }
+
+    pub fn max_scalar_in_place_masked(&mut self, scalar: f32, mask: &Mask<D>) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Max, Some(mask));
+    }
+
+    pub fn min_scalar_in_place(&mut self, scalar: f32) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Min, None);
+    }
+
+    pub fn min_scalar_in_place_masked(&mut self, scalar: f32, mask: &Mask<D>) {
+        self.binary_scalar_in_place(scalar, BinaryOp::Min, Some(mask));
+    }
+
+    pub fn sqrt_in_place(&mut self) {
+        self.unary_in_place(UnaryOp::Sqrt, None);
+    }
+
+    pub fn sqrt_in_place_masked(&mut self, mask: &Mask<D>) {
+        self.unary_in_place(UnaryOp::Sqrt, Some(mask));
+    }
+
+    pub fn square_in_place(&mut self) {
+        self.unary_in_place(UnaryOp::Square, None);
+    }
+
+    pub fn square_in_place_masked(&mut self, mask: &Mask<D>) {
+        self.unary_in_place(UnaryOp::Square, Some(mask));
+    }
+
+    pub fn abs_in_place(&mut self) {
+        self.unary_in_place(UnaryOp::Abs, None);
+    }
+
+    pub fn abs_in_place_masked(&mut self, mask: &Mask<D>) {
+        self.unary_in_place(UnaryOp::Abs, Some(mask));
+    }
+
+    pub fn exp_in_place(&mut self) {
+        self.unary_in_place(UnaryOp::Exp, None);
+    }
+
+    /// Compute `self + a * b` in place
+    pub fn fmadd_in_place(&mut self, a: &ArrayView<D>, b: &ArrayView<D>) {
+        self.fmadd_in_place_with_mask(a, b, None);
+    }
+
+    pub fn fmadd_in_place_masked(&mut self, a: &ArrayView<D>, b: &ArrayView<D>, mask: &Mask<D>) {
+        self.fmadd_in_place_with_mask(a, b, Some(mask));
+    }
+
+    /// Compute `self + a * scalar` in place
+    pub fn fmadd_scalar_in_place(&mut self, a: &ArrayView<D>, scalar: f32) {
+        self.fmadd_scalar_in_place_with_mask(a, scalar, None);
+    }
+
+    pub fn fmadd_scalar_in_place_masked(&mut self, a: &ArrayView<D>, scalar: f32, mask: &Mask<D>) {
+        self.fmadd_scalar_in_place_with_mask(a, scalar, Some(mask));
+    }
+}
+
+impl ArrayViewMut<'_, 1> {
+    #[track_caller]
+    pub fn get(&self, index: usize) -> f32 {
+        self.as_view().get(index)
+    }
+
+    #[track_caller]
+    pub fn set(&mut self, index: usize, value: f32) {
+        unwrap_or_panic(check_index(0, index, self.shape[0]));
+
+        self.row_mut(0)[index] = value;
+    }
+}
+
+impl ArrayViewMut<'_, 2> {
+    #[track_caller]
+    pub fn get(&self, row: usize, column: usize) -> f32 {
+        self.as_view().get(row, column)
+    }
+
+    #[track_caller]
+    pub fn set(&mut self, row: usize, column: usize, value: f32) {
+        unwrap_or_panic(check_index(0, row, self.shape[0]));
+        unwrap_or_panic(check_index(1, column, self.shape[1]));
+
+        self.row_mut(row)[column] = value;
+    }
+}
@@ -0,0 +1,105 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+use std::arch::x86_64::{__m512, __mmask16};
+
+use crate::{Array, Mask};
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+type ArrayBuffer = Vec<__m512>;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+type MaskBuffer = Vec<__mmask16>;
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+type ArrayBuffer = Vec<f32>;
+#[cfg(not(all(target_arch = "x86_64", target_feature = "avx512f")))]
+type MaskBuffer = Vec<bool>;
+
+/// A pool of buffers for temporary arrays and masks
+///
+/// Arrays and masks given back with `recycle_array` and `recycle_mask` keep their allocation, which is reused for
+/// the next array or mask of any shape that fits into it. Together with the `_out_of_place` functions, a chain of
+/// operations that runs repeatedly doesn't allocate after the first run.
+#[derive(Default)]
+pub struct Workspace {
+    arrays: Vec<ArrayBuffer>,
+    masks: Vec<MaskBuffer>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// an array with the given shape, the values of the elements are unspecified
+    pub fn array<const D: usize>(&mut self, shape: &[usize; D]) -> Array<D> {
+        let length = Array::<D>::buffer_length(shape);
+
+        Array::from_buffer(shape, take_buffer(&mut self.arrays, length))
+    }
+
+    /// an array with the given shape and all elements set to `value`
+    pub fn array_from_value<const D: usize>(&mut self, shape: &[usize; D], value: f32) -> Array<D> {
+        let mut array = self.array(shape);
+        array.set_all(value);
+
+        array
+    }
+
+    /// a mask with the given shape and all elements false
+    pub fn mask<const D: usize>(&mut self, shape: &[usize; D]) -> Mask<D> {
+        let length = Mask::<D>::buffer_length(shape);
+
+        Mask::from_buffer(shape, take_buffer(&mut self.masks, length))
+    }
+
+    /// give an array back to the workspace, so that its allocation can be reused
+    pub fn recycle_array<const D: usize>(&mut self, array: Array<D>) {
+        self.arrays.push(array.data);
+    }
+
+    /// give a mask back to the workspace, so that its allocation can be reused
+    pub fn recycle_mask<const D: usize>(&mut self, mask: Mask<D>) {
+        self.masks.push(mask.into_buffer());
+    }
+
+    /// the number of buffers that are ready to be reused
+    pub fn buffer_count(&self) -> usize {
+        self.arrays.len() + self.masks.len()
+    }
+
+    /// free all buffers
+    pub fn clear(&mut self) {
+        self.arrays.clear();
+        self.masks.clear();
+    }
+}
+
+/// take the smallest buffer with a capacity of at least `length`, or a new one if there is none
+fn take_buffer<T>(buffers: &mut Vec<Vec<T>>, length: usize) -> Vec<T> {
+    let best = buffers
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| b.capacity() >= length)
+        .min_by_key(|(_, b)| b.capacity())
+        .map(|(i, _)| i);
+
+    match best {
+        Some(i) => buffers.swap_remove(i),
+        None => Vec::with_capacity(length),
+    }
+}
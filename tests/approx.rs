@@ -0,0 +1,165 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{
+    assert_array_approx_eq, debug_assert_array_approx_eq, Array, Difference, Mask, Tolerance,
+};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn equal1d() {
+    for i in 0..64 {
+        let a: Array<1> = get_random_f32_vec(0, i).into();
+        let b: Array<1> = get_random_f32_vec(0, i).into();
+
+        assert_eq!(a, b);
+
+        for j in 0..i {
+            let mut c = b.clone();
+            c.set(j, c.get(j) + 1.0);
+
+            assert_ne!(a, c);
+        }
+    }
+}
+
+#[test]
+fn equal2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let a = Array::from_vec(&get_random_f32_vec(0, i * j), [i, j]);
+            let mut b = a.clone();
+
+            assert_eq!(a, b);
+
+            b.set(i - 1, j - 1, 100.0);
+            assert_ne!(a, b);
+        }
+    }
+}
+
+#[test]
+fn equal_ignores_padding() {
+    let a = Array::from_vec(&vec![1.0; 2 * 17], [2, 17]);
+    // adding to the whole array also changes the padding, which must not matter
+    let mut b = Array::from_vec(&vec![0.0; 2 * 17], [2, 17]);
+    b.add_scalar_in_place(1.0);
+
+    assert_eq!(a, b);
+}
+
+#[test]
+fn equal_different_shapes() {
+    let a = Array::from_vec(&vec![1.0; 6], [2, 3]);
+    let b = Array::from_vec(&vec![1.0; 6], [3, 2]);
+
+    assert_ne!(a, b);
+}
+
+#[test]
+fn equal_nan() {
+    let a: Array<1> = vec![f32::NAN].into();
+
+    assert_ne!(a, a.clone());
+}
+
+#[test]
+fn equal_mask() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_bool_vec(0, i * j);
+            let a = Mask::from_vec(&data, [i, j]);
+            let b = Mask::from_vec(&data, [i, j]);
+
+            assert_eq!(a, b);
+
+            let mut data = data;
+            data[i * j - 1] = !data[i * j - 1];
+            assert_ne!(a, Mask::from_vec(&data, [i, j]));
+        }
+    }
+}
+
+#[test]
+fn tolerances() {
+    assert!(Tolerance::Absolute(0.1).is_satisfied(1.0, 1.05));
+    assert!(!Tolerance::Absolute(0.1).is_satisfied(1.0, 1.2));
+    assert!(Tolerance::Relative(0.01).is_satisfied(1000.0, 1005.0));
+    assert!(!Tolerance::Relative(0.01).is_satisfied(1.0, 1.05));
+    assert!(Tolerance::Ulps(1).is_satisfied(1.0, f32::from_bits(1.0f32.to_bits() + 1)));
+    assert!(!Tolerance::Ulps(1).is_satisfied(1.0, f32::from_bits(1.0f32.to_bits() + 2)));
+    assert!(Tolerance::Ulps(0).is_satisfied(0.0, -0.0));
+    assert!(!Tolerance::Ulps(u32::MAX).is_satisfied(1.0, -1.0));
+    assert!(Tolerance::Absolute(0.0).is_satisfied(f32::INFINITY, f32::INFINITY));
+    assert!(!Tolerance::Absolute(f32::INFINITY).is_satisfied(f32::INFINITY, 1.0));
+    assert!(!Tolerance::Absolute(f32::INFINITY).is_satisfied(f32::NAN, f32::NAN));
+}
+
+#[test]
+fn approximately_equal() {
+    let a = Array::from_vec(&vec![1.0, 2.0, 3.0, 4.0], [2, 2]);
+    let b = Array::from_vec(&vec![1.0, 2.0, 3.001, 4.0], [2, 2]);
+
+    assert!(a.abs_eq(&b, 0.01));
+    assert!(!a.abs_eq(&b, 0.0001));
+    assert!(a.rel_eq(&b, 0.001));
+    assert!(!a.rel_eq(&b, 0.0001));
+    assert!(!a.ulps_eq(&b, 4));
+    assert!(a.ulps_eq(&a.clone(), 0));
+    assert!(!a.abs_eq(&Array::from_vec(&vec![1.0; 4], [4, 1]), 10.0));
+
+    assert_eq!(
+        a.first_difference(&b, Tolerance::Absolute(0.0001)),
+        Some(Difference {
+            index: [1, 0],
+            left: 3.0,
+            right: 3.001
+        })
+    );
+}
+
+#[test]
+fn assert_macro() {
+    let a: Array<1> = get_random_f32_vec(0, 20).into();
+    let mut b = a.clone();
+    b.add_scalar_in_place(1e-6);
+
+    assert_array_approx_eq!(a, b, abs <= 1e-4);
+    assert_array_approx_eq!(a, b, rel <= 1e-3);
+    assert_array_approx_eq!(a, a.clone(), ulps <= 0);
+    assert_array_approx_eq!(a, b, Tolerance::Absolute(1e-4));
+    debug_assert_array_approx_eq!(a, b, abs <= 1e-4);
+}
+
+#[test]
+#[should_panic(expected = "at index [1, 0]: 3 != 3.5")]
+fn assert_macro_fails() {
+    let a = Array::from_vec(&vec![1.0, 2.0, 3.0, 4.0], [2, 2]);
+    let b = Array::from_vec(&vec![1.0, 2.0, 3.5, 4.5], [2, 2]);
+
+    assert_array_approx_eq!(a, b, abs <= 0.1);
+}
+
+#[test]
+#[should_panic(expected = "the shapes don't match")]
+fn assert_macro_different_shapes() {
+    let a = Array::<1>::zeros(&[3]);
+    let b = Array::<1>::zeros(&[4]);
+
+    assert_array_approx_eq!(a, b, abs <= 0.1);
+}
@@ -0,0 +1,231 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![cfg(feature = "arrow")]
+
+mod utils;
+
+use std::sync::Arc;
+
+use arrow_array::{Array as _, BooleanArray, FixedSizeListArray, Float32Array, Int32Array};
+use arrow_buffer::NullBuffer;
+use arrow_schema::{DataType, Field};
+use fast_arrays::{Array, Error, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn float32_round_trip() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let array = Array::<1>::try_from(&Float32Array::from(data.clone())).unwrap();
+
+        let converted = Float32Array::from(&array);
+        assert_eq!(converted.values().to_vec(), data);
+        assert_eq!(converted.null_count(), 0);
+
+        let array: Vec<f32> = array.into();
+        assert_eq!(array, data);
+    }
+}
+
+#[test]
+fn float32_sliced() {
+    let data = get_random_f32_vec(0, 40);
+    let sliced = Float32Array::from(data.clone()).slice(3, 30);
+
+    let array: Vec<f32> = Array::<1>::try_from(&sliced).unwrap().into();
+    assert_eq!(array, data[3..33]);
+}
+
+#[test]
+fn float32_nulls() {
+    for i in 1..64 {
+        let data = get_random_f32_vec(0, i);
+        let valid = get_random_bool_vec(1, i);
+        let arrow = Float32Array::new(data.clone().into(), Some(NullBuffer::from(valid.clone())));
+        let null_count = valid.iter().filter(|v| !**v).count();
+
+        if null_count > 0 {
+            assert_eq!(
+                Array::<1>::try_from(&arrow).err().unwrap(),
+                Error::NullValues { count: null_count }
+            );
+        }
+
+        let (array, validity) = Array::<1>::from_arrow_with_validity(&arrow);
+        validity.assert_invariants_satisfied();
+
+        let converted = array.to_arrow_with_validity(&validity);
+        assert_eq!(converted, arrow);
+
+        let validity: Vec<bool> = validity.into();
+        assert_eq!(validity, valid);
+
+        let array: Vec<f32> = array.into();
+        assert_eq!(array, data);
+    }
+}
+
+#[test]
+#[should_panic]
+fn float32_validity_wrong_shape() {
+    let array = Array::<1>::zeros(&[5]);
+    array.to_arrow_with_validity(&Mask::<1>::zeros(&[4]));
+}
+
+#[test]
+fn boolean_round_trip() {
+    for i in 0..64 {
+        let data = get_random_bool_vec(0, i);
+        let mask = Mask::<1>::try_from(&BooleanArray::from(data.clone())).unwrap();
+        mask.assert_invariants_satisfied();
+
+        let converted = BooleanArray::from(&mask);
+        assert_eq!(converted, BooleanArray::from(data.clone()));
+
+        let mask: Vec<bool> = mask.into();
+        assert_eq!(mask, data);
+    }
+}
+
+#[test]
+fn boolean_sliced() {
+    for offset in 0..20 {
+        let data = get_random_bool_vec(0, 100);
+        let sliced = BooleanArray::from(data.clone()).slice(offset, 37);
+
+        let mask = Mask::<1>::try_from(&sliced).unwrap();
+        mask.assert_invariants_satisfied();
+
+        let mask: Vec<bool> = mask.into();
+        assert_eq!(mask, data[offset..offset + 37]);
+    }
+}
+
+#[test]
+fn boolean_nulls() {
+    let arrow = BooleanArray::from(vec![Some(true), None, Some(false), Some(true)]);
+
+    assert_eq!(
+        Mask::<1>::try_from(&arrow).err().unwrap(),
+        Error::NullValues { count: 1 }
+    );
+
+    let (mask, validity) = Mask::<1>::from_arrow_with_validity(&arrow);
+    assert_eq!(mask.to_arrow_with_validity(&validity), arrow);
+
+    let validity: Vec<bool> = validity.into();
+    assert_eq!(validity, [true, false, true, true]);
+}
+
+fn list_array(data: Vec<f32>, columns: usize, nulls: Option<NullBuffer>) -> FixedSizeListArray {
+    FixedSizeListArray::new(
+        Arc::new(Field::new("item", DataType::Float32, true)),
+        columns as i32,
+        Arc::new(Float32Array::from(data)),
+        nulls,
+    )
+}
+
+#[test]
+fn fixed_size_list_round_trip() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_f32_vec(0, i * j);
+            let array = Array::<2>::try_from(&list_array(data.clone(), j, None)).unwrap();
+            assert_eq!(array.get_shape(), [i, j]);
+
+            let converted = FixedSizeListArray::from(&array);
+            assert_eq!(converted.len(), i);
+            assert_eq!(converted.value_length(), j as i32);
+
+            let converted = Array::<2>::try_from(&converted).unwrap();
+            let converted: Vec<f32> = converted.into();
+            assert_eq!(converted, data);
+        }
+    }
+}
+
+#[test]
+fn fixed_size_list_sliced() {
+    let data = get_random_f32_vec(0, 10 * 20);
+    let sliced = list_array(data.clone(), 20, None).slice(2, 5);
+
+    let array: Vec<f32> = Array::<2>::try_from(&sliced).unwrap().into();
+    assert_eq!(array, data[40..140]);
+}
+
+#[test]
+fn fixed_size_list_nulls() {
+    let values = Float32Array::from(vec![
+        Some(1.0),
+        None,
+        Some(3.0),
+        Some(4.0),
+        Some(5.0),
+        Some(6.0),
+    ]);
+    let list = FixedSizeListArray::new(
+        Arc::new(Field::new("item", DataType::Float32, true)),
+        2,
+        Arc::new(values),
+        Some(NullBuffer::from(vec![true, true, false])),
+    );
+
+    assert_eq!(
+        Array::<2>::try_from(&list).err().unwrap(),
+        Error::NullValues { count: 3 }
+    );
+
+    let (array, validity) = Array::<2>::from_arrow_with_validity(&list).unwrap();
+    assert_eq!(array.get_shape(), [3, 2]);
+    assert_eq!(array.get(1, 0), 3.0);
+
+    let expected = [[true, false], [true, true], [false, false]];
+
+    for (r, row) in expected.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            assert_eq!(validity.get(r, c), *value);
+        }
+    }
+
+    let converted = array.to_arrow_with_validity(&validity);
+    let (_, round_trip) = Array::<2>::from_arrow_with_validity(&converted).unwrap();
+
+    for (r, row) in expected.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            assert_eq!(round_trip.get(r, c), *value);
+        }
+    }
+}
+
+#[test]
+fn fixed_size_list_wrong_type() {
+    let list = FixedSizeListArray::new(
+        Arc::new(Field::new("item", DataType::Int32, true)),
+        2,
+        Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+        None,
+    );
+
+    assert_eq!(
+        Array::<2>::try_from(&list).err().unwrap(),
+        Error::UnsupportedDtype {
+            expected: "Float32",
+            found: "Int32".to_string()
+        }
+    );
+}
@@ -17,6 +17,7 @@ limitations under the License.
 mod utils;
 
 use fast_arrays::{Array, Mask};
+use rstest::rstest;
 use utils::{get_random_bool_vec, get_random_f32_vec};
 
 #[test]
@@ -79,6 +80,31 @@ fn serde2d() {
     }
 }
 
+#[rstest]
+#[case::too_few_dimensions(r#"{"data":[1.0,2.0],"shape":[2]}"#, "dimension(s)")]
+#[case::too_many_dimensions(r#"{"data":[1.0,2.0],"shape":[1,1,2]}"#, "dimension(s)")]
+#[case::too_few_elements(r#"{"data":[1.0,2.0],"shape":[2,2]}"#, "element(s)")]
+#[case::too_many_elements(r#"{"data":[1.0,2.0,3.0],"shape":[1,2]}"#, "element(s)")]
+#[case::empty_axis(r#"{"data":[1.0],"shape":[0,1]}"#, "element(s)")]
+#[case::overflow(r#"{"data":[],"shape":[18446744073709551615,2]}"#, "overflows")]
+fn deserialize_malformed2d(#[case] json: &str, #[case] message: &str) {
+    let result = serde_json::from_str::<Array<2>>(json);
+
+    match result {
+        Ok(_) => panic!("deserialized a malformed array"),
+        Err(error) => assert!(error.to_string().contains(message), "{}", error),
+    }
+}
+
+#[test]
+fn deserialize_malformed1d() {
+    assert!(serde_json::from_str::<Array<1>>(r#"{"data":[1.0],"shape":[2]}"#).is_err());
+    assert!(serde_json::from_str::<Array<1>>(r#"{"data":[1.0],"shape":[]}"#).is_err());
+
+    let array: Array<1> = serde_json::from_str(r#"{"data":[],"shape":[0]}"#).unwrap();
+    assert_eq!(array.get_shape(), [0]);
+}
+
 #[test]
 fn zeros1d() {
     for i in 0..64 {
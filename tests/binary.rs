@@ -0,0 +1,187 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use std::io::ErrorKind;
+
+use fast_arrays::{Array, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn array_round_trip1d() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let array: Array<1> = data.clone().into();
+
+        let bytes = array.to_bytes();
+        assert_eq!(bytes.len(), 20 + 4 * i);
+
+        let read = Array::<1>::from_bytes(&bytes).unwrap();
+        assert_eq!(read.get_shape(), [i]);
+
+        let read: Vec<f32> = read.into();
+        assert_eq!(read, data);
+    }
+}
+
+#[test]
+fn array_round_trip2d() {
+    for i in 1..32 {
+        for j in 0..32 {
+            let data = get_random_f32_vec(0, i * j);
+            let array = if j == 0 {
+                Array::zeros(&[i, j])
+            } else {
+                Array::from_vec(&data, [i, j])
+            };
+
+            let mut bytes = Vec::new();
+            array.write_to(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), 28 + 4 * i * j);
+
+            let read = Array::<2>::read_from(bytes.as_slice()).unwrap();
+            assert_eq!(read.get_shape(), [i, j]);
+
+            let read: Vec<f32> = read.into();
+            assert_eq!(read, data);
+        }
+    }
+}
+
+#[test]
+fn mask_round_trip1d() {
+    for i in 0..64 {
+        let data = get_random_bool_vec(0, i);
+        let mask: Mask<1> = data.clone().into();
+
+        let bytes = mask.to_bytes();
+        assert_eq!(bytes.len(), 20 + 2 * i.div_ceil(16));
+
+        let read = Mask::<1>::from_bytes(&bytes).unwrap();
+        read.assert_invariants_satisfied();
+
+        let read: Vec<bool> = read.into();
+        assert_eq!(read, data);
+    }
+}
+
+#[test]
+fn mask_round_trip2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_bool_vec(0, i * j);
+            let mask = Mask::from_vec(&data, [i, j]);
+
+            let bytes = mask.to_bytes();
+            assert_eq!(bytes.len(), 28 + 2 * i * j.div_ceil(16));
+
+            let read = Mask::<2>::from_bytes(&bytes).unwrap();
+            read.assert_invariants_satisfied();
+            assert_eq!(read.get_shape(), &[i, j]);
+
+            for r in 0..i {
+                for c in 0..j {
+                    assert_eq!(read.get(r, c), data[r * j + c]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn layout() {
+    let array: Array<1> = vec![1.0, -2.0].into();
+    let mut expected = b"FASTARR\0".to_vec();
+    expected.extend_from_slice(&[1, 0, 0, 1]);
+    expected.extend_from_slice(&2u64.to_le_bytes());
+    expected.extend_from_slice(&1.0f32.to_le_bytes());
+    expected.extend_from_slice(&(-2.0f32).to_le_bytes());
+
+    assert_eq!(array.to_bytes(), expected);
+
+    let mask = Mask::from_vec(&vec![true, false, true, false, true, true], [2, 3]);
+    let mut expected = b"FASTARR\0".to_vec();
+    expected.extend_from_slice(&[1, 0, 1, 2]);
+    expected.extend_from_slice(&2u64.to_le_bytes());
+    expected.extend_from_slice(&3u64.to_le_bytes());
+    expected.extend_from_slice(&0b101u16.to_le_bytes());
+    expected.extend_from_slice(&0b110u16.to_le_bytes());
+
+    assert_eq!(mask.to_bytes(), expected);
+}
+
+#[test]
+fn invalid_magic() {
+    let mut bytes = Array::<1>::zeros(&[4]).to_bytes();
+    bytes[0] = b'X';
+
+    let error = Array::<1>::from_bytes(&bytes).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn wrong_kind() {
+    let bytes = Array::<1>::zeros(&[4]).to_bytes();
+    let error = Mask::<1>::from_bytes(&bytes).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let bytes = Mask::<1>::zeros(&[4]).to_bytes();
+    let error = Array::<1>::from_bytes(&bytes).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn wrong_rank() {
+    let bytes = Array::<2>::zeros(&[2, 2]).to_bytes();
+    let error = Array::<1>::from_bytes(&bytes).err().unwrap();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("dimension(s)"));
+}
+
+#[test]
+fn truncated() {
+    let bytes = Array::<2>::zeros(&[3, 5]).to_bytes();
+
+    for len in [0, 5, 20, bytes.len() - 1] {
+        let error = Array::<2>::from_bytes(&bytes[..len]).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+}
+
+#[test]
+fn huge_shape() {
+    let mut bytes = Array::<2>::zeros(&[1, 1]).to_bytes();
+    bytes[12..20].copy_from_slice(&(1u64 << 40).to_le_bytes());
+
+    let error = Array::<2>::from_bytes(&bytes).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+    bytes[20..28].copy_from_slice(&(1u64 << 40).to_le_bytes());
+
+    let error = Array::<2>::from_bytes(&bytes).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn mask_unused_bits_set() {
+    let mut bytes = Mask::<1>::zeros(&[3]).to_bytes();
+    bytes[20] = 0b1000;
+
+    let error = Mask::<1>::from_bytes(&bytes).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
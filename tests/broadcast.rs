@@ -0,0 +1,258 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use std::ops::{Add, Div, Mul, Sub};
+
+use fast_arrays::{Array, Mask};
+use utils::get_random_f32_vec;
+
+use rstest::rstest;
+
+#[rstest]
+#[case::add(Array::add_row_broadcast_in_place, f32::add)]
+#[case::sub(Array::sub_row_broadcast_in_place, f32::sub)]
+#[case::mul(Array::mul_row_broadcast_in_place, f32::mul)]
+#[case::div(Array::div_row_broadcast_in_place, f32::div)]
+#[case::max(Array::max_row_broadcast_in_place, f32::max)]
+#[case::min(Array::min_row_broadcast_in_place, f32::min)]
+fn row_in_place(
+    #[case] test_function: fn(&mut Array<2>, &Array<1>),
+    #[case] target_function: fn(f32, f32) -> f32,
+) {
+    for rows in 1..32 {
+        for columns in 1..32 {
+            let data = get_random_f32_vec(0, rows * columns);
+            let row = get_random_f32_vec(1, columns);
+
+            let mut array = Array::from_vec(&data, [rows, columns]);
+            test_function(&mut array, &row.clone().into());
+            let result: Vec<f32> = array.into();
+
+            for (i, (d, r)) in data.iter().zip(result.iter()).enumerate() {
+                assert_eq!(*r, target_function(*d, row[i % columns]));
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::add(Array::add_column_broadcast_in_place, f32::add)]
+#[case::sub(Array::sub_column_broadcast_in_place, f32::sub)]
+#[case::mul(Array::mul_column_broadcast_in_place, f32::mul)]
+#[case::div(Array::div_column_broadcast_in_place, f32::div)]
+#[case::max(Array::max_column_broadcast_in_place, f32::max)]
+#[case::min(Array::min_column_broadcast_in_place, f32::min)]
+fn column_in_place(
+    #[case] test_function: fn(&mut Array<2>, &Array<1>),
+    #[case] target_function: fn(f32, f32) -> f32,
+) {
+    for rows in 1..32 {
+        for columns in 1..32 {
+            let data = get_random_f32_vec(0, rows * columns);
+            let column = get_random_f32_vec(1, rows);
+
+            let mut array = Array::from_vec(&data, [rows, columns]);
+            test_function(&mut array, &column.clone().into());
+            let result: Vec<f32> = array.into();
+
+            for (i, (d, r)) in data.iter().zip(result.iter()).enumerate() {
+                assert_eq!(*r, target_function(*d, column[i / columns]));
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::add(Array::add_row_broadcast, Array::add)]
+#[case::sub(Array::sub_row_broadcast, Array::sub)]
+#[case::mul(Array::mul_row_broadcast, Array::mul)]
+#[case::div(Array::div_row_broadcast, Array::div)]
+#[case::max(Array::max_row_broadcast, Array::max)]
+#[case::min(Array::min_row_broadcast, Array::min)]
+fn row(
+    #[case] test_function: fn(&Array<2>, &Array<1>) -> Array<2>,
+    #[case] target_function: fn(&Array<2>, &Array<2>) -> Array<2>,
+) {
+    for rows in 1..32 {
+        for columns in [1, 15, 16, 17, 31] {
+            let array = Array::from_vec(&get_random_f32_vec(0, rows * columns), [rows, columns]);
+            let row: Array<1> = get_random_f32_vec(1, columns).into();
+
+            let mut repeated = Array::zeros(&[rows, columns]);
+            row.repeat_as_row_in_place(rows, &mut repeated);
+
+            assert_eq!(
+                test_function(&array, &row),
+                target_function(&array, &repeated)
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case::add(Array::add_column_broadcast, Array::add)]
+#[case::sub(Array::sub_column_broadcast, Array::sub)]
+#[case::mul(Array::mul_column_broadcast, Array::mul)]
+#[case::div(Array::div_column_broadcast, Array::div)]
+#[case::max(Array::max_column_broadcast, Array::max)]
+#[case::min(Array::min_column_broadcast, Array::min)]
+fn column(
+    #[case] test_function: fn(&Array<2>, &Array<1>) -> Array<2>,
+    #[case] target_function: fn(&Array<2>, &Array<2>) -> Array<2>,
+) {
+    for rows in 1..32 {
+        for columns in [1, 15, 16, 17, 31] {
+            let array = Array::from_vec(&get_random_f32_vec(0, rows * columns), [rows, columns]);
+            let column: Array<1> = get_random_f32_vec(1, rows).into();
+
+            let mut repeated = Array::zeros(&[rows, columns]);
+            column.repeat_as_column_in_place(columns, &mut repeated);
+
+            assert_eq!(
+                test_function(&array, &column),
+                target_function(&array, &repeated)
+            );
+        }
+    }
+}
+
+#[test]
+fn fmadd() {
+    for rows in 1..32 {
+        for columns in [1, 15, 16, 17, 31] {
+            let c = Array::from_vec(&get_random_f32_vec(0, rows * columns), [rows, columns]);
+            let a = Array::from_vec(&get_random_f32_vec(1, rows * columns), [rows, columns]);
+            let row: Array<1> = get_random_f32_vec(2, columns).into();
+            let column: Array<1> = get_random_f32_vec(3, rows).into();
+
+            let mut repeated = Array::zeros(&[rows, columns]);
+            row.repeat_as_row_in_place(rows, &mut repeated);
+            assert_eq!(c.fmadd_row_broadcast(&a, &row), c.fmadd(&a, &repeated));
+
+            column.repeat_as_column_in_place(columns, &mut repeated);
+            assert_eq!(
+                c.fmadd_column_broadcast(&a, &column),
+                c.fmadd(&a, &repeated)
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case::equal(Array::compare_equal_row_broadcast, Array::compare_equal)]
+#[case::not_equal(Array::compare_not_equal_row_broadcast, Array::compare_not_equal)]
+#[case::greater_than(Array::compare_greater_than_row_broadcast, Array::compare_greater_than)]
+#[case::greater_than_or_equal(
+    Array::compare_greater_than_or_equal_row_broadcast,
+    Array::compare_greater_than_or_equal
+)]
+#[case::less_than(Array::compare_less_than_row_broadcast, Array::compare_less_than)]
+#[case::less_than_or_equal(
+    Array::compare_less_than_or_equal_row_broadcast,
+    Array::compare_less_than_or_equal
+)]
+fn compare_row(
+    #[case] test_function: fn(&Array<2>, &Array<1>) -> Mask<2>,
+    #[case] target_function: fn(&Array<2>, &Array<2>) -> Mask<2>,
+) {
+    for rows in 1..32 {
+        for columns in [1, 15, 16, 17, 31] {
+            let mut data = get_random_f32_vec(0, rows * columns);
+            let row = get_random_f32_vec(1, columns);
+
+            // make some elements equal
+            for i in (0..data.len()).step_by(3) {
+                data[i] = row[i % columns];
+            }
+
+            let array = Array::from_vec(&data, [rows, columns]);
+            let row: Array<1> = row.into();
+
+            let mut repeated = Array::zeros(&[rows, columns]);
+            row.repeat_as_row_in_place(rows, &mut repeated);
+
+            let mask = test_function(&array, &row);
+            mask.assert_invariants_satisfied();
+            assert_eq!(mask, target_function(&array, &repeated));
+        }
+    }
+}
+
+#[rstest]
+#[case::equal(Array::compare_equal_column_broadcast, Array::compare_equal)]
+#[case::not_equal(Array::compare_not_equal_column_broadcast, Array::compare_not_equal)]
+#[case::greater_than(
+    Array::compare_greater_than_column_broadcast,
+    Array::compare_greater_than
+)]
+#[case::greater_than_or_equal(
+    Array::compare_greater_than_or_equal_column_broadcast,
+    Array::compare_greater_than_or_equal
+)]
+#[case::less_than(Array::compare_less_than_column_broadcast, Array::compare_less_than)]
+#[case::less_than_or_equal(
+    Array::compare_less_than_or_equal_column_broadcast,
+    Array::compare_less_than_or_equal
+)]
+fn compare_column(
+    #[case] test_function: fn(&Array<2>, &Array<1>) -> Mask<2>,
+    #[case] target_function: fn(&Array<2>, &Array<2>) -> Mask<2>,
+) {
+    for rows in 1..32 {
+        for columns in [1, 15, 16, 17, 31] {
+            let mut data = get_random_f32_vec(0, rows * columns);
+            let column = get_random_f32_vec(1, rows);
+
+            // make some elements equal
+            for i in (0..data.len()).step_by(3) {
+                data[i] = column[i / columns];
+            }
+
+            let array = Array::from_vec(&data, [rows, columns]);
+            let column: Array<1> = column.into();
+
+            let mut repeated = Array::zeros(&[rows, columns]);
+            column.repeat_as_column_in_place(columns, &mut repeated);
+
+            let mask = test_function(&array, &column);
+            mask.assert_invariants_satisfied();
+            assert_eq!(mask, target_function(&array, &repeated));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn row_wrong_length() {
+    let mut array = Array::<2>::zeros(&[3, 4]);
+    array.add_row_broadcast_in_place(&Array::zeros(&[3]));
+}
+
+#[test]
+#[should_panic]
+fn column_wrong_length() {
+    let array = Array::<2>::zeros(&[3, 4]);
+    array.compare_less_than_column_broadcast(&Array::zeros(&[4]));
+}
+
+#[test]
+#[should_panic]
+fn fmadd_different_shapes() {
+    let array = Array::<2>::zeros(&[3, 4]);
+    array.fmadd_row_broadcast(&Array::zeros(&[3, 5]), &Array::zeros(&[4]));
+}
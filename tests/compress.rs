@@ -0,0 +1,129 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn compress1d() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let mask_data = get_random_bool_vec(1, i);
+
+        let array: Array<1> = data.clone().into();
+        let mask: Mask<1> = mask_data.clone().into();
+
+        let result: Vec<f32> = array.compress(&mask).into();
+        let target: Vec<f32> = data
+            .iter()
+            .zip(mask_data.iter())
+            .filter(|(_, m)| **m)
+            .map(|(d, _)| *d)
+            .collect();
+
+        assert_eq!(result, target);
+    }
+}
+
+#[test]
+fn compress2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_f32_vec(0, i * j);
+            let mask_data = get_random_bool_vec(1, i * j);
+
+            let array = Array::<2>::from_vec(&data, [i, j]);
+            let mask = Mask::<2>::from_vec(&mask_data, [i, j]);
+
+            let result: Vec<f32> = array.compress(&mask).into();
+            let target: Vec<f32> = data
+                .iter()
+                .zip(mask_data.iter())
+                .filter(|(_, m)| **m)
+                .map(|(d, _)| *d)
+                .collect();
+
+            assert_eq!(result, target);
+        }
+    }
+}
+
+#[test]
+fn expand_into1d() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let mask_data = get_random_bool_vec(1, i);
+        let mask: Mask<1> = mask_data.clone().into();
+        let values_data = get_random_f32_vec(2, mask.number_of_true_elements());
+        let values: Array<1> = values_data.clone().into();
+
+        let mut array: Array<1> = data.clone().into();
+        array.expand_into(&values, &mask);
+        let result: Vec<f32> = array.into();
+
+        let mut values_iter = values_data.iter();
+
+        for ((d, m), r) in data.iter().zip(mask_data.iter()).zip(result.iter()) {
+            if *m {
+                assert_eq!(*r, *values_iter.next().unwrap());
+            } else {
+                assert_eq!(*r, *d);
+            }
+        }
+    }
+}
+
+#[test]
+fn compress_expand_round_trip2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_f32_vec(0, i * j);
+            let mask_data = get_random_bool_vec(1, i * j);
+
+            let array = Array::<2>::from_vec(&data, [i, j]);
+            let mask = Mask::<2>::from_vec(&mask_data, [i, j]);
+
+            let compressed = array.compress(&mask).mul_scalar(2.0);
+            let mut result = array.clone();
+            result.expand_into(&compressed, &mask);
+
+            for r in 0..i {
+                for c in 0..j {
+                    let index = r * j + c;
+                    let target = if mask_data[index] {
+                        data[index] * 2.0
+                    } else {
+                        data[index]
+                    };
+
+                    assert_eq!(result.get(r, c), target);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn expand_into_length_mismatch() {
+    let mut array: Array<1> = get_random_f32_vec(0, 8).into();
+    let mask: Mask<1> = vec![true; 8].into();
+    let values: Array<1> = get_random_f32_vec(1, 7).into();
+
+    array.expand_into(&values, &mask);
+}
@@ -0,0 +1,137 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::Array;
+use utils::{assert_approximate, get_random_f32_vec};
+
+#[test]
+fn arange() {
+    for count in 1..64 {
+        let array: Vec<f32> = Array::arange(0.0, count as f32, 1.0).into();
+        let expected: Vec<f32> = (0..count).map(|i| i as f32).collect();
+        assert_eq!(array, expected);
+    }
+
+    let array: Vec<f32> = Array::arange(1.0, 2.0, 0.25).into();
+    assert_eq!(array, [1.0, 1.25, 1.5, 1.75]);
+
+    let array: Vec<f32> = Array::arange(5.0, -1.0, -2.0).into();
+    assert_eq!(array, [5.0, 3.0, 1.0]);
+
+    let array: Vec<f32> = Array::arange(0.0, 100.0, 0.3).into();
+    assert_eq!(array.len(), 334);
+
+    for (i, value) in array.iter().enumerate() {
+        assert_eq!(*value, (i as f32).mul_add(0.3, 0.0));
+    }
+}
+
+#[test]
+#[should_panic]
+fn arange_empty() {
+    Array::arange(1.0, 1.0, 1.0);
+}
+
+#[test]
+#[should_panic]
+fn arange_zero_step() {
+    Array::arange(0.0, 1.0, 0.0);
+}
+
+#[test]
+fn linspace() {
+    for count in 2..64 {
+        let array: Vec<f32> = Array::linspace(-1.0, 3.0, count).into();
+        assert_eq!(array.len(), count);
+        assert_eq!(array[0], -1.0);
+        assert_eq!(array[count - 1], 3.0);
+
+        for (i, value) in array.iter().enumerate() {
+            assert_approximate(*value, -1.0 + 4.0 * i as f32 / (count - 1) as f32, 1e-5);
+        }
+    }
+
+    let array: Vec<f32> = Array::linspace(2.0, 3.0, 1).into();
+    assert_eq!(array, [2.0]);
+}
+
+#[test]
+fn logspace() {
+    let array: Vec<f32> = Array::logspace(0.0, 3.0, 4, 10.0).into();
+
+    for (value, expected) in array.iter().zip([1.0, 10.0, 100.0, 1000.0]) {
+        assert_approximate(*value, expected, 1e-6);
+    }
+
+    let array: Vec<f32> = Array::logspace(1.0, 4.0, 4, 2.0).into();
+    assert_eq!(array, [2.0, 4.0, 8.0, 16.0]);
+}
+
+#[test]
+fn ones() {
+    let array: Vec<f32> = Array::<2>::ones(&[3, 17]).into();
+    assert_eq!(array, vec![1.0; 3 * 17]);
+}
+
+#[test]
+fn eye() {
+    for n in 1..40 {
+        let array = Array::eye(n);
+        assert_eq!(array.get_shape(), [n, n]);
+        assert_eq!(array.sum(), n as f32);
+        assert_eq!(array.diag(), Array::ones(&[n]));
+    }
+}
+
+#[test]
+fn diag() {
+    for n in 1..40 {
+        let data = get_random_f32_vec(0, n);
+        let diagonal: Array<1> = data.clone().into();
+        let array = Array::from_diag(&diagonal);
+
+        for (r, value) in data.iter().enumerate() {
+            for c in 0..n {
+                assert_eq!(array.get(r, c), if r == c { *value } else { 0.0 });
+            }
+        }
+
+        assert_eq!(array.diag(), diagonal);
+    }
+
+    let array = Array::from_vec(&get_random_f32_vec(0, 3 * 20), [3, 20]);
+    let diagonal: Vec<f32> = array.diag().into();
+    assert_eq!(
+        diagonal,
+        [array.get(0, 0), array.get(1, 1), array.get(2, 2)]
+    );
+}
+
+#[test]
+fn from_fn() {
+    let array = Array::from_fn(&[5, 18], |[r, c]| (r * 100 + c) as f32);
+
+    for r in 0..5 {
+        for c in 0..18 {
+            assert_eq!(array.get(r, c), (r * 100 + c) as f32);
+        }
+    }
+
+    let array: Vec<f32> = Array::from_fn(&[20], |[i]| (i * i) as f32).into();
+    assert_eq!(array, (0..20).map(|i| (i * i) as f32).collect::<Vec<_>>());
+}
@@ -0,0 +1,252 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use std::io::ErrorKind;
+
+use fast_arrays::{Array, CsvReadOptions, CsvWriteOptions, EmptyCellPolicy, Error, FloatFormat};
+use rstest::rstest;
+use utils::get_random_f32_vec;
+
+fn parse_error(error: std::io::Error) -> Error {
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    error
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<Error>()
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn round_trip2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_f32_vec(0, i * j);
+            let array = Array::from_vec(&data, [i, j]);
+
+            let mut csv = Vec::new();
+            array.to_csv(&mut csv, &CsvWriteOptions::default()).unwrap();
+
+            let read = Array::<2>::from_csv(csv.as_slice(), &CsvReadOptions::default()).unwrap();
+            assert_eq!(read.get_shape(), [i, j]);
+
+            let read: Vec<f32> = read.into();
+            assert_eq!(read, data);
+        }
+    }
+}
+
+#[test]
+fn round_trip1d() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let array: Array<1> = data.clone().into();
+
+        let mut csv = Vec::new();
+        array.to_csv(&mut csv, &CsvWriteOptions::tsv()).unwrap();
+        assert_eq!(csv.iter().filter(|c| **c == b'\n').count(), i);
+
+        let read = Array::<1>::from_csv(csv.as_slice(), &CsvReadOptions::tsv()).unwrap();
+        let read: Vec<f32> = read.into();
+
+        assert_eq!(read, data);
+    }
+}
+
+#[test]
+fn header_and_delimiter() {
+    let csv = "a;b;c\r\n1;2.5;-3\r\n\"4\"; 5 ;6e1\r\n\r\n";
+    let options = CsvReadOptions {
+        delimiter: ';',
+        has_header: true,
+        ..Default::default()
+    };
+
+    let array = Array::<2>::from_csv(csv.as_bytes(), &options).unwrap();
+    let data: Vec<f32> = array.into();
+
+    assert_eq!(data, vec![1.0, 2.5, -3.0, 4.0, 5.0, 60.0]);
+}
+
+#[test]
+fn only_header() {
+    let options = CsvReadOptions {
+        has_header: true,
+        ..Default::default()
+    };
+
+    let array = Array::<2>::from_csv("a,b,c\n".as_bytes(), &options).unwrap();
+
+    assert_eq!(array.get_shape(), [0, 3]);
+}
+
+#[rstest]
+#[case::nan(EmptyCellPolicy::Nan)]
+#[case::value(EmptyCellPolicy::Value(-1.0))]
+fn empty_cells(#[case] policy: EmptyCellPolicy) {
+    let options = CsvReadOptions {
+        empty_cells: policy,
+        ..Default::default()
+    };
+
+    let array = Array::<2>::from_csv("1,,3\nNaN,5,\n".as_bytes(), &options).unwrap();
+    let data: Vec<f32> = array.into();
+    let empty = data[1];
+
+    match policy {
+        EmptyCellPolicy::Nan => assert!(empty.is_nan()),
+        EmptyCellPolicy::Value(value) => assert_eq!(empty, value),
+        EmptyCellPolicy::Error => unreachable!(),
+    }
+
+    assert!(data[3].is_nan());
+    assert_eq!(data[5].to_bits(), empty.to_bits());
+}
+
+#[test]
+fn nan_as_empty_cell_round_trip() {
+    let write_options = CsvWriteOptions {
+        nan: "".to_string(),
+        ..Default::default()
+    };
+    let read_options = CsvReadOptions {
+        empty_cells: EmptyCellPolicy::Nan,
+        ..Default::default()
+    };
+
+    for data in [
+        vec![1.0, f32::NAN, 3.0],
+        vec![f32::NAN, 2.0],
+        vec![1.0, f32::NAN],
+    ] {
+        let array: Array<1> = data.clone().into();
+        let mut csv = Vec::new();
+        array.to_csv(&mut csv, &write_options).unwrap();
+
+        let read: Vec<f32> = Array::<1>::from_csv(csv.as_slice(), &read_options)
+            .unwrap()
+            .into();
+        assert_eq!(read.len(), data.len());
+        assert!(read
+            .iter()
+            .zip(data.iter())
+            .all(|(r, d)| r.to_bits() == d.to_bits()));
+
+        let array = Array::from_vec(&data, [data.len(), 1]);
+        let mut csv = Vec::new();
+        array.to_csv(&mut csv, &write_options).unwrap();
+
+        let read = Array::<2>::from_csv(csv.as_slice(), &read_options).unwrap();
+        assert_eq!(read.get_shape(), [data.len(), 1]);
+        let read: Vec<f32> = read.into();
+        assert!(read
+            .iter()
+            .zip(data.iter())
+            .all(|(r, d)| r.to_bits() == d.to_bits()));
+    }
+
+    // blank lines are still skipped with several columns
+    let array = Array::<2>::from_csv("1,2\n\n3,4\n\n".as_bytes(), &read_options).unwrap();
+    assert_eq!(array.get_shape(), [2, 2]);
+}
+
+#[test]
+fn empty_cell_error() {
+    let error = Array::<2>::from_csv("1,2\n3,\n".as_bytes(), &CsvReadOptions::default())
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        parse_error(error),
+        Error::Parse {
+            line: 2,
+            column: 2,
+            reason: "the cell is empty".to_string(),
+        }
+    );
+}
+
+#[test]
+fn invalid_number() {
+    let options = CsvReadOptions {
+        has_header: true,
+        ..Default::default()
+    };
+    let error = Array::<2>::from_csv("x,y,z\n1,2,3\n4,five,6\n".as_bytes(), &options)
+        .err()
+        .unwrap();
+
+    match parse_error(error) {
+        Error::Parse { line, column, .. } => {
+            assert_eq!(line, 3);
+            assert_eq!(column, 2);
+        }
+        error => panic!("unexpected error {:?}", error),
+    }
+}
+
+#[test]
+fn ragged_rows() {
+    let error = Array::<2>::from_csv("1,2,3\n4,5\n".as_bytes(), &CsvReadOptions::default())
+        .err()
+        .unwrap();
+
+    match parse_error(error) {
+        Error::Parse { line, column, .. } => {
+            assert_eq!(line, 2);
+            assert_eq!(column, 3);
+        }
+        error => panic!("unexpected error {:?}", error),
+    }
+
+    let error = Array::<1>::from_csv("1\n4,5\n".as_bytes(), &CsvReadOptions::default())
+        .err()
+        .unwrap();
+
+    match parse_error(error) {
+        Error::Parse { line, .. } => assert_eq!(line, 2),
+        error => panic!("unexpected error {:?}", error),
+    }
+}
+
+#[test]
+fn write_options() {
+    let array = Array::from_vec(&vec![1.0, f32::NAN, 0.125, 1500.0], [2, 2]);
+
+    let mut csv = Vec::new();
+    let options = CsvWriteOptions {
+        delimiter: '\t',
+        header: Some(vec!["x".to_string(), "y".to_string()]),
+        nan: "".to_string(),
+        float_format: FloatFormat::Fixed(2),
+    };
+    array.to_csv(&mut csv, &options).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "x\ty\n1.00\t\n0.12\t1500.00\n"
+    );
+
+    let mut csv = Vec::new();
+    let options = CsvWriteOptions {
+        float_format: FloatFormat::Scientific(1),
+        ..Default::default()
+    };
+    array.to_csv(&mut csv, &options).unwrap();
+    assert_eq!(String::from_utf8(csv).unwrap(), "1.0e0,NaN\n1.2e-1,1.5e3\n");
+}
@@ -0,0 +1,120 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{
+    assert_array_approx_eq, Array, Expression, ExpressionNode, IntoExpression, Mask,
+};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn expr() {
+    for rows in 1..10 {
+        for columns in 1..40 {
+            let a = Array::from_vec(&get_random_f32_vec(0, rows * columns), [rows, columns]);
+            let b = Array::from_vec(&get_random_f32_vec(1, rows * columns), [rows, columns]);
+
+            let result = a.expr().mul(&b).add_scalar(1.0).abs().sqrt().eval();
+            let expected = a.mul(&b).add_scalar(1.0).abs().sqrt();
+            assert_eq!(result, expected);
+
+            let result = a
+                .expr()
+                .sub(&b)
+                .square()
+                .div_scalar(2.0)
+                .max(&a)
+                .min_scalar(0.5)
+                .eval();
+            let expected = a.sub(&b).square().div_scalar(2.0).max(&a).min_scalar(0.5);
+            assert_eq!(result, expected);
+        }
+    }
+}
+
+#[test]
+fn expr_of_expressions() {
+    let a: Array<1> = get_random_f32_vec(0, 37).into();
+    let b: Array<1> = get_random_f32_vec(1, 37).into();
+    let c: Array<1> = get_random_f32_vec(2, 37).into();
+
+    let result = a
+        .expr()
+        .add(b.expr().mul_scalar(3.0))
+        .div(c.expr().abs().add_scalar(1.0))
+        .eval();
+    let expected = a.add(&b.mul_scalar(3.0)).div(&c.abs().add_scalar(1.0));
+    assert_eq!(result, expected);
+
+    let result = c.expr().fmadd(&a, b.expr().sub_scalar(1.0)).eval();
+    // the fallback fmadd rounds the product before the addition
+    assert_array_approx_eq!(result, c.fmadd(&a, &b.sub_scalar(1.0)), abs <= 1e-2);
+}
+
+#[test]
+fn eval_into() {
+    for i in 1..64 {
+        let a: Array<1> = get_random_f32_vec(0, i).into();
+        let b: Array<1> = get_random_f32_vec(1, i).into();
+        let mask: Mask<1> = get_random_bool_vec(2, i).into();
+
+        let mut output = Array::zeros(&[i]);
+        a.expr().mul(&b).sub_scalar(1.0).eval_into(&mut output);
+        assert_eq!(output, a.mul(&b).sub_scalar(1.0));
+
+        let mut output = b.clone();
+        a.expr().add(&b).eval_into_masked(&mut output, &mask);
+        let mut expected = b.clone();
+        expected.add_in_place_masked(&a, &mask);
+        assert_eq!(output, expected);
+    }
+}
+
+/// an expression built in a function and returned without evaluating it
+fn mean<'a>(a: &'a Array<2>, b: &'a Array<2>) -> Expression<impl ExpressionNode<2> + 'a, 2> {
+    a.expr().add(b).mul_scalar(0.5)
+}
+
+/// a function that accepts both arrays and expressions
+fn squared<const D: usize>(operand: impl IntoExpression<D>) -> Array<D> {
+    operand.into_expression().square().eval()
+}
+
+#[test]
+fn named_expressions() {
+    let a = Array::from_vec(&get_random_f32_vec(0, 3 * 20), [3, 20]);
+    let b = Array::from_vec(&get_random_f32_vec(1, 3 * 20), [3, 20]);
+
+    let expected = a.add(&b).mul_scalar(0.5);
+    assert_array_approx_eq!(mean(&a, &b).eval(), expected, ulps <= 0);
+    assert_array_approx_eq!(squared(mean(&a, &b)), expected.square(), ulps <= 0);
+    assert_array_approx_eq!(squared(&a), a.square(), ulps <= 0);
+}
+
+#[test]
+#[should_panic]
+fn expr_different_shapes() {
+    let a = Array::<2>::zeros(&[2, 3]);
+    a.expr().add(&Array::zeros(&[3, 2]));
+}
+
+#[test]
+#[should_panic]
+fn eval_into_different_shape() {
+    let a = Array::<2>::zeros(&[2, 3]);
+    a.expr().sqrt().eval_into(&mut Array::zeros(&[3, 2]));
+}
@@ -0,0 +1,396 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Error, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn try_add() {
+    for i in 0..64 {
+        let data1 = get_random_f32_vec(0, i);
+        let data2 = get_random_f32_vec(1, i);
+
+        let array1: Array<1> = data1.clone().into();
+        let array2: Array<1> = data2.clone().into();
+
+        let result: Vec<f32> = array1.try_add(&array2).unwrap().into();
+        let expected: Vec<f32> = array1.add(&array2).into();
+
+        assert_eq!(result, expected);
+    }
+}
+
+#[test]
+fn try_add_shape_mismatch() {
+    let array1 = Array::zeros(&[3, 4]);
+    let array2 = Array::zeros(&[4, 3]);
+
+    assert_eq!(
+        array1.try_add(&array2).err(),
+        Some(Error::ShapeMismatch {
+            expected: vec![3, 4],
+            found: vec![4, 3],
+        })
+    );
+}
+
+#[test]
+fn try_add_in_place_masked_shape_mismatch() {
+    let mut array1 = Array::zeros(&[16]);
+    let array2 = Array::zeros(&[16]);
+    let mask: Mask<1> = get_random_bool_vec(0, 17).into();
+
+    assert_eq!(
+        array1.try_add_in_place_masked(&array2, &mask),
+        Err(Error::ShapeMismatch {
+            expected: vec![16],
+            found: vec![17],
+        })
+    );
+}
+
+#[test]
+fn try_fmadd_out_of_place_shape_mismatch() {
+    let array1 = Array::zeros(&[5]);
+    let array2 = Array::zeros(&[5]);
+    let mut output = Array::zeros(&[6]);
+
+    assert!(array1.try_fmadd(&array2, &output).is_err());
+    assert!(array1.try_sub_out_of_place(&array2, &mut output).is_err());
+}
+
+#[test]
+fn try_compare_greater_than() {
+    let data1 = get_random_f32_vec(0, 33);
+    let data2 = get_random_f32_vec(1, 33);
+
+    let array1: Array<1> = data1.into();
+    let array2: Array<1> = data2.into();
+
+    let result: Vec<bool> = array1.try_compare_greater_than(&array2).unwrap().into();
+    let expected: Vec<bool> = array1.compare_greater_than(&array2).into();
+
+    assert_eq!(result, expected);
+    assert!(array1
+        .try_compare_greater_than(&Array::zeros(&[32]))
+        .is_err());
+}
+
+#[test]
+fn try_matrix_multiplication() {
+    let a = Array::from_vec(&get_random_f32_vec(0, 6), [2, 3]);
+    let b = Array::from_vec(&get_random_f32_vec(1, 12), [3, 4]);
+
+    let result: Vec<f32> = a.try_matrix_multiplication(&b).unwrap().into();
+    let expected: Vec<f32> = a.matrix_multiplication(&b).into();
+
+    assert_eq!(result, expected);
+    assert_eq!(
+        b.try_matrix_multiplication(&a).err(),
+        Some(Error::LengthMismatch {
+            expected: 4,
+            found: 2,
+        })
+    );
+}
+
+#[test]
+#[should_panic]
+fn matrix_multiplication_shape_mismatch() {
+    let a = Array::zeros(&[2, 3]);
+    let b = Array::zeros(&[4, 2]);
+
+    a.matrix_multiplication(&b);
+}
+
+#[test]
+fn try_vector_multiplication() {
+    let a = Array::zeros(&[2, 3]);
+
+    assert!(a.try_vector_multiplication(&Array::zeros(&[3])).is_ok());
+    assert!(a.try_vector_multiplication(&Array::zeros(&[2])).is_err());
+}
+
+#[test]
+fn try_matrix_out_of_place() {
+    let a = Array::zeros(&[2, 3]);
+    let b = Array::zeros(&[3, 4]);
+
+    assert!(a
+        .try_matrix_multiplication_out_of_place(&b, &mut Array::zeros(&[2, 4]))
+        .is_ok());
+    assert!(a
+        .try_matrix_multiplication_out_of_place(&b, &mut Array::zeros(&[4, 2]))
+        .is_err());
+    assert!(a
+        .try_matrix_multiplication_transposed_out_of_place(&a, &mut Array::zeros(&[2, 2]))
+        .is_ok());
+    assert!(a
+        .try_matrix_multiplication_transposed_out_of_place(&b, &mut Array::zeros(&[2, 3]))
+        .is_err());
+    assert!(a
+        .try_transpose_out_of_place(&mut Array::zeros(&[3, 2]))
+        .is_ok());
+    assert!(a
+        .try_transpose_out_of_place(&mut Array::zeros(&[2, 3]))
+        .is_err());
+    assert!(a
+        .try_vector_multiplication_out_of_place(&Array::zeros(&[3]), &mut Array::zeros(&[3]))
+        .is_err());
+}
+
+#[test]
+fn try_get_1d() {
+    let data = get_random_f32_vec(0, 20);
+    let mut array: Array<1> = data.clone().into();
+
+    assert_eq!(array.try_get(19), Ok(data[19]));
+    assert_eq!(
+        array.try_get(20),
+        Err(Error::IndexOutOfBounds {
+            axis: 0,
+            index: 20,
+            length: 20,
+        })
+    );
+    assert!(array.try_set(20, 1.0).is_err());
+    assert!(array.try_set(3, 1.0).is_ok());
+    assert_eq!(array.get(3), 1.0);
+}
+
+#[test]
+fn try_get_2d() {
+    let mut array = Array::from_vec(&get_random_f32_vec(0, 15), [3, 5]);
+
+    assert_eq!(array.try_get(2, 4), Ok(array.get(2, 4)));
+    assert_eq!(
+        array.try_get(3, 0),
+        Err(Error::IndexOutOfBounds {
+            axis: 0,
+            index: 3,
+            length: 3,
+        })
+    );
+    assert_eq!(
+        array.try_set(0, 5, 1.0),
+        Err(Error::IndexOutOfBounds {
+            axis: 1,
+            index: 5,
+            length: 5,
+        })
+    );
+}
+
+#[test]
+fn try_from_vec() {
+    assert!(Array::try_from_vec(&vec![0.0; 6], [2, 3]).is_ok());
+    assert_eq!(
+        Array::try_from_vec(&vec![0.0; 5], [2, 3]).err(),
+        Some(Error::LengthMismatch {
+            expected: 6,
+            found: 5,
+        })
+    );
+    assert!(matches!(
+        Array::try_from_vec(&vec![], [0, 3]),
+        Err(Error::InvalidShape { .. })
+    ));
+    assert!(matches!(
+        Mask::try_from_vec(&vec![], [2, 0]),
+        Err(Error::InvalidShape { .. })
+    ));
+}
+
+#[test]
+#[should_panic]
+fn from_vec_length_mismatch() {
+    Array::from_vec(&vec![0.0; 5], [2, 3]);
+}
+
+#[test]
+fn try_sum_empty_axis() {
+    let array = Array::zeros(&[3, 0]);
+
+    assert!(matches!(array.try_sum(), Err(Error::InvalidShape { .. })));
+    assert_eq!(Array::zeros(&[3, 2]).try_sum(), Ok(0.0));
+}
+
+#[test]
+fn try_gather() {
+    let data = get_random_f32_vec(0, 20);
+    let array: Array<1> = data.clone().into();
+
+    let result: Vec<f32> = array.try_gather(&[19, 0, 7]).unwrap().into();
+    assert_eq!(result, vec![data[19], data[0], data[7]]);
+
+    assert_eq!(
+        array.try_gather(&[0, 20]).err(),
+        Some(Error::IndexOutOfBounds {
+            axis: 0,
+            index: 20,
+            length: 20,
+        })
+    );
+}
+
+#[test]
+fn try_scatter_in_place_masked() {
+    let mut array = Array::zeros(&[4]);
+    let values: Array<1> = vec![1.0, 2.0].into();
+    let mask: Mask<1> = vec![true, false].into();
+
+    // the second index is out of bounds, but it is masked out
+    assert!(array
+        .try_scatter_in_place_masked(&[1, 100], &values, &mask)
+        .is_ok());
+    assert_eq!(array.get(1), 1.0);
+
+    let mask: Mask<1> = vec![true, true].into();
+    assert!(array
+        .try_scatter_in_place_masked(&[1, 100], &values, &mask)
+        .is_err());
+    assert!(array.try_scatter_in_place(&[1], &values).is_err());
+}
+
+#[test]
+fn try_expand_into() {
+    let mut array = Array::zeros(&[4]);
+    let mask: Mask<1> = vec![true, false, true, false].into();
+
+    assert!(array.try_expand_into(&vec![1.0, 2.0].into(), &mask).is_ok());
+    assert_eq!(
+        array.try_expand_into(&vec![1.0].into(), &mask),
+        Err(Error::LengthMismatch {
+            expected: 2,
+            found: 1,
+        })
+    );
+}
+
+#[test]
+fn try_mask_operations() {
+    let mask1: Mask<1> = get_random_bool_vec(0, 20).into();
+    let mask2: Mask<1> = get_random_bool_vec(1, 20).into();
+    let mut mask3: Mask<1> = get_random_bool_vec(2, 21).into();
+
+    let result: Vec<bool> = mask1.try_and(&mask2).unwrap().into();
+    let expected: Vec<bool> = mask1.and(&mask2).into();
+    assert_eq!(result, expected);
+
+    assert!(mask1.try_or(&mask3).is_err());
+    assert!(mask3.try_copy(&mask1).is_err());
+    assert_eq!(mask3.try_get(20), Ok(mask3.get(20)));
+    assert!(mask3.try_get(21).is_err());
+}
+
+#[test]
+fn try_repeating() {
+    let array: Array<1> = get_random_f32_vec(0, 32).into();
+    let mask: Mask<1> = get_random_bool_vec(1, 32).into();
+
+    let mut output = Array::zeros(&[64]);
+    assert_eq!(array.try_tile_in_place(2, &mut output), Ok(()));
+    assert_eq!(
+        array.try_repeat_in_place(2, &mut output),
+        Err(Error::InvalidArgument {
+            name: "k",
+            value: 2,
+            reason: "it must be a multiple of 16"
+        })
+    );
+    assert!(array.try_tile_in_place(3, &mut output).is_err());
+
+    let mut output = Array::zeros(&[3, 32]);
+    assert_eq!(array.try_repeat_as_row_in_place(3, &mut output), Ok(()));
+    assert!(array.try_repeat_as_column_in_place(3, &mut output).is_err());
+
+    let mut output = Mask::zeros(&[64]);
+    assert_eq!(mask.try_tile_in_place(2, &mut output), Ok(()));
+    assert!(mask.try_repeat_in_place(2, &mut output).is_err());
+
+    let mut output = Mask::zeros(&[32, 3]);
+    assert_eq!(mask.try_repeat_as_column_in_place(3, &mut output), Ok(()));
+    assert!(mask.try_repeat_as_row_in_place(3, &mut output).is_err());
+
+    let odd: Array<1> = get_random_f32_vec(0, 20).into();
+    assert!(odd.try_tile_in_place(2, &mut Array::zeros(&[40])).is_err());
+}
+
+#[test]
+fn try_sum_masked() {
+    let array = Array::from_vec(&get_random_f32_vec(0, 3 * 20), [3, 20]);
+    let mask = Mask::from_vec(&get_random_bool_vec(1, 3 * 20), [3, 20]);
+
+    let mut row = Array::zeros(&[20]);
+    assert_eq!(
+        array.try_sum_to_row_in_place_masked(&mask, &mut row),
+        Ok(())
+    );
+    assert!(array
+        .try_sum_to_column_in_place_masked(&mask, &mut row)
+        .is_err());
+
+    let mut column = Array::zeros(&[3]);
+    assert_eq!(
+        array.try_sum_to_column_in_place_masked(&mask, &mut column),
+        Ok(())
+    );
+    assert!(array
+        .try_sum_to_row_in_place_masked(&Mask::zeros(&[3, 21]), &mut row)
+        .is_err());
+}
+
+#[test]
+fn try_rows_and_columns() {
+    let mut array = Array::from_vec(&get_random_f32_vec(0, 3 * 20), [3, 20]);
+    let mut mask = Mask::from_vec(&get_random_bool_vec(1, 3 * 20), [3, 20]);
+
+    assert_eq!(array.try_get_row(2), Ok(array.get_row(2)));
+    assert!(array.try_get_row(3).is_err());
+    assert!(array.try_column(20).is_err());
+    assert!(array.try_copy_row_from(0, &Array::zeros(&[3])).is_err());
+    assert_eq!(array.try_set_column(19, &Array::zeros(&[3])), Ok(()));
+
+    assert_eq!(mask.try_get_row(2), Ok(mask.get_row(2)));
+    assert!(mask.try_get_row(3).is_err());
+    assert!(mask.try_column(20).is_err());
+    assert!(mask.try_copy_row_from(0, &Mask::zeros(&[3])).is_err());
+    assert_eq!(mask.try_set_column(19, &Mask::zeros(&[3])), Ok(()));
+
+    assert!(array.try_chunks(0, 0).is_err());
+    assert_eq!(array.try_chunks(0, 2).map(|c| c.len()), Ok(2));
+    assert!(mask.try_chunks(2, 1).is_err());
+}
+
+#[test]
+#[should_panic]
+fn mask_get_out_of_bounds() {
+    let mask: Mask<1> = get_random_bool_vec(0, 20).into();
+
+    mask.get(20);
+}
+
+#[test]
+fn error_message() {
+    let error = Array::zeros(&[2])
+        .try_add(&Array::zeros(&[3]))
+        .err()
+        .unwrap();
+
+    assert_eq!(error.to_string(), "the shapes don't match: [2] != [3]");
+}
@@ -0,0 +1,120 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use fast_arrays::{Array, Mask};
+
+#[test]
+fn debug_array() {
+    let array = Array::from_vec(&vec![1.0, 2.5, -3.0, 4.0, 5.0, 6.0], [2, 3]);
+
+    assert_eq!(
+        format!("{:?}", array),
+        "Array { shape: [2, 3], elements: [1.0, 2.5, -3.0, 4.0, 5.0, 6.0] }"
+    );
+}
+
+#[test]
+fn debug_array_without_padding() {
+    for i in 0..64 {
+        let array: Array<1> = vec![1.0; i].into();
+        let expected = format!(
+            "Array {{ shape: [{}], elements: {:?} }}",
+            i,
+            vec![1.0f32; i]
+        );
+
+        assert_eq!(format!("{:?}", array), expected);
+    }
+}
+
+#[test]
+fn debug_mask() {
+    let mask = Mask::from_vec(&vec![true, false, false, true], [2, 2]);
+
+    assert_eq!(
+        format!("{:?}", mask),
+        "Mask { shape: [2, 2], elements: [true, false, false, true] }"
+    );
+}
+
+#[test]
+fn display1d() {
+    let array: Array<1> = vec![1.0, -2.5, 30.0].into();
+
+    assert_eq!(format!("{}", array), "[   1 -2.5   30]");
+    assert_eq!(format!("{:.2}", array), "[ 1.00 -2.50 30.00]");
+}
+
+#[test]
+fn display2d() {
+    let array = Array::from_vec(&vec![1.0, 2.0, 3.0, 4.0, -50.0, 6.0], [2, 3]);
+
+    assert_eq!(format!("{}", array), "[[  1   2   3]\n [  4 -50   6]]");
+    assert_eq!(
+        format!("{:.1}", array),
+        "[[  1.0   2.0   3.0]\n [  4.0 -50.0   6.0]]"
+    );
+}
+
+#[test]
+fn display_empty() {
+    assert_eq!(format!("{}", Array::<1>::zeros(&[0])), "[]");
+    assert_eq!(format!("{}", Array::<2>::zeros(&[2, 0])), "[[]\n []]");
+}
+
+#[test]
+fn display_truncated1d() {
+    let array: Array<1> = (0..1001).map(|i| i as f32).collect::<Vec<f32>>().into();
+
+    assert_eq!(format!("{}", array), "[   0    1    2 ...  998  999 1000]");
+
+    // arrays up to the threshold aren't truncated
+    let array: Array<1> = vec![0.0; 1000].into();
+    assert_eq!(format!("{}", array).matches('0').count(), 1000);
+}
+
+#[test]
+fn display_truncated2d() {
+    let data: Vec<f32> = (0..40 * 30).map(|i| (i % 30) as f32).collect();
+    let array = Array::from_vec(&data, [40, 30]);
+    let row = "[ 0  1  2 ... 27 28 29]";
+
+    assert_eq!(
+        format!("{}", array),
+        format!(
+            "[{}\n {}\n {}\n ...\n {}\n {}\n {}]",
+            row, row, row, row, row, row
+        )
+    );
+
+    // only long axes are truncated
+    let array = Array::from_vec(&vec![1.0; 500 * 3], [500, 3]);
+    assert!(format!("{}", array).starts_with("[[1 1 1]\n [1 1 1]\n [1 1 1]\n ...\n [1 1 1]"));
+}
+
+#[test]
+fn display_mask() {
+    let mask = Mask::from_vec(&vec![true, false, false, true, true, true], [2, 3]);
+
+    assert_eq!(format!("{}", mask), "[[1 0 0]\n [1 1 1]]");
+    assert_eq!(format!("{:#}", mask), "[[T F F]\n [T T T]]");
+
+    let mask: Mask<1> = vec![false; 17].into();
+    assert_eq!(
+        format!("{}", mask),
+        format!("[{}]", vec!["0"; 17].join(" "))
+    );
+}
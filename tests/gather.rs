@@ -0,0 +1,197 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Mask};
+use rand::{distributions::Uniform, Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+fn get_random_indices(seed: u64, len: usize, max: usize) -> Vec<u32> {
+    let rng = ChaCha20Rng::seed_from_u64(seed);
+
+    rng.sample_iter(Uniform::new(0, max as u32))
+        .take(len)
+        .collect()
+}
+
+#[test]
+fn gather() {
+    for i in 1..64 {
+        for j in 0..64 {
+            let data = get_random_f32_vec(0, i);
+            let indices = get_random_indices(1, j, i);
+            let array: Array<1> = data.clone().into();
+
+            let result: Vec<f32> = array.gather(&indices).into();
+            let target: Vec<f32> = indices.iter().map(|i| data[*i as usize]).collect();
+
+            assert_eq!(result, target);
+        }
+    }
+}
+
+#[test]
+fn gather_in_place_masked() {
+    for i in 1..64 {
+        for j in 0..64 {
+            let data = get_random_f32_vec(0, i);
+            let indices = get_random_indices(1, j, i);
+            let mask_data = get_random_bool_vec(2, j);
+            let array: Array<1> = data.clone().into();
+            let mask: Mask<1> = mask_data.clone().into();
+            let mut output = Array::new_from_value(&[j], 42.0);
+
+            array.gather_in_place_masked(&indices, &mask, &mut output);
+            let result: Vec<f32> = output.into();
+
+            for ((index, m), r) in indices.iter().zip(mask_data.iter()).zip(result.iter()) {
+                if *m {
+                    assert_eq!(*r, data[*index as usize]);
+                } else {
+                    assert_eq!(*r, 42.0);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn gather_in_place_masked_skips_unselected_indices() {
+    let array: Array<1> = get_random_f32_vec(0, 4).into();
+    let indices = vec![0, 1000, 2];
+    let mask: Mask<1> = vec![true, false, true].into();
+    let mut output = Array::zeros(&[3]);
+
+    array.gather_in_place_masked(&indices, &mask, &mut output);
+
+    assert_eq!(output.get(0), array.get(0));
+    assert_eq!(output.get(1), 0.0);
+    assert_eq!(output.get(2), array.get(2));
+}
+
+#[test]
+#[should_panic]
+fn gather_out_of_bounds() {
+    let array: Array<1> = get_random_f32_vec(0, 17).into();
+    let _ = array.gather(&[0, 3, 17]);
+}
+
+#[test]
+fn scatter_in_place() {
+    for i in 1..64 {
+        let data = get_random_f32_vec(0, i);
+        let values_data = get_random_f32_vec(1, i);
+        let values: Array<1> = values_data.clone().into();
+
+        // a permutation so that every index occurs exactly once
+        let indices: Vec<u32> = (0..i as u32).rev().collect();
+        let mut array: Array<1> = data.clone().into();
+
+        array.scatter_in_place(&indices, &values);
+        let result: Vec<f32> = array.into();
+
+        for (index, v) in indices.iter().zip(values_data.iter()) {
+            assert_eq!(result[*index as usize], *v);
+        }
+    }
+}
+
+#[test]
+fn scatter_in_place_masked() {
+    for i in 1..64 {
+        let data = get_random_f32_vec(0, i);
+        let values_data = get_random_f32_vec(1, i);
+        let mask_data = get_random_bool_vec(2, i);
+        let values: Array<1> = values_data.clone().into();
+        let mask: Mask<1> = mask_data.clone().into();
+
+        let indices: Vec<u32> = (0..i as u32).rev().collect();
+        let mut array: Array<1> = data.clone().into();
+
+        array.scatter_in_place_masked(&indices, &values, &mask);
+        let result: Vec<f32> = array.into();
+
+        for ((index, v), m) in indices.iter().zip(values_data.iter()).zip(mask_data.iter()) {
+            if *m {
+                assert_eq!(result[*index as usize], *v);
+            } else {
+                assert_eq!(result[*index as usize], data[*index as usize]);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn scatter_out_of_bounds() {
+    let mut array: Array<1> = get_random_f32_vec(0, 17).into();
+    let values: Array<1> = get_random_f32_vec(1, 2).into();
+
+    array.scatter_in_place(&[3, 17], &values);
+}
+
+#[test]
+fn scatter_add_in_place() {
+    for i in 1..32 {
+        for j in 0..100 {
+            let indices = get_random_indices(1, j, i);
+            let values_data = get_random_f32_vec(2, j);
+            let values: Array<1> = values_data.clone().into();
+            let mut histogram = Array::zeros(&[i]);
+
+            histogram.scatter_add_in_place(&indices, &values);
+
+            let mut target = vec![0.0; i];
+
+            for (index, v) in indices.iter().zip(values_data.iter()) {
+                target[*index as usize] += *v;
+            }
+
+            let result: Vec<f32> = histogram.into();
+
+            assert_eq!(result, target);
+        }
+    }
+}
+
+#[test]
+fn scatter_add_in_place_masked() {
+    for i in 1..32 {
+        for j in 0..100 {
+            let indices = get_random_indices(1, j, i);
+            let mask_data = get_random_bool_vec(3, j);
+            let mask: Mask<1> = mask_data.clone().into();
+            let values: Array<1> = vec![1.0; j].into();
+            let mut histogram = Array::zeros(&[i]);
+
+            histogram.scatter_add_in_place_masked(&indices, &values, &mask);
+
+            let mut target = vec![0.0; i];
+
+            for (index, m) in indices.iter().zip(mask_data.iter()) {
+                if *m {
+                    target[*index as usize] += 1.0;
+                }
+            }
+
+            let result: Vec<f32> = histogram.into();
+
+            assert_eq!(result, target);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn iter1d() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let array: Array<1> = data.clone().into();
+
+        assert_eq!(array.iter().len(), i);
+        assert_eq!(array.iter().collect::<Vec<f32>>(), data);
+        assert_eq!((&array).into_iter().collect::<Vec<f32>>(), data);
+        assert_eq!(array.into_iter().collect::<Vec<f32>>(), data);
+    }
+}
+
+#[test]
+fn iter2d() {
+    for rows in 1..32 {
+        for columns in 1..32 {
+            let data = get_random_f32_vec(0, rows * columns);
+            let array = Array::from_vec(&data, [rows, columns]);
+
+            assert_eq!(array.iter().collect::<Vec<f32>>(), data);
+
+            let mut into_iter = array.clone().into_iter();
+            assert_eq!(into_iter.len(), rows * columns);
+            into_iter.next();
+            assert_eq!(into_iter.len(), rows * columns - 1);
+            assert_eq!(into_iter.collect::<Vec<f32>>(), data[1..]);
+        }
+    }
+}
+
+#[test]
+fn iter_mut() {
+    for columns in 1..40 {
+        let data = get_random_f32_vec(0, 3 * columns);
+        let mut array = Array::from_vec(&data, [3, columns]);
+
+        for element in array.iter_mut() {
+            *element *= 2.0;
+        }
+
+        for element in &mut array {
+            *element += 1.0;
+        }
+
+        let expected: Vec<f32> = data.iter().map(|d| d * 2.0 + 1.0).collect();
+        assert_eq!(array, Array::from_vec(&expected, [3, columns]));
+    }
+}
+
+#[test]
+fn indexed_iter() {
+    let data = get_random_f32_vec(0, 4 * 19);
+    let array = Array::from_vec(&data, [4, 19]);
+
+    assert_eq!(array.indexed_iter().len(), 4 * 19);
+
+    for (i, ([r, c], value)) in array.indexed_iter().enumerate() {
+        assert_eq!([r, c], [i / 19, i % 19]);
+        assert_eq!(value, array.get(r, c));
+    }
+}
+
+#[test]
+fn from_iterator_and_extend() {
+    let array: Array<1> = (0..40).map(|i| i as f32).collect();
+    assert_eq!(array, Array::arange(0.0, 40.0, 1.0));
+
+    let data = get_random_f32_vec(0, 20);
+    let mut array: Array<1> = data.iter().collect();
+    array.extend(data.iter());
+    array.extend([1.0, 2.0]);
+
+    let expected = [data.clone(), data, vec![1.0, 2.0]].concat();
+    assert_eq!(array.iter().collect::<Vec<f32>>(), expected);
+}
+
+#[test]
+fn mask_iter() {
+    for rows in 1..20 {
+        for columns in [1, 15, 16, 17, 33] {
+            let data = get_random_bool_vec(0, rows * columns);
+            let mask = Mask::from_vec(&data, [rows, columns]);
+
+            assert_eq!(mask.iter().len(), rows * columns);
+            assert_eq!(mask.iter().collect::<Vec<bool>>(), data);
+            assert_eq!((&mask).into_iter().collect::<Vec<bool>>(), data);
+
+            for ([r, c], value) in mask.indexed_iter() {
+                assert_eq!(value, data[r * columns + c]);
+            }
+
+            assert_eq!(mask.into_iter().collect::<Vec<bool>>(), data);
+        }
+    }
+}
+
+#[test]
+fn mask_from_iterator_and_extend() {
+    let data = get_random_bool_vec(0, 37);
+    let mut mask: Mask<1> = data.iter().collect();
+    mask.extend(data.iter().map(|d| !d));
+    mask.assert_invariants_satisfied();
+
+    let expected: Vec<bool> = data
+        .iter()
+        .copied()
+        .chain(data.iter().map(|d| !d))
+        .collect();
+    assert_eq!(mask, expected.into_iter().collect());
+}
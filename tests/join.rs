@@ -0,0 +1,279 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Error, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+/// the elements of a row-major matrix in `columns`
+fn columns_of<T: Copy>(data: &[T], column_count: usize, columns: std::ops::Range<usize>) -> Vec<T> {
+    data.chunks(column_count)
+        .flat_map(|row| row[columns.clone()].to_vec())
+        .collect()
+}
+
+#[test]
+fn concatenate1d() {
+    for i in 1..40 {
+        for j in 1..40 {
+            let data1 = get_random_f32_vec(0, i);
+            let data2 = get_random_f32_vec(1, j);
+            let array1: Array<1> = data1.clone().into();
+            let array2: Array<1> = data2.clone().into();
+
+            let result: Vec<f32> = Array::concatenate(&[&array1, &array2], 0).into();
+            assert_eq!(result, [data1, data2].concat());
+        }
+    }
+}
+
+#[test]
+fn concatenate2d() {
+    for rows in [1, 2, 5] {
+        for (columns1, columns2) in [(1, 1), (3, 20), (16, 16), (17, 15), (32, 1)] {
+            let data1 = get_random_f32_vec(0, rows * columns1);
+            let data2 = get_random_f32_vec(1, rows * columns2);
+            let data3 = get_random_f32_vec(2, 3 * columns1);
+            let array1 = Array::from_vec(&data1, [rows, columns1]);
+            let array2 = Array::from_vec(&data2, [rows, columns2]);
+            let array3 = Array::from_vec(&data3, [3, columns1]);
+
+            let rows_joined = Array::concatenate(&[&array1, &array3, &array1], 0);
+            let expected = [data1.clone(), data3, data1.clone()].concat();
+            assert_eq!(
+                rows_joined,
+                Array::from_vec(&expected, [rows * 2 + 3, columns1])
+            );
+
+            let columns_joined = Array::concatenate(&[&array1, &array2], 1);
+            let expected: Vec<f32> = data1
+                .chunks(columns1)
+                .zip(data2.chunks(columns2))
+                .flat_map(|(a, b)| [a, b].concat())
+                .collect();
+            assert_eq!(
+                columns_joined,
+                Array::from_vec(&expected, [rows, columns1 + columns2])
+            );
+        }
+    }
+}
+
+#[test]
+fn split_at2d() {
+    for rows in [2, 5] {
+        for columns in [2, 15, 16, 17, 40] {
+            let data = get_random_f32_vec(0, rows * columns);
+            let array = Array::from_vec(&data, [rows, columns]);
+
+            for index in 1..rows {
+                let (top, bottom) = array.split_at(0, index);
+                assert_eq!(
+                    top,
+                    Array::from_vec(&data[..index * columns].to_vec(), [index, columns])
+                );
+                assert_eq!(
+                    bottom,
+                    Array::from_vec(&data[index * columns..].to_vec(), [rows - index, columns])
+                );
+                assert_eq!(Array::concatenate(&[&top, &bottom], 0), array);
+            }
+
+            for index in 1..columns {
+                let (left, right) = array.split_at(1, index);
+                assert_eq!(
+                    left,
+                    Array::from_vec(&columns_of(&data, columns, 0..index), [rows, index])
+                );
+                assert_eq!(
+                    right,
+                    Array::from_vec(
+                        &columns_of(&data, columns, index..columns),
+                        [rows, columns - index]
+                    )
+                );
+                assert_eq!(Array::concatenate(&[&left, &right], 1), array);
+            }
+        }
+    }
+}
+
+#[test]
+fn chunks() {
+    let data = get_random_f32_vec(0, 7 * 37);
+    let array = Array::from_vec(&data, [7, 37]);
+
+    for size in 1..40 {
+        let parts = array.chunks(1, size);
+        assert_eq!(parts.len(), 37usize.div_ceil(size));
+        assert!(parts.iter().all(|p| p.get_shape()[1] <= size));
+
+        let parts: Vec<&Array<2>> = parts.iter().collect();
+        assert_eq!(Array::concatenate(&parts, 1), array);
+
+        let rows = array.chunks(0, size);
+        let rows: Vec<&Array<2>> = rows.iter().collect();
+        assert_eq!(Array::concatenate(&rows, 0), array);
+    }
+
+    let elements: Array<1> = data.clone().into();
+    let parts = elements.chunks(0, 16);
+    assert_eq!(parts.len(), data.len().div_ceil(16));
+    assert_eq!(parts[1], data[16..32].to_vec().into());
+}
+
+#[test]
+fn stack() {
+    for columns in 1..40 {
+        let data: Vec<Vec<f32>> = (0..4).map(|i| get_random_f32_vec(i, columns)).collect();
+        let arrays: Vec<Array<1>> = data.iter().map(|d| d.clone().into()).collect();
+        let references: Vec<&Array<1>> = arrays.iter().collect();
+
+        let stacked = Array::stack(&references);
+        assert_eq!(stacked, Array::from_vec(&data.concat(), [4, columns]));
+        assert_eq!(stacked.get_row(2), arrays[2]);
+    }
+}
+
+#[test]
+fn masks() {
+    for rows in [1, 3] {
+        for (columns1, columns2) in [(1, 1), (3, 20), (16, 16), (17, 15), (32, 1)] {
+            let data1 = get_random_bool_vec(0, rows * columns1);
+            let data2 = get_random_bool_vec(1, rows * columns2);
+            let mask1 = Mask::from_vec(&data1, [rows, columns1]);
+            let mask2 = Mask::from_vec(&data2, [rows, columns2]);
+
+            let rows_joined = Mask::concatenate(&[&mask1, &mask1], 0);
+            rows_joined.assert_invariants_satisfied();
+            assert_eq!(
+                rows_joined,
+                Mask::from_vec(
+                    &[data1.clone(), data1.clone()].concat(),
+                    [rows * 2, columns1]
+                )
+            );
+
+            let columns_joined = Mask::concatenate(&[&mask1, &mask2], 1);
+            columns_joined.assert_invariants_satisfied();
+            let expected: Vec<bool> = data1
+                .chunks(columns1)
+                .zip(data2.chunks(columns2))
+                .flat_map(|(a, b)| [a, b].concat())
+                .collect();
+            assert_eq!(
+                columns_joined,
+                Mask::from_vec(&expected, [rows, columns1 + columns2])
+            );
+
+            let (left, right) = columns_joined.split_at(1, columns1);
+            left.assert_invariants_satisfied();
+            right.assert_invariants_satisfied();
+            assert_eq!(left, mask1);
+            assert_eq!(right, mask2);
+
+            let (top, bottom) = rows_joined.split_at(0, rows);
+            assert_eq!(top, mask1);
+            assert_eq!(bottom, mask1);
+
+            let parts = columns_joined.chunks(1, 5);
+            let parts: Vec<&Mask<2>> = parts.iter().collect();
+            assert_eq!(Mask::concatenate(&parts, 1), columns_joined);
+        }
+    }
+
+    let data: Vec<Vec<bool>> = (0..3).map(|i| get_random_bool_vec(i, 20)).collect();
+    let masks: Vec<Mask<1>> = data.iter().map(|d| d.clone().into()).collect();
+    let stacked = Mask::stack(&masks.iter().collect::<Vec<_>>());
+    stacked.assert_invariants_satisfied();
+    assert_eq!(stacked, Mask::from_vec(&data.concat(), [3, 20]));
+}
+
+#[test]
+fn zero_columns() {
+    let array = Array::<2>::zeros(&[4, 0]);
+
+    let (top, bottom) = array.split_at(0, 2);
+    assert_eq!(top, Array::zeros(&[2, 0]));
+    assert_eq!(bottom, Array::zeros(&[2, 0]));
+
+    let parts = array.chunks(0, 3);
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[1], Array::zeros(&[1, 0]));
+    assert_eq!(
+        Array::concatenate(&[&array, &array], 0),
+        Array::zeros(&[8, 0])
+    );
+    assert_eq!(Array::concatenate(&[&array, &array], 1), array);
+
+    let empty = Array::<1>::zeros(&[0]);
+    assert_eq!(Array::stack(&[&empty, &empty]), Array::zeros(&[2, 0]));
+
+    let mask = Mask::<2>::zeros(&[4, 0]);
+
+    let (top, bottom) = mask.split_at(0, 2);
+    assert_eq!(top, Mask::zeros(&[2, 0]));
+    assert_eq!(bottom, Mask::zeros(&[2, 0]));
+
+    let parts = mask.chunks(0, 3);
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[1], Mask::zeros(&[1, 0]));
+    assert_eq!(Mask::concatenate(&[&mask, &mask], 0), Mask::zeros(&[8, 0]));
+    assert_eq!(Mask::concatenate(&[&mask, &mask], 1), mask);
+
+    let empty = Mask::<1>::zeros(&[0]);
+    assert_eq!(Mask::stack(&[&empty, &empty]), Mask::zeros(&[2, 0]));
+}
+
+#[test]
+fn errors() {
+    let a = Array::<2>::zeros(&[2, 3]);
+    let b = Array::<2>::zeros(&[3, 4]);
+
+    assert_eq!(
+        Array::<2>::try_concatenate(&[], 0).err(),
+        Some(Error::NoInputs)
+    );
+    assert_eq!(
+        Array::try_concatenate(&[&a, &b], 0).err(),
+        Some(Error::ShapeMismatch {
+            expected: vec![3, 3],
+            found: vec![3, 4]
+        })
+    );
+    assert_eq!(
+        Array::try_concatenate(&[&a, &a], 2).err(),
+        Some(Error::InvalidAxis {
+            axis: 2,
+            dimensions: 2
+        })
+    );
+    assert!(a.try_split_at(1, 0).is_err());
+    assert!(a.try_split_at(1, 3).is_err());
+    assert!(a.try_split_at(1, 2).is_ok());
+    assert!(Array::try_stack(&[&Array::zeros(&[3]), &Array::zeros(&[4])]).is_err());
+    assert_eq!(
+        Mask::<1>::try_concatenate(&[], 0).err(),
+        Some(Error::NoInputs)
+    );
+}
+
+#[test]
+#[should_panic]
+fn chunks_of_size_zero() {
+    Array::<1>::zeros(&[4]).chunks(0, 0);
+}
@@ -0,0 +1,110 @@
+#![feature(portable_simd)]
+
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use std::simd::{f32x16, num::SimdFloat, StdFloat};
+
+use fast_arrays::{assert_array_approx_eq, Array};
+use utils::get_random_f32_vec;
+
+#[test]
+fn map16() {
+    for rows in 1..20 {
+        for columns in 1..40 {
+            let data = get_random_f32_vec(0, rows * columns);
+            let array = Array::from_vec(&data, [rows, columns]);
+
+            let result = array.map16(|r| r * r + f32x16::splat(1.0));
+            let expected: Vec<f32> = data.iter().map(|d| d * d + 1.0).collect();
+            assert_eq!(result, Array::from_vec(&expected, [rows, columns]));
+        }
+    }
+}
+
+#[test]
+fn map16_sees_zero_padding() {
+    for i in 1..64 {
+        let mut array = Array::<1>::ones(&[i]);
+        let mut sum = 0.0;
+        let mut registers = 0;
+
+        array.map16_in_place(|r| {
+            sum += r.reduce_sum();
+            registers += 1;
+            f32x16::splat(3.0)
+        });
+
+        assert_eq!(sum, i as f32);
+        assert_eq!(registers, i.div_ceil(16));
+
+        // the padding written by the previous call was set to zero again
+        let mut sum = 0.0;
+        array.map16(|r| {
+            sum += r.reduce_sum();
+            r
+        });
+        assert_eq!(sum, 3.0 * i as f32);
+        assert_eq!(array, Array::new_from_value(&[i], 3.0));
+    }
+}
+
+#[test]
+fn zip_map16() {
+    for rows in 1..10 {
+        for columns in 1..40 {
+            let data1 = get_random_f32_vec(0, rows * columns);
+            let data2 = get_random_f32_vec(1, rows * columns);
+            let data3 = get_random_f32_vec(2, rows * columns);
+            let a = Array::from_vec(&data1, [rows, columns]);
+            let b = Array::from_vec(&data2, [rows, columns]);
+            let c = Array::from_vec(&data3, [rows, columns]);
+
+            let result = a.zip_map16(&b, |a, b| a.simd_max(b) - b);
+            assert_eq!(result, a.max(&b).sub(&b));
+
+            let result = a.zip3_map16(&b, &c, |a, b, c| a.mul_add(b, c));
+            // the fallback fmadd rounds the product before the addition
+            assert_array_approx_eq!(result, c.fmadd(&a, &b), abs <= 1e-2);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn zip_map16_different_shapes() {
+    let a = Array::<2>::zeros(&[2, 3]);
+    a.zip_map16(&Array::zeros(&[3, 2]), |a, b| a + b);
+}
+
+#[test]
+fn map() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let array: Array<1> = data.clone().into();
+
+        let result: Vec<f32> = array.map(|x| x.exp() - 1.0).into();
+        let expected: Vec<f32> = data.iter().map(|d| d.exp() - 1.0).collect();
+        assert_eq!(result, expected);
+    }
+
+    let mut array = Array::from_vec(&get_random_f32_vec(0, 3 * 20), [3, 20]);
+    let expected = array.abs();
+    array.map_in_place(f32::abs);
+    assert_eq!(array, expected);
+}
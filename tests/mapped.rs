@@ -0,0 +1,192 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![cfg(feature = "mmap")]
+
+mod utils;
+
+use std::{fs, io::ErrorKind, path::PathBuf, process};
+
+use fast_arrays::{Array, MappedArray};
+use utils::{assert_approximate, assert_approximate_vector, get_random_f32_vec};
+
+/// a file in the temporary directory that is removed when it is dropped
+struct TemporaryFile(PathBuf);
+
+impl TemporaryFile {
+    fn new(name: &str) -> Self {
+        Self(std::env::temp_dir().join(format!("fast_arrays_{}_{}.bin", name, process::id())))
+    }
+
+    fn write_array<const D: usize>(&self, array: &Array<D>) {
+        let mut bytes = Vec::new();
+        array.write_mapped(&mut bytes).unwrap();
+        fs::write(&self.0, bytes).unwrap();
+    }
+}
+
+impl Drop for TemporaryFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn layout() {
+    let array = Array::from_vec(&vec![1.0, 2.0, 3.0, 4.0], [2, 2]);
+
+    let mut bytes = Vec::new();
+    array.write_mapped(&mut bytes).unwrap();
+
+    assert_eq!(bytes.len(), 64 + 2 * 64);
+    assert_eq!(&bytes[..12], b"FASTARR\0\x01\x00\x02\x02");
+    assert!(bytes[28..64].iter().all(|b| *b == 0));
+    assert_eq!(&bytes[64..68], &1.0f32.to_le_bytes());
+    assert_eq!(&bytes[68..72], &2.0f32.to_le_bytes());
+    assert!(bytes[72..128].iter().all(|b| *b == 0));
+    assert_eq!(&bytes[128..132], &3.0f32.to_le_bytes());
+}
+
+#[test]
+fn read_only_operations2d() {
+    let file = TemporaryFile::new("read_only_operations2d");
+
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_f32_vec(0, i * j);
+            let array = Array::from_vec(&data, [i, j]);
+            file.write_array(&array);
+
+            let mapped = unsafe { MappedArray::<2>::open(&file.0).unwrap() };
+            assert_eq!(mapped.get_shape(), [i, j]);
+            let view = mapped.view();
+
+            for r in 0..i {
+                for c in 0..j {
+                    assert_eq!(view.get(r, c), array.get(r, c));
+                }
+            }
+
+            assert_approximate(view.sum(), array.sum(), 1e-4);
+            assert_eq!(view.max_reduce(), array.max_reduce());
+            assert_eq!(view.min_reduce(), array.min_reduce());
+
+            let vector: Array<1> = get_random_f32_vec(1, j).into();
+            let result: Vec<f32> = view.vector_multiplication(&vector.as_view()).into();
+            let expected: Vec<f32> = array.vector_multiplication(&vector).into();
+            assert_approximate_vector(&result, &expected, 1e-4);
+
+            let mask = view.compare_scalar_greater_than(0.5);
+            let expected = array.compare_scalar_greater_than(0.5);
+
+            for r in 0..i {
+                for c in 0..j {
+                    assert_eq!(mask.get(r, c), expected.get(r, c));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn read_only_operations1d() {
+    let file = TemporaryFile::new("read_only_operations1d");
+
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let array: Array<1> = data.clone().into();
+        file.write_array(&array);
+
+        let mapped = unsafe { MappedArray::<1>::open(&file.0).unwrap() };
+        let copy: Vec<f32> = mapped.to_array().into();
+
+        assert_eq!(copy, data);
+        assert_approximate(mapped.view().sum(), array.sum(), 1e-4);
+        assert_approximate(
+            mapped.view().dot_product(&array.as_view()),
+            array.dot_product(&array),
+            1e-4,
+        );
+    }
+}
+
+#[test]
+fn empty_rows() {
+    let file = TemporaryFile::new("empty_rows");
+    file.write_array(&Array::<2>::zeros(&[3, 0]));
+
+    let mapped = unsafe { MappedArray::<2>::open(&file.0).unwrap() };
+    assert_eq!(mapped.get_shape(), [3, 0]);
+    assert_eq!(mapped.to_array().get_shape(), [3, 0]);
+}
+
+#[test]
+fn zero_padding() {
+    for columns in [1, 5, 17, 31] {
+        let array =
+            Array::from_vec(&get_random_f32_vec(0, 2 * columns), [2, columns]).add_scalar(1.0);
+
+        let mut bytes = Vec::new();
+        array.write_mapped(&mut bytes).unwrap();
+
+        let row_length = columns.div_ceil(16) * 64;
+        assert_eq!(bytes.len(), 64 + 2 * row_length);
+
+        for row in bytes[64..].chunks(row_length) {
+            assert!(row[columns * 4..].iter().all(|b| *b == 0));
+        }
+    }
+}
+
+#[test]
+fn invalid_files() {
+    let file = TemporaryFile::new("invalid_files");
+    let array = Array::from_vec(&get_random_f32_vec(0, 40), [2, 20]);
+
+    let mut bytes = Vec::new();
+    array.write_mapped(&mut bytes).unwrap();
+
+    // truncated
+    fs::write(&file.0, &bytes[..bytes.len() - 4]).unwrap();
+    let error = unsafe { MappedArray::<2>::open(&file.0) }.err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+    // trailing data
+    let mut longer = bytes.clone();
+    longer.extend_from_slice(&[0; 64]);
+    fs::write(&file.0, &longer).unwrap();
+    let error = unsafe { MappedArray::<2>::open(&file.0) }.err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    // truncated in the padding of the header, without elements
+    let mut empty = Vec::new();
+    Array::<2>::zeros(&[3, 0]).write_mapped(&mut empty).unwrap();
+    fs::write(&file.0, &empty[..28]).unwrap();
+    let error = unsafe { MappedArray::<2>::open(&file.0) }.err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+    // wrong number of dimensions
+    fs::write(&file.0, &bytes).unwrap();
+    let error = unsafe { MappedArray::<1>::open(&file.0) }.err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    // the unpadded format can't be mapped
+    let mut unpadded = Vec::new();
+    array.write_to(&mut unpadded).unwrap();
+    fs::write(&file.0, &unpadded).unwrap();
+    let error = unsafe { MappedArray::<2>::open(&file.0) }.err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
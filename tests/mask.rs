@@ -21,6 +21,28 @@ use rstest::rstest;
 
 use crate::utils::get_random_bool_vec;
 
+#[rstest]
+#[case::too_few_dimensions(r#"{"masks":[true,false],"shape":[2]}"#, "dimension(s)")]
+#[case::too_many_dimensions(r#"{"masks":[true,false],"shape":[1,1,2]}"#, "dimension(s)")]
+#[case::too_few_elements(r#"{"masks":[true,false],"shape":[2,2]}"#, "element(s)")]
+#[case::too_many_elements(r#"{"masks":[true,false,true],"shape":[1,2]}"#, "element(s)")]
+#[case::empty_axis(r#"{"masks":[true],"shape":[0,1]}"#, "element(s)")]
+#[case::overflow(r#"{"masks":[],"shape":[18446744073709551615,2]}"#, "overflows")]
+fn deserialize_malformed2d(#[case] json: &str, #[case] message: &str) {
+    let result = serde_json::from_str::<Mask<2>>(json);
+
+    match result {
+        Ok(_) => panic!("deserialized a malformed mask"),
+        Err(error) => assert!(error.to_string().contains(message), "{}", error),
+    }
+}
+
+#[test]
+fn deserialize_malformed1d() {
+    assert!(serde_json::from_str::<Mask<1>>(r#"{"masks":[true],"shape":[2]}"#).is_err());
+    assert!(serde_json::from_str::<Mask<1>>(r#"{"masks":[true],"shape":[]}"#).is_err());
+}
+
 #[test]
 fn convert() {
     for i in 0..64 {
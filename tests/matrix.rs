@@ -97,3 +97,74 @@ fn matrix_matrix_multiplication_reference(
 
     result
 }
+
+#[test]
+fn transpose() {
+    for i in 1..40 {
+        for j in 1..40 {
+            let data = get_random_f32_vec(0, i * j);
+            let matrix = Array::<2>::from_vec(&data, [i, j]);
+
+            let transposed = matrix.transpose();
+            assert_eq!(transposed.get_shape(), [j, i]);
+
+            for row in 0..i {
+                for column in 0..j {
+                    assert_eq!(transposed.get(column, row), data[row * j + column]);
+                }
+            }
+
+            assert_eq!(transposed.transpose(), matrix);
+        }
+    }
+}
+
+#[test]
+fn multiplication_out_of_place() {
+    for i in 1..20 {
+        for j in 1..40 {
+            let k = 17;
+            let matrix_a = Array::<2>::from_vec(&get_random_f32_vec(0, i * j), [i, j]);
+            let matrix_b = Array::<2>::from_vec(&get_random_f32_vec(1, j * k), [j, k]);
+            let vector: Array<1> = get_random_f32_vec(2, j).into();
+
+            let mut output = Array::new_from_value(&[i], f32::NAN);
+            matrix_a.vector_multiplication_out_of_place(&vector, &mut output);
+            assert_eq!(output, matrix_a.vector_multiplication(&vector));
+
+            let expected = matrix_a.matrix_multiplication(&matrix_b);
+            let mut output = Array::new_from_value(&[i, k], f32::NAN);
+            matrix_a.matrix_multiplication_out_of_place(&matrix_b, &mut output);
+            assert_eq!(output, expected);
+
+            // the padding of both operands isn't zero after add_scalar
+            let mut transposed_b = Array::zeros(&[k, j]);
+            matrix_b.transpose_out_of_place(&mut transposed_b);
+            let transposed_b = transposed_b.add_scalar(1.0);
+            let matrix_a = matrix_a.add_scalar(1.0);
+            let matrix_b = matrix_b.add_scalar(1.0);
+
+            let mut output = Array::zeros(&[i, k]);
+            matrix_a.matrix_multiplication_transposed_out_of_place(&transposed_b, &mut output);
+            assert_eq!(output, matrix_a.matrix_multiplication(&matrix_b));
+        }
+    }
+}
+
+#[test]
+fn empty_inner_dimension() {
+    let matrix_a = Array::<2>::zeros(&[2, 0]);
+
+    assert_eq!(
+        matrix_a.matrix_multiplication(&Array::zeros(&[0, 3])),
+        Array::zeros(&[2, 3])
+    );
+
+    let mut output = Array::new_from_value(&[2, 3], f32::NAN);
+    matrix_a.matrix_multiplication_transposed_out_of_place(&Array::zeros(&[3, 0]), &mut output);
+    assert_eq!(output, Array::zeros(&[2, 3]));
+
+    let mut output = Array::new_from_value(&[2], f32::NAN);
+    matrix_a.vector_multiplication_out_of_place(&Array::zeros(&[0]), &mut output);
+    assert_eq!(output, Array::zeros(&[2]));
+}
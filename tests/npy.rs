@@ -0,0 +1,255 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use std::io::ErrorKind;
+
+use fast_arrays::{Array, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+/// create a .npy file the same way as `numpy.save`
+fn npy_file(dictionary: &str, payload: &[u8]) -> Vec<u8> {
+    let mut dictionary = dictionary.to_string();
+
+    while !(10 + dictionary.len() + 1).is_multiple_of(64) {
+        dictionary.push(' ');
+    }
+
+    dictionary.push('\n');
+
+    let mut file = b"\x93NUMPY\x01\x00".to_vec();
+    file.extend_from_slice(&(dictionary.len() as u16).to_le_bytes());
+    file.extend_from_slice(dictionary.as_bytes());
+    file.extend_from_slice(payload);
+
+    file
+}
+
+#[test]
+fn array_round_trip1d() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let array: Array<1> = data.clone().into();
+
+        let mut bytes = Vec::new();
+        array.write_npy(&mut bytes).unwrap();
+        assert_eq!(bytes.len() % 64, (4 * i) % 64);
+
+        let read = Array::<1>::read_npy(bytes.as_slice()).unwrap();
+        let read: Vec<f32> = read.into();
+
+        assert_eq!(read, data);
+    }
+}
+
+#[test]
+fn array_round_trip2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_f32_vec(0, i * j);
+            let array = Array::from_vec(&data, [i, j]);
+
+            let mut bytes = Vec::new();
+            array.write_npy(&mut bytes).unwrap();
+
+            let read = Array::<2>::read_npy(bytes.as_slice()).unwrap();
+            assert_eq!(read.get_shape(), [i, j]);
+
+            let read: Vec<f32> = read.into();
+            assert_eq!(read, data);
+        }
+    }
+}
+
+#[test]
+fn mask_round_trip2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_bool_vec(0, i * j);
+            let mask = Mask::from_vec(&data, [i, j]);
+
+            let mut bytes = Vec::new();
+            mask.write_npy(&mut bytes).unwrap();
+
+            let read = Mask::<2>::read_npy(bytes.as_slice()).unwrap();
+            read.assert_invariants_satisfied();
+            assert_eq!(read.get_shape(), &[i, j]);
+
+            for r in 0..i {
+                for c in 0..j {
+                    assert_eq!(read.get(r, c), data[r * j + c]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn write_layout() {
+    let array = Array::from_vec(&vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], [2, 3]);
+    let payload: Vec<u8> = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+
+    let mut bytes = Vec::new();
+    array.write_npy(&mut bytes).unwrap();
+
+    assert_eq!(
+        bytes,
+        npy_file(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }",
+            &payload
+        )
+    );
+
+    let mask: Mask<1> = vec![true, false, true].into();
+    let mut bytes = Vec::new();
+    mask.write_npy(&mut bytes).unwrap();
+
+    assert_eq!(
+        bytes,
+        npy_file(
+            "{'descr': '|b1', 'fortran_order': False, 'shape': (3,), }",
+            &[1, 0, 1]
+        )
+    );
+}
+
+#[test]
+fn read_numpy_file() {
+    let payload: Vec<u8> = [1.5f32, -2.0]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let file = npy_file(
+        "{'descr': '<f4', 'fortran_order': False, 'shape': (1, 2), }",
+        &payload,
+    );
+
+    let array = Array::<2>::read_npy(file.as_slice()).unwrap();
+    assert_eq!(array.get_shape(), [1, 2]);
+    assert_eq!(array.get(0, 0), 1.5);
+    assert_eq!(array.get(0, 1), -2.0);
+}
+
+#[test]
+fn unsupported_dtype() {
+    let file = npy_file(
+        "{'descr': '<f8', 'fortran_order': False, 'shape': (1,), }",
+        &[0; 8],
+    );
+
+    let error = Array::<1>::read_npy(file.as_slice()).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("<f8"));
+
+    let file = npy_file(
+        "{'descr': '<f4', 'fortran_order': False, 'shape': (1,), }",
+        &[0; 4],
+    );
+    assert!(Mask::<1>::read_npy(file.as_slice()).is_err());
+}
+
+#[test]
+fn fortran_order() {
+    let file = npy_file(
+        "{'descr': '<f4', 'fortran_order': True, 'shape': (2, 2), }",
+        &[0; 16],
+    );
+
+    let error = Array::<2>::read_npy(file.as_slice()).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("Fortran"));
+}
+
+#[test]
+fn wrong_rank() {
+    let file = npy_file(
+        "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 2), }",
+        &[0; 16],
+    );
+
+    assert!(Array::<1>::read_npy(file.as_slice()).is_err());
+
+    let file = npy_file(
+        "{'descr': '<f4', 'fortran_order': False, 'shape': (), }",
+        &[0; 4],
+    );
+
+    assert!(Array::<1>::read_npy(file.as_slice()).is_err());
+}
+
+#[test]
+fn truncated() {
+    let file = npy_file(
+        "{'descr': '<f4', 'fortran_order': False, 'shape': (4,), }",
+        &[0; 15],
+    );
+
+    let error = Array::<1>::read_npy(file.as_slice()).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn npz() {
+    use fast_arrays::{NpzReader, NpzWriter};
+    use std::io::Cursor;
+
+    for compressed in [false, true] {
+        let array1: Array<1> = get_random_f32_vec(0, 37).into();
+        let array2 = Array::from_vec(&get_random_f32_vec(1, 12), [3, 4]);
+        let mask: Mask<1> = get_random_bool_vec(2, 19).into();
+
+        let cursor = Cursor::new(Vec::new());
+        let mut writer = if compressed {
+            NpzWriter::new_compressed(cursor)
+        } else {
+            NpzWriter::new(cursor)
+        };
+
+        writer.add_array("a", &array1).unwrap();
+        writer.add_array("b", &array2).unwrap();
+        writer.add_mask("m", &mask).unwrap();
+        let cursor = writer.finish().unwrap();
+
+        let mut reader = NpzReader::new(cursor).unwrap();
+        let mut names = reader.names();
+        names.sort();
+        assert_eq!(names, vec!["a", "b", "m"]);
+
+        let read1: Vec<f32> = reader.array::<1>("a").unwrap().into();
+        let expected1: Vec<f32> = array1.into();
+        assert_eq!(read1, expected1);
+
+        let read2 = reader.array::<2>("b").unwrap();
+        assert_eq!(read2.get_shape(), [3, 4]);
+        assert_eq!(read2.get(2, 3), array2.get(2, 3));
+
+        let read_mask: Vec<bool> = reader.mask::<1>("m").unwrap().into();
+        let expected_mask: Vec<bool> = mask.into();
+        assert_eq!(read_mask, expected_mask);
+
+        assert_eq!(
+            reader.array::<1>("missing").err().unwrap().kind(),
+            ErrorKind::NotFound
+        );
+        assert!(reader.array::<1>("b").is_err());
+        assert!(reader.array::<1>("m").is_err());
+    }
+}
@@ -90,3 +90,25 @@ fn ref_out_of_place(
         }
     }
 }
+
+#[rstest]
+#[case::sqrt(Array::sqrt_out_of_place, f32::sqrt)]
+#[case::square(Array::square_out_of_place, |x| x * x)]
+#[case::abs(Array::abs_out_of_place, f32::abs)]
+fn out_of_place(
+    #[case] test_function: fn(&Array<1>, &mut Array<1>),
+    #[case] target_function: fn(f32) -> f32,
+) {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let array: Array<1> = data.clone().into();
+        let mut output = Array::new_from_value(&[i], f32::NAN);
+
+        test_function(&array, &mut output);
+        let result: Vec<f32> = output.into();
+
+        for (d, r) in data.iter().zip(result.iter()) {
+            assert_approximate(*r, target_function(*d), 0.001);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Error, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+/// shapes with `count` elements and different numbers of columns
+fn shapes(count: usize) -> Vec<[usize; 2]> {
+    (1..=count)
+        .filter(|columns| count.is_multiple_of(*columns))
+        .map(|columns| [count / columns, columns])
+        .collect()
+}
+
+#[test]
+fn reshape_array() {
+    for count in [1, 12, 16, 32, 48, 60, 64, 96] {
+        let data = get_random_f32_vec(0, count);
+        let array: Array<1> = data.clone().into();
+
+        for shape in shapes(count) {
+            let reshaped = array.reshape(shape);
+            assert_eq!(reshaped, Array::from_vec(&data, shape));
+            assert_eq!(reshaped.flatten(), array);
+
+            for other_shape in shapes(count) {
+                let expected = Array::from_vec(&data, other_shape);
+                assert_eq!(reshaped.reshape(other_shape), expected);
+                assert_eq!(reshaped.clone().into_shape(other_shape), expected);
+            }
+
+            assert_eq!(reshaped.into_flattened(), array);
+        }
+    }
+}
+
+#[test]
+fn reshape_mask() {
+    for count in [1, 12, 16, 32, 48, 60, 64, 96] {
+        let data = get_random_bool_vec(0, count);
+        let mask: Mask<1> = data.clone().into();
+
+        for shape in shapes(count) {
+            let reshaped = mask.reshape(shape);
+            reshaped.assert_invariants_satisfied();
+            assert_eq!(reshaped, Mask::from_vec(&data, shape));
+            assert_eq!(reshaped.flatten(), mask);
+
+            for other_shape in shapes(count) {
+                let expected = Mask::from_vec(&data, other_shape);
+                let converted = reshaped.clone().into_shape(other_shape);
+                converted.assert_invariants_satisfied();
+                assert_eq!(reshaped.reshape(other_shape), expected);
+                assert_eq!(converted, expected);
+            }
+
+            assert_eq!(reshaped.into_flattened(), mask);
+        }
+    }
+}
+
+#[test]
+fn operations_after_reshape() {
+    let data = get_random_f32_vec(0, 6 * 20);
+    let array: Array<1> = data.clone().into();
+    let reshaped = array.reshape([6, 20]);
+
+    // the padding of the repacked rows must not affect the results
+    assert_eq!(reshaped.max_reduce(), array.max_reduce());
+    assert_eq!(reshaped.sum(), Array::from_vec(&data, [6, 20]).sum());
+    assert_eq!(reshaped.min_reduce(), array.min_reduce());
+}
+
+#[test]
+fn wrong_element_count() {
+    let array = Array::<2>::zeros(&[3, 4]);
+
+    assert_eq!(
+        array.try_reshape([5, 2]).err(),
+        Some(Error::LengthMismatch {
+            expected: 10,
+            found: 12
+        })
+    );
+    assert!(array.clone().try_into_shape([12]).is_ok());
+    assert!(Mask::<1>::zeros(&[7]).try_into_shape([2, 3]).is_err());
+}
+
+#[test]
+#[should_panic]
+fn reshape_empty_axis() {
+    Array::<1>::zeros(&[4]).reshape([0, 4]);
+}
+
+#[test]
+fn reshape_without_columns() {
+    let array = Array::<2>::zeros(&[0, 5]);
+    assert_eq!(array.reshape([5, 0]), Array::<2>::zeros(&[5, 0]));
+    assert_eq!(array.reshape([0]), Array::<1>::zeros(&[0]));
+    assert_eq!(
+        Array::<2>::zeros(&[5, 0]).reshape([0, 17]),
+        Array::<2>::zeros(&[0, 17])
+    );
+
+    let mask = Mask::<2>::zeros(&[0, 5]);
+    assert_eq!(mask.reshape([5, 0]), Mask::<2>::zeros(&[5, 0]));
+    assert_eq!(mask.clone().into_shape([5, 0]), Mask::<2>::zeros(&[5, 0]));
+    assert_eq!(
+        Mask::<2>::zeros(&[5, 0]).reshape([0, 17]),
+        Mask::<2>::zeros(&[0, 17])
+    );
+}
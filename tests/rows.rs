@@ -0,0 +1,209 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn get_row() {
+    for rows in 1..8 {
+        for columns in 1..40 {
+            let data = get_random_f32_vec(0, rows * columns);
+            let array = Array::from_vec(&data, [rows, columns]);
+
+            for r in 0..rows {
+                let row: Vec<f32> = array.get_row(r).into();
+                assert_eq!(row, data[r * columns..(r + 1) * columns]);
+
+                let view: Vec<f32> = array.row(r).to_array().into();
+                assert_eq!(view, row);
+            }
+        }
+    }
+}
+
+#[test]
+fn copy_row_from() {
+    for columns in 1..40 {
+        let data = get_random_f32_vec(0, 4 * columns);
+        let values = get_random_f32_vec(1, columns);
+        let mut array = Array::from_vec(&data, [4, columns]);
+
+        array.copy_row_from(2, &values.clone().into());
+
+        let mut expected = data.clone();
+        expected[2 * columns..3 * columns].copy_from_slice(&values);
+        assert_eq!(array, Array::from_vec(&expected, [4, columns]));
+    }
+}
+
+#[test]
+fn column() {
+    for rows in 1..40 {
+        for columns in [1, 15, 16, 17, 33] {
+            let data = get_random_f32_vec(0, rows * columns);
+            let array = Array::from_vec(&data, [rows, columns]);
+
+            for c in 0..columns {
+                let column: Vec<f32> = array.column(c).into();
+                let expected: Vec<f32> = (0..rows).map(|r| data[r * columns + c]).collect();
+                assert_eq!(column, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn set_column() {
+    for rows in 1..40 {
+        let data = get_random_f32_vec(0, rows * 20);
+        let values = get_random_f32_vec(1, rows);
+        let mut array = Array::from_vec(&data, [rows, 20]);
+
+        array.set_column(17, &values.clone().into());
+
+        let mut expected = data.clone();
+
+        for r in 0..rows {
+            expected[r * 20 + 17] = values[r];
+        }
+
+        assert_eq!(array, Array::from_vec(&expected, [rows, 20]));
+    }
+}
+
+#[test]
+fn rows() {
+    let data = get_random_f32_vec(0, 5 * 21);
+    let array = Array::from_vec(&data, [5, 21]);
+
+    assert_eq!(array.rows().len(), 5);
+
+    for (r, row) in array.rows().enumerate() {
+        assert_eq!(row.get_shape(), [21]);
+        assert_eq!(row.sum(), array.get_row(r).sum());
+        assert_eq!(row.to_array(), array.get_row(r));
+    }
+}
+
+#[test]
+fn rows_mut() {
+    let data = get_random_f32_vec(0, 5 * 21);
+    let mut array = Array::from_vec(&data, [5, 21]);
+
+    for (r, mut row) in array.rows_mut().enumerate() {
+        row.add_scalar_in_place(r as f32);
+    }
+
+    array.row_mut(4).set(20, 100.0);
+
+    let mut expected = data.clone();
+
+    for (i, element) in expected.iter_mut().enumerate() {
+        *element += (i / 21) as f32;
+    }
+
+    expected[4 * 21 + 20] = 100.0;
+    assert_eq!(array, Array::from_vec(&expected, [5, 21]));
+}
+
+#[test]
+fn zero_columns() {
+    let mut array = Array::<2>::zeros(&[3, 0]);
+
+    assert_eq!(array.rows().len(), 3);
+    assert!(array.rows().all(|row| row.get_shape() == [0]));
+    assert_eq!(array.row(2).to_array().get_shape(), [0]);
+    assert_eq!(array.get_row(2).get_shape(), [0]);
+
+    assert_eq!(array.rows_mut().len(), 3);
+
+    for mut row in array.rows_mut() {
+        row.add_scalar_in_place(1.0);
+    }
+
+    array.row_mut(1).set_all(1.0);
+    assert_eq!(array, Array::<2>::zeros(&[3, 0]));
+}
+
+#[test]
+#[should_panic]
+fn row_out_of_bounds() {
+    let array = Array::<2>::zeros(&[3, 4]);
+    array.row(3);
+}
+
+#[test]
+#[should_panic]
+fn copy_row_from_wrong_length() {
+    let mut array = Array::<2>::zeros(&[3, 4]);
+    array.copy_row_from(0, &Array::zeros(&[5]));
+}
+
+#[test]
+#[should_panic]
+fn set_column_wrong_length() {
+    let mut array = Array::<2>::zeros(&[3, 4]);
+    array.set_column(0, &Array::zeros(&[4]));
+}
+
+#[test]
+fn mask_rows() {
+    for rows in 1..20 {
+        for columns in [1, 15, 16, 17, 33] {
+            let data = get_random_bool_vec(0, rows * columns);
+            let mask = Mask::from_vec(&data, [rows, columns]);
+
+            for r in 0..rows {
+                let row: Vec<bool> = mask.get_row(r).into();
+                assert_eq!(row, data[r * columns..(r + 1) * columns]);
+            }
+
+            for c in 0..columns {
+                let column: Vec<bool> = mask.column(c).into();
+                let expected: Vec<bool> = (0..rows).map(|r| data[r * columns + c]).collect();
+                assert_eq!(column, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn mask_set_rows() {
+    for rows in 1..20 {
+        for columns in [1, 15, 16, 17, 33] {
+            let data = get_random_bool_vec(0, rows * columns);
+            let row = get_random_bool_vec(1, columns);
+            let column = get_random_bool_vec(2, rows);
+            let mut mask = Mask::from_vec(&data, [rows, columns]);
+
+            mask.copy_row_from(rows - 1, &row.clone().into());
+            mask.set_column(0, &column.clone().into());
+            mask.assert_invariants_satisfied();
+
+            let mut expected = data.clone();
+            expected[(rows - 1) * columns..].copy_from_slice(&row);
+
+            for r in 0..rows {
+                expected[r * columns] = column[r];
+            }
+
+            assert_eq!(mask, Mask::from_vec(&expected, [rows, columns]));
+        }
+    }
+}
@@ -0,0 +1,276 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![cfg(feature = "safetensors")]
+
+mod utils;
+
+use std::{collections::BTreeMap, io::ErrorKind};
+
+use fast_arrays::{
+    read_safetensors, read_safetensors_tensors, write_safetensors, write_safetensors_tensors,
+    Array, Error, Mask, Tensor,
+};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+/// create a safetensors file from a JSON header and the buffer
+fn safetensors_file(header: &str, buffer: &[u8]) -> Vec<u8> {
+    let mut file = (header.len() as u64).to_le_bytes().to_vec();
+    file.extend_from_slice(header.as_bytes());
+    file.extend_from_slice(buffer);
+
+    file
+}
+
+fn get_error(bytes: &[u8]) -> Error {
+    let error = read_safetensors::<1>(bytes).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    error
+        .into_inner()
+        .unwrap()
+        .downcast::<Error>()
+        .unwrap()
+        .as_ref()
+        .clone()
+}
+
+#[test]
+fn round_trip1d() {
+    for i in 0..64 {
+        let mut arrays = BTreeMap::new();
+        arrays.insert("a".to_string(), Array::<1>::from(get_random_f32_vec(0, i)));
+        arrays.insert(
+            "b".to_string(),
+            Array::<1>::from(get_random_f32_vec(1, i + 1)),
+        );
+
+        let mut bytes = Vec::new();
+        write_safetensors(&arrays, &mut bytes).unwrap();
+
+        let read = read_safetensors::<1>(bytes.as_slice()).unwrap();
+        assert_eq!(read.len(), 2);
+
+        let a: Vec<f32> = read["a"].clone().into();
+        let b: Vec<f32> = read["b"].clone().into();
+        assert_eq!(a, get_random_f32_vec(0, i));
+        assert_eq!(b, get_random_f32_vec(1, i + 1));
+    }
+}
+
+#[test]
+fn round_trip2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data = get_random_f32_vec(0, i * j);
+            let mut arrays = BTreeMap::new();
+            arrays.insert("weight".to_string(), Array::from_vec(&data, [i, j]));
+
+            let mut bytes = Vec::new();
+            write_safetensors(&arrays, &mut bytes).unwrap();
+
+            let read = read_safetensors::<2>(bytes.as_slice()).unwrap();
+            assert_eq!(read["weight"].get_shape(), [i, j]);
+
+            let read: Vec<f32> = read["weight"].clone().into();
+            assert_eq!(read, data);
+        }
+    }
+}
+
+#[test]
+fn round_trip_tensors() {
+    let mut tensors = BTreeMap::new();
+    tensors.insert(
+        "a1".to_string(),
+        Tensor::from(Array::<1>::from(get_random_f32_vec(0, 17))),
+    );
+    tensors.insert(
+        "a2".to_string(),
+        Array::from_vec(&get_random_f32_vec(1, 6), [2, 3]).into(),
+    );
+    tensors.insert(
+        "m1".to_string(),
+        Mask::<1>::from(get_random_bool_vec(2, 33)).into(),
+    );
+    tensors.insert(
+        "m2".to_string(),
+        Mask::from_vec(&get_random_bool_vec(3, 60), [3, 20]).into(),
+    );
+
+    let mut bytes = Vec::new();
+    write_safetensors_tensors(&tensors, &mut bytes).unwrap();
+
+    let read = read_safetensors_tensors(bytes.as_slice()).unwrap();
+    assert_eq!(read.len(), 4);
+
+    match &read["a1"] {
+        Tensor::Array1(a) => {
+            let a: Vec<f32> = a.clone().into();
+            assert_eq!(a, get_random_f32_vec(0, 17));
+        }
+        _ => panic!("wrong tensor type"),
+    }
+
+    match &read["a2"] {
+        Tensor::Array2(a) => {
+            assert_eq!(a.get_shape(), [2, 3]);
+            let a: Vec<f32> = a.clone().into();
+            assert_eq!(a, get_random_f32_vec(1, 6));
+        }
+        _ => panic!("wrong tensor type"),
+    }
+
+    match &read["m1"] {
+        Tensor::Mask1(m) => {
+            let m: Vec<bool> = m.clone().into();
+            assert_eq!(m, get_random_bool_vec(2, 33));
+        }
+        _ => panic!("wrong tensor type"),
+    }
+
+    match &read["m2"] {
+        Tensor::Mask2(m) => {
+            let data = get_random_bool_vec(3, 60);
+            assert_eq!(*m.get_shape(), [3, 20]);
+
+            for r in 0..3 {
+                for c in 0..20 {
+                    assert_eq!(m.get(r, c), data[r * 20 + c]);
+                }
+            }
+        }
+        _ => panic!("wrong tensor type"),
+    }
+}
+
+#[test]
+fn layout() {
+    let mut arrays = BTreeMap::new();
+    arrays.insert("x".to_string(), Array::<1>::from(vec![1.0, -2.0]));
+
+    let mut bytes = Vec::new();
+    write_safetensors(&arrays, &mut bytes).unwrap();
+
+    let header = r#"{"x":{"dtype":"F32","shape":[2],"data_offsets":[0,8]}}"#;
+    let mut expected = format!("{:<56}", header).into_bytes();
+    expected.extend_from_slice(&1.0f32.to_le_bytes());
+    expected.extend_from_slice(&(-2.0f32).to_le_bytes());
+
+    assert_eq!(&bytes[..8], &56u64.to_le_bytes());
+    assert_eq!(&bytes[8..], expected.as_slice());
+}
+
+#[test]
+fn read_reference_file() {
+    // the tensors are stored in a different order than their names, with metadata
+    let header = r#"{"__metadata__":{"format":"pt"},"b":{"dtype":"F32","shape":[1,2],"data_offsets":[0,8]},"a":{"dtype":"F32","shape":[1,1],"data_offsets":[8,12]}}"#;
+    let buffer: Vec<u8> = [3.0f32, 4.0, 5.0]
+        .iter()
+        .flat_map(|f| f.to_le_bytes())
+        .collect();
+
+    let read = read_safetensors::<2>(safetensors_file(header, &buffer).as_slice()).unwrap();
+
+    assert_eq!(read["a"].get(0, 0), 5.0);
+    assert_eq!(read["b"].get(0, 0), 3.0);
+    assert_eq!(read["b"].get(0, 1), 4.0);
+}
+
+#[test]
+fn unsupported_dtype() {
+    let header = r#"{"x":{"dtype":"F16","shape":[2],"data_offsets":[0,4]}}"#;
+
+    assert_eq!(
+        get_error(&safetensors_file(header, &[0; 4])),
+        Error::UnsupportedDtype {
+            expected: "F32",
+            found: "F16".to_string()
+        }
+    );
+
+    let header = r#"{"x":{"dtype":"BOOL","shape":[2],"data_offsets":[0,2]}}"#;
+
+    assert_eq!(
+        get_error(&safetensors_file(header, &[0; 2])),
+        Error::UnsupportedDtype {
+            expected: "F32",
+            found: "BOOL".to_string()
+        }
+    );
+}
+
+#[test]
+fn wrong_dimensions() {
+    let header = r#"{"x":{"dtype":"F32","shape":[1,2],"data_offsets":[0,8]}}"#;
+
+    assert_eq!(
+        get_error(&safetensors_file(header, &[0; 8])),
+        Error::DimensionMismatch {
+            expected: 1,
+            found: 2
+        }
+    );
+
+    let header = r#"{"x":{"dtype":"F32","shape":[1,1,2],"data_offsets":[0,8]}}"#;
+    let error = read_safetensors_tensors(safetensors_file(header, &[0; 8]).as_slice())
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn invalid_offsets() {
+    for header in [
+        // the size doesn't match the shape
+        r#"{"x":{"dtype":"F32","shape":[2],"data_offsets":[0,4]}}"#,
+        // gap at the beginning
+        r#"{"x":{"dtype":"F32","shape":[1],"data_offsets":[4,8]}}"#,
+        // overlapping tensors
+        r#"{"x":{"dtype":"F32","shape":[2],"data_offsets":[0,8]},"y":{"dtype":"F32","shape":[1],"data_offsets":[4,8]}}"#,
+        // end before begin
+        r#"{"x":{"dtype":"F32","shape":[0],"data_offsets":[4,0]}}"#,
+        // not JSON
+        r#"{"x":"#,
+    ] {
+        assert!(matches!(
+            get_error(&safetensors_file(header, &[0; 8])),
+            Error::InvalidFormat { .. }
+        ));
+    }
+}
+
+#[test]
+fn too_large_header() {
+    let mut bytes = u64::MAX.to_le_bytes().to_vec();
+    bytes.extend_from_slice(b"{}");
+
+    assert!(matches!(get_error(&bytes), Error::InvalidFormat { .. }));
+}
+
+#[test]
+fn truncated() {
+    let mut arrays = BTreeMap::new();
+    arrays.insert("x".to_string(), Array::<1>::from(get_random_f32_vec(0, 20)));
+
+    let mut bytes = Vec::new();
+    write_safetensors(&arrays, &mut bytes).unwrap();
+
+    for length in [0, 4, 20, bytes.len() - 1] {
+        let error = read_safetensors::<1>(&bytes[..length]).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,246 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use std::ops::{Add, Mul};
+
+use fast_arrays::{Array, Mask};
+use utils::{assert_approximate, get_random_bool_vec, get_random_f32_vec};
+
+use rstest::rstest;
+
+fn scan_reference(
+    data: &[f32],
+    mask: &[bool],
+    identity: f32,
+    func: fn(f32, f32) -> f32,
+) -> Vec<f32> {
+    let mut carry = identity;
+    let mut result = data.to_vec();
+
+    for (r, m) in result.iter_mut().zip(mask.iter()) {
+        if *m {
+            carry = func(carry, *r);
+            *r = carry;
+        }
+    }
+
+    result
+}
+
+/// values close to 1, so that the products don't overflow
+fn get_scan_data(seed: u64, len: usize) -> Vec<f32> {
+    get_random_f32_vec(seed, len)
+        .iter()
+        .map(|x| 1.0 + x / 100.0)
+        .collect()
+}
+
+#[rstest]
+#[case::cumsum(Array::cumsum_in_place, 0.0, f32::add)]
+#[case::cumprod(Array::cumprod_in_place, 1.0, f32::mul)]
+#[case::cummax(Array::cummax_in_place, f32::NEG_INFINITY, f32::max)]
+#[case::cummin(Array::cummin_in_place, f32::INFINITY, f32::min)]
+fn in_place(
+    #[case] test_function: fn(&mut Array<1>),
+    #[case] identity: f32,
+    #[case] target_function: fn(f32, f32) -> f32,
+) {
+    for i in 0..100 {
+        let data = get_scan_data(0, i);
+        let mut array: Array<1> = data.clone().into();
+
+        test_function(&mut array);
+        let result: Vec<f32> = array.into();
+        let target = scan_reference(&data, &vec![true; i], identity, target_function);
+
+        for (r, t) in result.iter().zip(target.iter()) {
+            assert_approximate(*r, *t, 0.001);
+        }
+    }
+}
+
+#[rstest]
+#[case::cumsum(Array::cumsum_in_place_masked, 0.0, f32::add)]
+#[case::cumprod(Array::cumprod_in_place_masked, 1.0, f32::mul)]
+#[case::cummax(Array::cummax_in_place_masked, f32::NEG_INFINITY, f32::max)]
+#[case::cummin(Array::cummin_in_place_masked, f32::INFINITY, f32::min)]
+fn in_place_masked(
+    #[case] test_function: fn(&mut Array<1>, &Mask<1>),
+    #[case] identity: f32,
+    #[case] target_function: fn(f32, f32) -> f32,
+) {
+    for i in 0..100 {
+        let data = get_scan_data(0, i);
+        let mask_data = get_random_bool_vec(1, i);
+        let mut array: Array<1> = data.clone().into();
+        let mask: Mask<1> = mask_data.clone().into();
+
+        test_function(&mut array, &mask);
+        let result: Vec<f32> = array.into();
+        let target = scan_reference(&data, &mask_data, identity, target_function);
+
+        for (r, t) in result.iter().zip(target.iter()) {
+            assert_approximate(*r, *t, 0.001);
+        }
+    }
+}
+
+#[rstest]
+#[case::cumsum(Array::cumsum, Array::cumsum_in_place)]
+#[case::cumprod(Array::cumprod, Array::cumprod_in_place)]
+#[case::cummax(Array::cummax, Array::cummax_in_place)]
+#[case::cummin(Array::cummin, Array::cummin_in_place)]
+fn ref_out_of_place(
+    #[case] test_function: fn(&Array<1>) -> Array<1>,
+    #[case] in_place_function: fn(&mut Array<1>),
+) {
+    for i in 0..64 {
+        let data = get_scan_data(0, i);
+        let array: Array<1> = data.clone().into();
+        let mut target = array.clone();
+        in_place_function(&mut target);
+
+        let result: Vec<f32> = test_function(&array).into();
+        let target: Vec<f32> = target.into();
+        let original: Vec<f32> = array.into();
+
+        assert_eq!(result, target);
+        assert_eq!(original, data);
+    }
+}
+
+#[rstest]
+#[case::cumsum(Array::cumsum_rows_in_place_masked, 0.0, f32::add)]
+#[case::cumprod(Array::cumprod_rows_in_place_masked, 1.0, f32::mul)]
+#[case::cummax(Array::cummax_rows_in_place_masked, f32::NEG_INFINITY, f32::max)]
+#[case::cummin(Array::cummin_rows_in_place_masked, f32::INFINITY, f32::min)]
+fn rows_in_place_masked(
+    #[case] test_function: fn(&mut Array<2>, &Mask<2>),
+    #[case] identity: f32,
+    #[case] target_function: fn(f32, f32) -> f32,
+) {
+    for i in 1..20 {
+        for j in 1..40 {
+            let data = get_scan_data(0, i * j);
+            let mask_data = get_random_bool_vec(1, i * j);
+            let mut array = Array::<2>::from_vec(&data, [i, j]);
+            let mask = Mask::<2>::from_vec(&mask_data, [i, j]);
+
+            test_function(&mut array, &mask);
+            let result: Vec<f32> = array.into();
+
+            for ((r, d), m) in result
+                .chunks(j)
+                .zip(data.chunks(j))
+                .zip(mask_data.chunks(j))
+            {
+                let target = scan_reference(d, m, identity, target_function);
+
+                for (r, t) in r.iter().zip(target.iter()) {
+                    assert_approximate(*r, *t, 0.001);
+                }
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::cumsum(Array::cumsum_rows_in_place, 0.0, f32::add)]
+#[case::cummax(Array::cummax_rows_in_place, f32::NEG_INFINITY, f32::max)]
+fn rows_in_place(
+    #[case] test_function: fn(&mut Array<2>),
+    #[case] identity: f32,
+    #[case] target_function: fn(f32, f32) -> f32,
+) {
+    for i in 1..20 {
+        for j in 1..40 {
+            let data = get_scan_data(0, i * j);
+            let mut array = Array::<2>::from_vec(&data, [i, j]);
+
+            test_function(&mut array);
+            let result: Vec<f32> = array.into();
+
+            for (r, d) in result.chunks(j).zip(data.chunks(j)) {
+                let target = scan_reference(d, &vec![true; j], identity, target_function);
+
+                for (r, t) in r.iter().zip(target.iter()) {
+                    assert_approximate(*r, *t, 0.001);
+                }
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::cumsum(Array::cumsum_columns_in_place_masked, 0.0, f32::add)]
+#[case::cumprod(Array::cumprod_columns_in_place_masked, 1.0, f32::mul)]
+#[case::cummax(Array::cummax_columns_in_place_masked, f32::NEG_INFINITY, f32::max)]
+#[case::cummin(Array::cummin_columns_in_place_masked, f32::INFINITY, f32::min)]
+fn columns_in_place_masked(
+    #[case] test_function: fn(&mut Array<2>, &Mask<2>),
+    #[case] identity: f32,
+    #[case] target_function: fn(f32, f32) -> f32,
+) {
+    for i in 1..20 {
+        for j in 1..40 {
+            let data = get_scan_data(0, i * j);
+            let mask_data = get_random_bool_vec(1, i * j);
+            let mut array = Array::<2>::from_vec(&data, [i, j]);
+            let mask = Mask::<2>::from_vec(&mask_data, [i, j]);
+
+            test_function(&mut array, &mask);
+
+            for c in 0..j {
+                let column: Vec<f32> = (0..i).map(|r| data[r * j + c]).collect();
+                let column_mask: Vec<bool> = (0..i).map(|r| mask_data[r * j + c]).collect();
+                let target = scan_reference(&column, &column_mask, identity, target_function);
+
+                for (r, t) in target.iter().enumerate() {
+                    assert_approximate(array.get(r, c), *t, 0.001);
+                }
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::cumsum(Array::cumsum_columns, 0.0, f32::add)]
+#[case::cummin(Array::cummin_columns, f32::INFINITY, f32::min)]
+fn columns(
+    #[case] test_function: fn(&Array<2>) -> Array<2>,
+    #[case] identity: f32,
+    #[case] target_function: fn(f32, f32) -> f32,
+) {
+    for i in 1..20 {
+        for j in 1..40 {
+            let data = get_scan_data(0, i * j);
+            let array = Array::<2>::from_vec(&data, [i, j]);
+
+            let result = test_function(&array);
+
+            for c in 0..j {
+                let column: Vec<f32> = (0..i).map(|r| data[r * j + c]).collect();
+                let target = scan_reference(&column, &vec![true; i], identity, target_function);
+
+                for (r, t) in target.iter().enumerate() {
+                    assert_approximate(result.get(r, c), *t, 0.001);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Mask};
+use utils::{get_random_bool_vec, get_random_f32_vec};
+
+#[test]
+fn select() {
+    for i in 0..64 {
+        let data1 = get_random_f32_vec(0, i);
+        let data2 = get_random_f32_vec(1, i);
+        let mask_data = get_random_bool_vec(2, i);
+
+        let array1: Array<1> = data1.clone().into();
+        let array2: Array<1> = data2.clone().into();
+        let mask: Mask<1> = mask_data.clone().into();
+
+        let result: Vec<f32> = Array::select(&mask, &array1, &array2).into();
+
+        for (((d1, d2), m), r) in data1
+            .iter()
+            .zip(data2.iter())
+            .zip(mask_data.iter())
+            .zip(result.iter())
+        {
+            assert_eq!(*r, if *m { *d1 } else { *d2 });
+        }
+    }
+}
+
+#[test]
+fn select_out_of_place() {
+    for i in 0..64 {
+        let data1 = get_random_f32_vec(0, i);
+        let data2 = get_random_f32_vec(1, i);
+        let mask_data = get_random_bool_vec(2, i);
+
+        let array1: Array<1> = data1.clone().into();
+        let array2: Array<1> = data2.clone().into();
+        let mask: Mask<1> = mask_data.clone().into();
+        let mut output = Array::new_from_value(&[i], 42.0);
+
+        Array::select_out_of_place(&mask, &array1, &array2, &mut output);
+        let result: Vec<f32> = output.into();
+
+        for (((d1, d2), m), r) in data1
+            .iter()
+            .zip(data2.iter())
+            .zip(mask_data.iter())
+            .zip(result.iter())
+        {
+            assert_eq!(*r, if *m { *d1 } else { *d2 });
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn select_shape_mismatch() {
+    let array1: Array<1> = get_random_f32_vec(0, 3).into();
+    let array2: Array<1> = get_random_f32_vec(1, 4).into();
+    let mask: Mask<1> = get_random_bool_vec(2, 3).into();
+
+    let _ = Array::select(&mask, &array1, &array2);
+}
+
+#[test]
+fn select_scalar() {
+    for i in 0..64 {
+        let data = get_random_f32_vec(0, i);
+        let mask_data = get_random_bool_vec(2, i);
+
+        let array: Array<1> = data.clone().into();
+        let mask: Mask<1> = mask_data.clone().into();
+
+        let result: Vec<f32> = Array::select_scalar(&mask, &array, 42.0).into();
+
+        let mut output = Array::zeros(&[i]);
+        Array::select_scalar_out_of_place(&mask, &array, 42.0, &mut output);
+        let result_out_of_place: Vec<f32> = output.into();
+
+        for ((d, m), (r1, r2)) in data
+            .iter()
+            .zip(mask_data.iter())
+            .zip(result.iter().zip(result_out_of_place.iter()))
+        {
+            let target = if *m { *d } else { 42.0 };
+
+            assert_eq!(*r1, target);
+            assert_eq!(*r2, target);
+        }
+    }
+}
+
+#[test]
+fn select_scalars() {
+    for i in 0..64 {
+        let mask_data = get_random_bool_vec(2, i);
+        let mask: Mask<1> = mask_data.clone().into();
+
+        let result: Vec<f32> = Array::select_scalars(&mask, 1.0, -1.0).into();
+
+        let mut output = Array::zeros(&[i]);
+        Array::select_scalars_out_of_place(&mask, 1.0, -1.0, &mut output);
+        let result_out_of_place: Vec<f32> = output.into();
+
+        for (m, (r1, r2)) in mask_data
+            .iter()
+            .zip(result.iter().zip(result_out_of_place.iter()))
+        {
+            let target = if *m { 1.0 } else { -1.0 };
+
+            assert_eq!(*r1, target);
+            assert_eq!(*r2, target);
+        }
+    }
+}
+
+#[test]
+fn select2d() {
+    for i in 1..32 {
+        for j in 1..32 {
+            let data1 = get_random_f32_vec(0, i * j);
+            let data2 = get_random_f32_vec(1, i * j);
+            let mask_data = get_random_bool_vec(2, i * j);
+
+            let array1 = Array::<2>::from_vec(&data1, [i, j]);
+            let array2 = Array::<2>::from_vec(&data2, [i, j]);
+            let mask = Mask::<2>::from_vec(&mask_data, [i, j]);
+
+            let result = Array::select(&mask, &array1, &array2);
+
+            for r in 0..i {
+                for c in 0..j {
+                    let index = r * j + c;
+                    let target = if mask_data[index] {
+                        data1[index]
+                    } else {
+                        data2[index]
+                    };
+
+                    assert_eq!(result.get(r, c), target);
+                }
+            }
+        }
+    }
+}
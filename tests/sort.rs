@@ -0,0 +1,197 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::Array;
+use utils::get_random_f32_vec;
+
+/// random data with duplicates, signed zeros, infinities and NaNs
+fn get_data_with_special_values(seed: u64, len: usize) -> Vec<f32> {
+    let special_values = [
+        f32::NAN,
+        -f32::NAN,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+        0.0,
+        -0.0,
+        1.0,
+    ];
+    let mut data = get_random_f32_vec(seed, len);
+
+    for (i, d) in data.iter_mut().enumerate() {
+        if i % 5 == 0 {
+            *d = special_values[(i / 5) % special_values.len()];
+        }
+    }
+
+    data
+}
+
+fn assert_same_bits(a: &[f32], b: &[f32]) {
+    assert_eq!(a.len(), b.len());
+
+    for (a, b) in a.iter().zip(b.iter()) {
+        assert_eq!(a.to_bits(), b.to_bits(), "{} != {}", a, b);
+    }
+}
+
+fn argsort_reference(data: &[f32]) -> Vec<u32> {
+    let mut indices: Vec<u32> = (0..data.len() as u32).collect();
+    indices.sort_by(|a, b| data[*a as usize].total_cmp(&data[*b as usize]));
+
+    indices
+}
+
+fn top_k_reference(data: &[f32], k: usize) -> Vec<u32> {
+    let mut indices: Vec<u32> = (0..data.len() as u32).collect();
+    indices.sort_by(|a, b| data[*b as usize].total_cmp(&data[*a as usize]));
+    indices.truncate(k);
+
+    indices
+}
+
+#[test]
+fn sort_in_place() {
+    for i in 0..200 {
+        for data in [get_random_f32_vec(0, i), get_data_with_special_values(1, i)] {
+            let mut array: Array<1> = data.clone().into();
+            array.sort_in_place();
+            let result: Vec<f32> = array.into();
+
+            let mut target = data.clone();
+            target.sort_by(f32::total_cmp);
+
+            assert_same_bits(&result, &target);
+        }
+    }
+}
+
+#[test]
+fn sort() {
+    for i in 0..64 {
+        let data = get_data_with_special_values(0, i);
+        let array: Array<1> = data.clone().into();
+        let result: Vec<f32> = array.sort().into();
+        let original: Vec<f32> = array.into();
+
+        let mut target = data.clone();
+        target.sort_by(f32::total_cmp);
+
+        assert_same_bits(&result, &target);
+        assert_same_bits(&original, &data);
+    }
+}
+
+#[test]
+fn argsort() {
+    for i in 0..200 {
+        for data in [get_random_f32_vec(0, i), get_data_with_special_values(1, i)] {
+            let array: Array<1> = data.clone().into();
+
+            assert_eq!(array.argsort(), argsort_reference(&data));
+        }
+    }
+}
+
+#[test]
+fn top_k() {
+    for i in 0..100 {
+        for k in 0..=i {
+            let data = get_data_with_special_values(0, i);
+            let array: Array<1> = data.clone().into();
+
+            let (values, indices) = array.top_k(k);
+            let values: Vec<f32> = values.into();
+            let target_indices = top_k_reference(&data, k);
+            let target_values: Vec<f32> =
+                target_indices.iter().map(|i| data[*i as usize]).collect();
+
+            assert_eq!(indices, target_indices);
+            assert_same_bits(&values, &target_values);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn top_k_too_large() {
+    let array: Array<1> = get_random_f32_vec(0, 5).into();
+    let _ = array.top_k(6);
+}
+
+#[test]
+fn sort_rows_in_place() {
+    for i in 1..20 {
+        for j in 1..40 {
+            let data = get_data_with_special_values(0, i * j);
+            let mut array = Array::<2>::from_vec(&data, [i, j]);
+            array.sort_rows_in_place();
+            let result: Vec<f32> = array.into();
+
+            let mut target = data.clone();
+
+            for row in target.chunks_mut(j) {
+                row.sort_by(f32::total_cmp);
+            }
+
+            assert_same_bits(&result, &target);
+        }
+    }
+}
+
+#[test]
+fn argsort_rows() {
+    for i in 1..20 {
+        for j in 1..40 {
+            let data = get_data_with_special_values(0, i * j);
+            let array = Array::<2>::from_vec(&data, [i, j]);
+
+            let target: Vec<u32> = data.chunks(j).flat_map(argsort_reference).collect();
+
+            assert_eq!(array.argsort_rows(), target);
+        }
+    }
+}
+
+#[test]
+fn top_k_rows() {
+    for i in 1..10 {
+        for j in 1..40 {
+            for k in [0, 1, j / 2, j] {
+                let data = get_data_with_special_values(0, i * j);
+                let array = Array::<2>::from_vec(&data, [i, j]);
+
+                let (values, indices) = array.top_k_rows(k);
+                assert_eq!(values.get_shape(), [i, k]);
+
+                let target_indices: Vec<u32> = data
+                    .chunks(j)
+                    .flat_map(|row| top_k_reference(row, k))
+                    .collect();
+
+                assert_eq!(indices, target_indices);
+
+                for r in 0..i {
+                    for c in 0..k {
+                        let target = data[r * j + target_indices[r * k + c] as usize];
+                        assert_eq!(values.get(r, c).to_bits(), target.to_bits());
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,444 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{assert_array_approx_eq, Array, ArrayView, ArrayViewMut, Error, Mask};
+use rstest::rstest;
+use utils::{assert_approximate, get_random_f32_vec};
+
+/// the column ranges that are tested, aligned and unaligned to the registers
+fn column_ranges(column_count: usize) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+
+    for start in [0, 1, 7, 15, 16, 17] {
+        for end in [
+            start,
+            start + 1,
+            start + 15,
+            start + 16,
+            start + 33,
+            column_count,
+        ] {
+            if start < end && end <= column_count {
+                ranges.push((start, end));
+            }
+        }
+    }
+
+    ranges
+}
+
+#[test]
+fn to_array1d() {
+    let data = get_random_f32_vec(0, 70);
+    let array: Array<1> = data.clone().into();
+
+    for (start, end) in column_ranges(70) {
+        let view: Vec<f32> = array.view(start..end).to_array().into();
+        assert_eq!(view, data[start..end]);
+    }
+}
+
+#[test]
+fn to_array2d() {
+    let data = get_random_f32_vec(0, 5 * 50);
+    let array = Array::from_vec(&data, [5, 50]);
+
+    for (start, end) in column_ranges(50) {
+        let view = array.view(1..4, start..end);
+        assert_eq!(view.get_shape(), [3, end - start]);
+
+        let copy = view.to_array();
+
+        for r in 0..3 {
+            for c in 0..end - start {
+                assert_eq!(copy.get(r, c), data[(r + 1) * 50 + start + c]);
+                assert_eq!(view.get(r, c), data[(r + 1) * 50 + start + c]);
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::add(Array::add, |a: ArrayView<2>, b: ArrayView<2>| a.add(&b))]
+#[case::sub(Array::sub, |a: ArrayView<2>, b: ArrayView<2>| a.sub(&b))]
+#[case::mul(Array::mul, |a: ArrayView<2>, b: ArrayView<2>| a.mul(&b))]
+#[case::div(Array::div, |a: ArrayView<2>, b: ArrayView<2>| a.div(&b))]
+#[case::max(Array::max, |a: ArrayView<2>, b: ArrayView<2>| a.max(&b))]
+#[case::min(Array::min, |a: ArrayView<2>, b: ArrayView<2>| a.min(&b))]
+fn binary2d(
+    #[case] array_op: fn(&Array<2>, &Array<2>) -> Array<2>,
+    #[case] view_op: fn(ArrayView<2>, ArrayView<2>) -> Array<2>,
+) {
+    let a = Array::from_vec(&get_random_f32_vec(0, 4 * 50), [4, 50]);
+    let b = Array::from_vec(&get_random_f32_vec(1, 4 * 60), [4, 60]);
+
+    for (start, end) in column_ranges(50) {
+        let a_view = a.view(0..3, start..end);
+        let b_view = b.view(1..4, 60 - (end - start)..60);
+
+        let expected = array_op(&a_view.to_array(), &b_view.to_array());
+        assert_eq!(view_op(a_view, b_view), expected);
+    }
+}
+
+#[rstest]
+#[case::add(Array::add_scalar_in_place, |a: &mut ArrayViewMut<2>, s| a.add_scalar_in_place(s))]
+#[case::sub(Array::sub_scalar_in_place, |a: &mut ArrayViewMut<2>, s| a.sub_scalar_in_place(s))]
+#[case::mul(Array::mul_scalar_in_place, |a: &mut ArrayViewMut<2>, s| a.mul_scalar_in_place(s))]
+#[case::div(Array::div_scalar_in_place, |a: &mut ArrayViewMut<2>, s| a.div_scalar_in_place(s))]
+#[case::max(Array::max_scalar_in_place, |a: &mut ArrayViewMut<2>, s| a.max_scalar_in_place(s))]
+#[case::min(Array::min_scalar_in_place, |a: &mut ArrayViewMut<2>, s| a.min_scalar_in_place(s))]
+fn scalar_in_place2d(
+    #[case] array_op: fn(&mut Array<2>, f32),
+    #[case] view_op: fn(&mut ArrayViewMut<2>, f32),
+) {
+    let data = get_random_f32_vec(0, 4 * 50);
+
+    for (start, end) in column_ranges(50) {
+        let mut array = Array::from_vec(&data, [4, 50]);
+        let mut expected = array.clone();
+        let mut region = array.view(1..3, start..end).to_array();
+        array_op(&mut region, 2.5);
+
+        view_op(&mut array.view_mut(1..3, start..end), 2.5);
+
+        // only the region changes
+        for r in 0..2 {
+            for c in 0..end - start {
+                expected.set(r + 1, c + start, region.get(r, c));
+            }
+        }
+
+        assert_eq!(array, expected);
+    }
+}
+
+#[test]
+fn in_place1d() {
+    let data = get_random_f32_vec(0, 70);
+    let other: Array<1> = get_random_f32_vec(1, 70).into();
+
+    for (start, end) in column_ranges(70) {
+        let mut array: Array<1> = data.clone().into();
+        array
+            .view_mut(start..end)
+            .mul_in_place(&other.view(70 - (end - start)..70));
+
+        let result: Vec<f32> = array.into();
+
+        for i in 0..70 {
+            if i >= start && i < end {
+                assert_eq!(
+                    result[i],
+                    data[i] * other.get(70 - (end - start) + i - start)
+                );
+            } else {
+                assert_eq!(result[i], data[i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn unary2d() {
+    let data = get_random_f32_vec(0, 3 * 40);
+    let array = Array::from_vec(&data, [3, 40]);
+
+    for (start, end) in column_ranges(40) {
+        let view = array.view(0..3, start..end);
+        let copy = view.to_array();
+
+        assert_eq!(view.abs(), copy.abs());
+        assert_eq!(view.square(), copy.square());
+
+        let mut other = array.clone();
+        other.view_mut(0..3, start..end).abs_in_place();
+        assert_eq!(other.view(0..3, start..end).to_array(), copy.abs());
+    }
+}
+
+#[test]
+fn exp_and_fmadd2d() {
+    let a = Array::from_vec(&get_random_f32_vec(0, 3 * 40), [3, 40]);
+    let b = Array::from_vec(&get_random_f32_vec(1, 3 * 40), [3, 40]);
+
+    for (start, end) in column_ranges(40) {
+        let a_view = a.view(0..3, start..end);
+        let b_view = b.view(0..3, start..end);
+        let a_copy = a_view.to_array();
+        let b_copy = b_view.to_array();
+
+        assert_array_approx_eq!(a_view.exp(), a_copy.exp(), rel <= 1e-6);
+        assert_eq!(
+            a_view.fmadd(&b_view, &b_view),
+            a_copy.fmadd(&b_copy, &b_copy)
+        );
+        assert_eq!(
+            a_view.fmadd_scalar(&b_view, 2.5),
+            a_copy.fmadd_scalar(&b_copy, 2.5)
+        );
+
+        let mut other = a.clone();
+        let mut view = other.view_mut(0..3, start..end);
+        view.exp_in_place();
+        view.fmadd_scalar_in_place(&b_view, 2.5);
+        assert_array_approx_eq!(
+            other.view(0..3, start..end).to_array(),
+            a_copy.exp().fmadd_scalar(&b_copy, 2.5),
+            rel <= 1e-6
+        );
+    }
+}
+
+#[rstest]
+#[case::add(Array::add_in_place_masked, |a: &mut ArrayViewMut<2>, b: &ArrayView<2>, m: &Mask<2>| a.add_in_place_masked(b, m))]
+#[case::sub(Array::sub_in_place_masked, |a: &mut ArrayViewMut<2>, b: &ArrayView<2>, m: &Mask<2>| a.sub_in_place_masked(b, m))]
+#[case::mul(Array::mul_in_place_masked, |a: &mut ArrayViewMut<2>, b: &ArrayView<2>, m: &Mask<2>| a.mul_in_place_masked(b, m))]
+#[case::div(Array::div_in_place_masked, |a: &mut ArrayViewMut<2>, b: &ArrayView<2>, m: &Mask<2>| a.div_in_place_masked(b, m))]
+#[case::max(Array::max_in_place_masked, |a: &mut ArrayViewMut<2>, b: &ArrayView<2>, m: &Mask<2>| a.max_in_place_masked(b, m))]
+#[case::min(Array::min_in_place_masked, |a: &mut ArrayViewMut<2>, b: &ArrayView<2>, m: &Mask<2>| a.min_in_place_masked(b, m))]
+#[case::add_scalar(|a: &mut Array<2>, _: &Array<2>, m: &Mask<2>| a.add_scalar_in_place_masked(2.5, m), |a: &mut ArrayViewMut<2>, _: &ArrayView<2>, m: &Mask<2>| a.add_scalar_in_place_masked(2.5, m))]
+#[case::div_scalar(|a: &mut Array<2>, _: &Array<2>, m: &Mask<2>| a.div_scalar_in_place_masked(2.5, m), |a: &mut ArrayViewMut<2>, _: &ArrayView<2>, m: &Mask<2>| a.div_scalar_in_place_masked(2.5, m))]
+#[case::sqrt(|a: &mut Array<2>, _: &Array<2>, m: &Mask<2>| a.sqrt_in_place_masked(m), |a: &mut ArrayViewMut<2>, _: &ArrayView<2>, m: &Mask<2>| a.sqrt_in_place_masked(m))]
+#[case::abs(|a: &mut Array<2>, _: &Array<2>, m: &Mask<2>| a.abs_in_place_masked(m), |a: &mut ArrayViewMut<2>, _: &ArrayView<2>, m: &Mask<2>| a.abs_in_place_masked(m))]
+#[case::fmadd(|a: &mut Array<2>, b: &Array<2>, m: &Mask<2>| a.fmadd_in_place_masked(b, b, m), |a: &mut ArrayViewMut<2>, b: &ArrayView<2>, m: &Mask<2>| a.fmadd_in_place_masked(b, b, m))]
+#[case::fmadd_scalar(|a: &mut Array<2>, b: &Array<2>, m: &Mask<2>| a.fmadd_scalar_in_place_masked(b, 2.5, m), |a: &mut ArrayViewMut<2>, b: &ArrayView<2>, m: &Mask<2>| a.fmadd_scalar_in_place_masked(b, 2.5, m))]
+fn masked_in_place2d(
+    #[case] array_op: fn(&mut Array<2>, &Array<2>, &Mask<2>),
+    #[case] view_op: fn(&mut ArrayViewMut<2>, &ArrayView<2>, &Mask<2>),
+) {
+    let data = get_random_f32_vec(0, 4 * 50);
+    let other = Array::from_vec(&get_random_f32_vec(1, 4 * 50), [4, 50]);
+
+    for (start, end) in column_ranges(50) {
+        let mut array = Array::from_vec(&data, [4, 50]);
+        let mut expected = array.clone();
+        let other_view = other.view(2..4, start..end);
+        let mut region = array.view(1..3, start..end).to_array();
+        let mask = region.compare_scalar_greater_than(0.5);
+        array_op(&mut region, &other_view.to_array(), &mask);
+
+        view_op(&mut array.view_mut(1..3, start..end), &other_view, &mask);
+
+        // only the set elements of the region change
+        for r in 0..2 {
+            for c in 0..end - start {
+                expected.set(r + 1, c + start, region.get(r, c));
+            }
+        }
+
+        assert_eq!(array, expected);
+    }
+}
+
+#[test]
+fn dot_product1d() {
+    let a: Array<1> = get_random_f32_vec(0, 70).into();
+    let b: Array<1> = get_random_f32_vec(1, 70).into();
+
+    for (start, end) in column_ranges(70) {
+        let a_view = a.view(start..end);
+        let b_view = b.view(70 - (end - start)..70);
+
+        assert_approximate(
+            a_view.dot_product(&b_view),
+            a_view.to_array().dot_product(&b_view.to_array()),
+            1e-4,
+        );
+    }
+}
+
+#[test]
+fn vector_multiplication2d() {
+    let matrix = Array::from_vec(&get_random_f32_vec(0, 5 * 50), [5, 50]);
+    let vector: Array<1> = get_random_f32_vec(1, 50).into();
+
+    for (start, end) in column_ranges(50) {
+        let view = matrix.view(1..5, start..end);
+        let vector_view = vector.view(start..end);
+
+        let result: Vec<f32> = view.vector_multiplication(&vector_view).into();
+        let expected: Vec<f32> = view
+            .to_array()
+            .vector_multiplication(&vector_view.to_array())
+            .into();
+
+        for (result, expected) in result.iter().zip(expected.iter()) {
+            assert_approximate(*result, *expected, 1e-4);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn masked_different_shapes() {
+    let mut array = Array::zeros(&[4, 50]);
+    let mask = Mask::zeros(&[4, 50]);
+    array
+        .view_mut(1..3, 0..50)
+        .add_scalar_in_place_masked(1.0, &mask);
+}
+
+#[test]
+fn reductions2d() {
+    let array = Array::from_vec(&get_random_f32_vec(0, 5 * 50), [5, 50]);
+
+    for (start, end) in column_ranges(50) {
+        let view = array.view(1..5, start..end);
+        let copy = view.to_array();
+
+        assert_approximate(view.sum(), copy.sum(), 1e-4);
+
+        assert_eq!(view.max_reduce(), copy.max_reduce());
+        assert_eq!(view.min_reduce(), copy.min_reduce());
+    }
+
+    let small = Array::from_vec(&vec![1.5; 8], [2, 4]);
+    assert_eq!(small.view(0..2, 1..3).product(), 1.5f32.powi(4));
+}
+
+#[test]
+fn reductions1d() {
+    let array: Array<1> = get_random_f32_vec(0, 70).into();
+
+    for (start, end) in column_ranges(70) {
+        let view = array.view(start..end);
+        let copy = view.to_array();
+
+        assert_approximate(view.sum(), copy.sum(), 1e-4);
+
+        assert_eq!(view.max_reduce(), copy.max_reduce());
+    }
+}
+
+#[test]
+fn comparisons2d() {
+    let a = Array::from_vec(&get_random_f32_vec(0, 3 * 50), [3, 50]);
+    let b = Array::from_vec(&get_random_f32_vec(1, 3 * 50), [3, 50]);
+
+    for (start, end) in column_ranges(50) {
+        let a_view = a.view(0..3, start..end);
+        let b_view = b.view(0..3, start..end);
+        let a_copy = a_view.to_array();
+        let b_copy = b_view.to_array();
+
+        assert_eq!(
+            a_view.compare_greater_than(&b_view),
+            a_copy.compare_greater_than(&b_copy)
+        );
+        assert_eq!(
+            a_view.compare_less_than_or_equal(&b_view),
+            a_copy.compare_less_than_or_equal(&b_copy)
+        );
+        assert_eq!(
+            a_view.compare_scalar_greater_than(0.0),
+            a_copy.compare_scalar_greater_than(0.0)
+        );
+        assert_eq!(
+            a_view.compare_scalar_equal(a.get(0, start.min(49))),
+            a_copy.compare_scalar_equal(a.get(0, start.min(49)))
+        );
+    }
+}
+
+#[test]
+fn copy_and_set_all() {
+    let source = Array::from_vec(&get_random_f32_vec(0, 3 * 20), [3, 20]);
+    let mut destination = Array::<2>::zeros(&[4, 40]);
+
+    destination
+        .view_mut(1..4, 17..37)
+        .copy(&source.view(0..3, 0..20));
+    assert_eq!(destination.view(1..4, 17..37).to_array(), source);
+
+    destination.view_mut(0..1, 3..5).set_all(7.0);
+    assert_eq!(destination.get(0, 2), 0.0);
+    assert_eq!(destination.get(0, 3), 7.0);
+    assert_eq!(destination.get(0, 4), 7.0);
+    assert_eq!(destination.get(0, 5), 0.0);
+    assert_array_approx_eq!(destination.as_view().to_array(), destination, ulps <= 0);
+}
+
+#[test]
+fn invalid_ranges() {
+    let array = Array::<2>::zeros(&[3, 20]);
+
+    assert_eq!(
+        array.try_view(0..4, 0..20).err(),
+        Some(Error::InvalidRange {
+            axis: 0,
+            start: 0,
+            end: 4,
+            length: 3
+        })
+    );
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = 5..2;
+    assert!(array.try_view(0..3, reversed).is_err());
+    assert!(array.try_view(2..3, 19..20).is_ok());
+    assert!(array.try_view(1..1, 0..20).is_ok());
+    assert!(array.try_view(3..3, 20..20).is_ok());
+    assert!(array.try_view(4..4, 0..20).is_err());
+}
+
+#[test]
+fn empty_views() {
+    let mut array = Array::<1>::from(get_random_f32_vec(0, 20));
+
+    for i in 0..=20 {
+        let view = array.view(i..i);
+        assert_eq!(view.get_shape(), [0]);
+        assert_eq!(view.to_array().get_shape(), [0]);
+        assert_eq!(view.sum(), 0.0);
+        assert_eq!(view.add_scalar(1.0).get_shape(), [0]);
+        assert_eq!(view.compare_scalar_greater_than(0.5).get_shape(), &[0]);
+
+        array.view_mut(i..i).set_all(7.0);
+    }
+
+    assert_eq!(array, Array::<1>::from(get_random_f32_vec(0, 20)));
+
+    let array = Array::from_vec(&get_random_f32_vec(0, 60), [3, 20]);
+
+    for (rows, columns, shape) in [
+        (1..1, 0..20, [0, 20]),
+        (0..3, 17..17, [3, 0]),
+        (3..3, 20..20, [0, 0]),
+    ] {
+        let view = array.view(rows, columns);
+        assert_eq!(view.get_shape(), shape);
+        assert_eq!(view.to_array().get_shape(), shape);
+        assert_eq!(view.sum(), 0.0);
+        assert_eq!(view.add(&view).get_shape(), shape);
+        assert_eq!(view.compare_scalar_greater_than(0.5).get_shape(), &shape);
+    }
+}
+
+#[test]
+#[should_panic]
+fn view_out_of_bounds() {
+    let array = Array::<1>::zeros(&[10]);
+    array.view(5..11);
+}
+
+#[test]
+#[should_panic]
+fn different_shapes() {
+    let a = Array::<2>::zeros(&[3, 20]);
+    a.view(0..2, 0..10).add(&a.view(0..2, 0..11));
+}
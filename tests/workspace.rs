@@ -0,0 +1,137 @@
+/*
+Copyright 2023 Philipp Wundrack
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+mod utils;
+
+use fast_arrays::{Array, Workspace};
+use utils::get_random_f32_vec;
+
+#[test]
+fn workspace_reuses_buffers() {
+    let mut workspace = Workspace::new();
+
+    let array = workspace.array(&[3, 40]);
+    assert_eq!(array.get_shape(), [3, 40]);
+    workspace.recycle_array(array);
+    assert_eq!(workspace.buffer_count(), 1);
+
+    // a smaller array fits into the buffer of the recycled one
+    let array = workspace.array_from_value(&[2, 17], 1.0);
+    assert_eq!(workspace.buffer_count(), 0);
+    assert_eq!(array, Array::new_from_value(&[2, 17], 1.0));
+
+    // a larger array needs a new buffer
+    let larger = workspace.array_from_value(&[50, 50], 2.0);
+    assert_eq!(larger, Array::new_from_value(&[50, 50], 2.0));
+    workspace.recycle_array(array);
+    workspace.recycle_array(larger);
+
+    let mut mask = workspace.mask(&[4, 33]);
+    assert_eq!(mask.number_of_true_elements(), 0);
+    mask.not_in_place();
+    workspace.recycle_mask(mask);
+
+    // recycled masks are cleared
+    let mask = workspace.mask(&[33]);
+    assert_eq!(mask.number_of_true_elements(), 0);
+    assert_eq!(workspace.buffer_count(), 2);
+
+    workspace.clear();
+    assert_eq!(workspace.buffer_count(), 0);
+}
+
+#[test]
+fn out_of_place_chain() {
+    let mut workspace = Workspace::new();
+    let a: Array<1> = get_random_f32_vec(0, 37).into();
+    let b: Array<1> = get_random_f32_vec(1, 37).into();
+    let expected = a.mul(&b).add_scalar(1.0).abs().sqrt().exp();
+
+    for _ in 0..3 {
+        let mut x = workspace.array(&[37]);
+        let mut y = workspace.array(&[37]);
+
+        a.mul_out_of_place(&b, &mut x);
+        x.add_scalar_out_of_place(1.0, &mut y);
+        y.abs_out_of_place(&mut x);
+        x.sqrt_out_of_place(&mut y);
+        y.exp_out_of_place(&mut x);
+        assert_eq!(x, expected);
+
+        workspace.recycle_array(x);
+        workspace.recycle_array(y);
+        assert_eq!(workspace.buffer_count(), 2);
+    }
+}
+
+#[test]
+fn matrix_multiplication_with_workspace() {
+    let mut workspace = Workspace::new();
+    let a = Array::from_vec(&get_random_f32_vec(0, 5 * 37), [5, 37]);
+    let b = Array::from_vec(&get_random_f32_vec(1, 37 * 20), [37, 20]);
+    let expected = a.matrix_multiplication(&b);
+    let mut output = Array::zeros(&[5, 20]);
+
+    a.matrix_multiplication_out_of_place_with_workspace(&b, &mut output, &mut workspace);
+    assert_eq!(output, expected);
+    let buffer_count = workspace.buffer_count();
+
+    // the temporary array of the first call is reused, so no further buffers are allocated
+    for _ in 0..3 {
+        output.set_all(0.0);
+        a.matrix_multiplication_out_of_place_with_workspace(&b, &mut output, &mut workspace);
+        assert_eq!(output, expected);
+        assert_eq!(workspace.buffer_count(), buffer_count);
+    }
+
+    assert!(a
+        .try_matrix_multiplication_out_of_place_with_workspace(
+            &b,
+            &mut Array::zeros(&[5, 21]),
+            &mut workspace
+        )
+        .is_err());
+}
+
+#[test]
+fn scalar_out_of_place() {
+    let a: Array<1> = get_random_f32_vec(0, 37).into();
+    let b: Array<1> = get_random_f32_vec(1, 37).into();
+    let mut output = Array::zeros(&[37]);
+
+    a.sub_scalar_out_of_place(0.5, &mut output);
+    assert_eq!(output, a.sub_scalar(0.5));
+    a.mul_scalar_out_of_place(0.5, &mut output);
+    assert_eq!(output, a.mul_scalar(0.5));
+    a.div_scalar_out_of_place(0.5, &mut output);
+    assert_eq!(output, a.div_scalar(0.5));
+    a.max_scalar_out_of_place(0.5, &mut output);
+    assert_eq!(output, a.max_scalar(0.5));
+    a.min_scalar_out_of_place(0.5, &mut output);
+    assert_eq!(output, a.min_scalar(0.5));
+
+    a.fmadd_out_of_place(&a, &b, &mut output);
+    assert_eq!(output, a.fmadd(&a, &b));
+    a.fmadd_scalar_out_of_place(&b, 0.5, &mut output);
+    assert_eq!(output, a.fmadd_scalar(&b, 0.5));
+}
+
+#[test]
+#[should_panic]
+fn out_of_place_different_shape() {
+    let a = Array::<2>::zeros(&[2, 3]);
+    a.sqrt_out_of_place(&mut Array::zeros(&[3, 2]));
+}